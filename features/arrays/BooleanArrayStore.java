@@ -0,0 +1,11 @@
+public class BooleanArrayStore {
+    public static void main(String[] args) {
+        boolean[] flags = new boolean[3];
+        flags[0] = true;
+        flags[2] = false;
+        flags[1] = !flags[2];
+        for (int i = 0; i < flags.length; i++) {
+            System.out.println(flags[i] ? "true" : "false");
+        }
+    }
+}
@@ -0,0 +1,11 @@
+public class ByteArrayLoad {
+    public static void main(String[] args) {
+        byte[] bytes = {1, 2, -3, 127, -128};
+        int sum = 0;
+        for (int i = 0; i < bytes.length; i++) {
+            sum += bytes[i];
+            System.out.println(bytes[i]);
+        }
+        System.out.println(sum);
+    }
+}
@@ -0,0 +1,8 @@
+public class ByteArrayStore {
+    public static void main(String[] args) {
+        byte[] bytes = new byte[4];
+        bytes[0] = 1;
+        bytes[3] = -128;
+        System.out.println(bytes.length);
+    }
+}
@@ -0,0 +1,10 @@
+public class LongArrayStore {
+    public static void main(String[] args) {
+        long[] longs = new long[3];
+        longs[1] = 5000000000L;
+        longs[2] = -1L;
+        for (int i = 0; i < longs.length; i++) {
+            System.out.println("longs[" + i + "] = " + longs[i]);
+        }
+    }
+}
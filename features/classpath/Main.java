@@ -0,0 +1,12 @@
+import shapes.Square;
+import util.Counter;
+
+// Square only comes from libs/shapes.jar, and Counter only from the extra directory.
+public class Main {
+    public static void main(String[] args) {
+        Counter counter = new Counter();
+        for (int side = 1; side <= 3; side++) {
+            System.out.println(counter.next() + ": " + new Square(side).area());
+        }
+    }
+}
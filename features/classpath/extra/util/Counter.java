@@ -0,0 +1,9 @@
+package util;
+
+public class Counter {
+    private int count;
+
+    public int next() {
+        return ++count;
+    }
+}
@@ -0,0 +1,64 @@
+// Arrays are always cloneable, and clone is shallow. Objects need to implement Cloneable.
+public class Clones {
+    static class Sheep implements Cloneable {
+        String name;
+        int[] wool;
+
+        Sheep(String name, int[] wool) {
+            this.name = name;
+            this.wool = wool;
+        }
+
+        @Override
+        public Sheep clone() {
+            try {
+                return (Sheep) super.clone();
+            } catch (CloneNotSupportedException e) {
+                throw new AssertionError(e);
+            }
+        }
+    }
+
+    static class Goat {
+        Object tryClone() throws CloneNotSupportedException {
+            return clone();
+        }
+    }
+
+    public static void main(String[] args) throws Exception {
+        int[] ints = {1, 2, 3};
+        int[] intsCopy = ints.clone();
+        intsCopy[0] = 100;
+        System.out.println(ints[0] + " " + intsCopy[0] + " " + intsCopy.length + " " + (ints != intsCopy));
+
+        long[] longs = {5L, 6L};
+        double[] doubles = {1.5, 2.5};
+        char[] chars = {'a', 'b'};
+        System.out.println(longs.clone()[1] + " " + doubles.clone()[0] + " " + chars.clone()[1]);
+
+        String[] strings = {"x", "y"};
+        String[] stringsCopy = strings.clone();
+        stringsCopy[1] = "z";
+        System.out.println(strings[1] + " " + stringsCopy[1] + " " + (strings[0] == stringsCopy[0]));
+
+        int[][] grid = {{1, 2}, {3, 4}};
+        int[][] gridCopy = grid.clone();
+        gridCopy[0][0] = 9;
+        gridCopy[1] = new int[] {7, 8};
+        System.out.println(grid[0][0] + " " + grid[1][0] + " " + gridCopy[1][0] + " " + (grid[0] == gridCopy[0]));
+
+        Sheep dolly = new Sheep("Dolly", new int[] {1, 1});
+        Sheep copy = dolly.clone();
+        copy.name = "Polly";
+        copy.wool[0] = 2;
+        System.out.println(dolly.name + " " + copy.name + " " + dolly.wool[0] + " " + (dolly != copy) + " " + (dolly.wool == copy.wool));
+        System.out.println(copy.getClass() == Sheep.class);
+
+        try {
+            new Goat().tryClone();
+            System.out.println("cloned a goat");
+        } catch (CloneNotSupportedException e) {
+            System.out.println("CloneNotSupportedException");
+        }
+    }
+}
@@ -0,0 +1,63 @@
+// Virtual and interface dispatch: overrides, default methods, and which default wins when there's more than one.
+public class Dispatch {
+    interface Animal {
+        String name();
+        default String speak() { return name() + " makes a sound"; }
+        default String describe() { return "animal " + name(); }
+    }
+
+    interface Pet extends Animal {
+        // Pet's default is more specific than Animal's, so it wins for anything that's a Pet.
+        default String describe() { return "pet " + name(); }
+    }
+
+    interface Loud {
+        default String shout() { return "LOUD"; }
+    }
+
+    static abstract class Base implements Animal {
+        public String name() { return "base"; }
+        String level() { return "Base"; }
+        // Private methods are never overridden, even by a method with the same name in a subclass.
+        private String secret() { return "Base secret"; }
+        String callSecret() { return secret(); }
+        static String kind() { return "static Base"; }
+    }
+
+    static class Dog extends Base implements Pet, Loud {
+        public String name() { return "dog"; }
+        public String speak() { return "woof, and " + Pet.super.speak(); }
+        String level() { return "Dog over " + super.level(); }
+        private String secret() { return "Dog secret"; }
+        static String kind() { return "static Dog"; }
+    }
+
+    static class Puppy extends Dog {
+        public String name() { return "puppy"; }
+        String level() { return "Puppy over " + super.level(); }
+        public String shout() { return "small " + super.shout(); }
+    }
+
+    static class Cat extends Base {
+        public String name() { return "cat"; }
+    }
+
+    public static void main(String[] args) {
+        Animal[] animals = { new Dog(), new Puppy(), new Cat() };
+        for (Animal a : animals) {
+            System.out.println(a.name() + ": " + a.speak() + " / " + a.describe());
+        }
+        for (Animal a : animals) {
+            Base b = (Base) a;
+            System.out.println(b.level() + " / " + b.callSecret() + " / " + (a instanceof Pet) + " / " + (a instanceof Loud));
+        }
+        Loud loud = new Puppy();
+        System.out.println(loud.shout() + " " + ((Loud) new Dog()).shout());
+        Pet pet = new Puppy();
+        System.out.println(pet.describe() + " " + pet.speak());
+        Base base = new Dog();
+        System.out.println(Base.kind() + " " + Dog.kind() + " " + base.name());
+        Object o = new Cat();
+        System.out.println(o.equals(o) + " " + o.equals(new Cat()));
+    }
+}
@@ -0,0 +1,28 @@
+public class NativeSuper {
+    static class Worker extends Thread {
+        int runs;
+        public void run() {
+            runs++;
+        }
+        public String name() {
+            return "worker";
+        }
+    }
+    static class Busy extends Worker {
+        public void run() {
+            runs += 10;
+        }
+    }
+    public static void main(String[] args) {
+        Worker w = new Worker();
+        Object o = w;
+        System.out.println(o.equals(o));
+        System.out.println(o.equals(new Object()));
+        System.out.println(o.hashCode() == System.identityHashCode(o));
+        Thread t = new Busy();
+        t.run();
+        Runnable r = w;
+        r.run();
+        System.out.println(w.runs + " " + ((Worker) t).runs + " " + w.name());
+    }
+}
@@ -0,0 +1,18 @@
+import java.util.function.IntSupplier;
+
+public class LambdaCycles {
+    static class Node {
+        IntSupplier size;
+        long[] payload = new long[1000];
+    }
+    public static void main(String[] args) {
+        int total = 0;
+        for (int i = 0; i < 20000; i++) {
+            // The lambda captures the node that holds it, so neither goes away without the collector.
+            Node node = new Node();
+            node.size = () -> node.payload.length;
+            total += node.size.getAsInt() > 0 ? 1 : 0;
+        }
+        System.out.println(total);
+    }
+}
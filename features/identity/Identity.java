@@ -0,0 +1,66 @@
+// The default hashCode and toString differ from run to run, so this only prints what they have to agree on.
+public class Identity {
+    static class Point {
+        final int x, y;
+
+        Point(int x, int y) {
+            this.x = x;
+            this.y = y;
+        }
+
+        @Override
+        public int hashCode() {
+            return 31 * x + y;
+        }
+
+        @Override
+        public boolean equals(Object o) {
+            return o instanceof Point && ((Point) o).x == x && ((Point) o).y == y;
+        }
+
+        @Override
+        public String toString() {
+            return "(" + x + ", " + y + ")";
+        }
+    }
+
+    // Same digits as Integer.toHexString.
+    static String hex(int n) {
+        char[] digits = {'0', '1', '2', '3', '4', '5', '6', '7', '8', '9', 'a', 'b', 'c', 'd', 'e', 'f'};
+        String out = "";
+        do {
+            out = digits[n & 15] + out;
+            n >>>= 4;
+        } while (n != 0);
+        return out;
+    }
+
+    public static void main(String[] args) {
+        Object a = new Object();
+        Object b = new Object();
+        System.out.println("stable: " + (a.hashCode() == a.hashCode()));
+        System.out.println("identity: " + (a.hashCode() == System.identityHashCode(a)));
+        System.out.println("distinct: " + (a.hashCode() != b.hashCode()));
+
+        String s = a.toString();
+        String hex = hex(a.hashCode());
+        System.out.println("toString: " + s.equals("java.lang.Object@" + hex));
+
+        Identity i = new Identity();
+        System.out.println("own class: " + i.toString().equals("Identity@" + hex(i.hashCode())));
+
+        Point p = new Point(3, 4);
+        Point q = new Point(3, 4);
+        System.out.println(p + " " + p.hashCode() + " " + (p.hashCode() == q.hashCode()) + " " + p.equals(q));
+        System.out.println("overridden identity: " + (System.identityHashCode(p) != System.identityHashCode(q)));
+
+        // Concatenation uses the runtime class, so the Object[] prints as the String[] it is.
+        int[] ints = new int[2];
+        Object[] strings = new String[1];
+        System.out.println("array: " + ("" + ints).equals("[I@" + hex(ints.hashCode())));
+        System.out.println("runtime class: " + ("" + strings).equals("[Ljava.lang.String;@" + hex(strings.hashCode())));
+
+        System.out.println("hello".hashCode() + " " + "".hashCode());
+        System.out.println("hello".toString());
+    }
+}
@@ -0,0 +1,32 @@
+import java.lang.invoke.CallSite;
+import java.lang.invoke.ConstantCallSite;
+import java.lang.invoke.MethodHandle;
+import java.lang.invoke.MethodHandles;
+import java.lang.invoke.MethodType;
+
+// Bootstrap methods and helpers for Indys, which is generated by indys.py since javac only emits invokedynamic for its own bootstraps.
+public class Bootstraps {
+    public static CallSite constant(MethodHandles.Lookup lookup, String name, MethodType type, MethodHandle target) {
+        return new ConstantCallSite(target);
+    }
+    // Uses its lookup to find the target, which has the call site's name and type.
+    public static CallSite found(MethodHandles.Lookup lookup, String name, MethodType type) throws ReflectiveOperationException {
+        System.out.println("linking " + name + " for " + lookup.lookupClass().getName());
+        return new ConstantCallSite(lookup.findStatic(Bootstraps.class, name, type));
+    }
+    public static Object boxed(MethodHandles.Lookup lookup, String name, Class<?> type, Object value) {
+        return value;
+    }
+    public static int twice(int x) {
+        return 2 * x;
+    }
+    public static int thrice(int x) {
+        return 3 * x;
+    }
+    public static void show(String what, int value) {
+        System.out.println(what + ": " + value);
+    }
+    public static void showLong(String what, long value) {
+        System.out.println(what + ": " + value);
+    }
+}
@@ -0,0 +1,23 @@
+// String concatenation of every type, including floats, which have to print like floats and not like doubles.
+public class Concat {
+    static class Thing {
+        public String toString() { return "a thing"; }
+    }
+
+    public static void main(String[] args) {
+        int i = -7;
+        long l = 1L << 40;
+        float f = 0.1f;
+        double d = 0.1;
+        char c = 'c';
+        boolean z = true;
+        byte b = -1;
+        short s = 300;
+        Object nothing = null;
+        System.out.println("i=" + i + " l=" + l + " f=" + f + " d=" + d + " c=" + c + " z=" + z + " b=" + b + " s=" + s);
+        System.out.println("null=" + nothing + " thing=" + new Thing() + " big=" + 1e10f + " tiny=" + 1e-5);
+        final float constant = 2.5f;
+        System.out.println("constant=" + constant + " sum=" + (f + constant) + " " + f + d);
+        System.out.println(i + l + "" + c + z);
+    }
+}
@@ -0,0 +1,38 @@
+import java.util.function.BiFunction;
+import java.util.function.Function;
+import java.util.function.IntBinaryOperator;
+import java.util.function.Supplier;
+
+// Lambdas that capture, bound and unbound method references, constructor references, and default methods on them.
+public class Lambdas {
+    private final String name;
+
+    Lambdas(String name) {
+        this.name = name;
+    }
+
+    String greet(String other) {
+        return name + " greets " + other;
+    }
+
+    static String shout(String s) {
+        return s + "!";
+    }
+
+    public static void main(String[] args) {
+        int base = 10;
+        IntBinaryOperator add = (a, b) -> a + b + base;
+        System.out.println(add.applyAsInt(1, 2));
+        Runnable run = () -> System.out.println("ran");
+        run.run();
+        Lambdas alice = new Lambdas("alice");
+        Function<String, String> bound = alice::greet;
+        BiFunction<Lambdas, String, String> unbound = Lambdas::greet;
+        Function<String, Lambdas> made = Lambdas::new;
+        System.out.println(bound.apply("bob") + ", " + unbound.apply(made.apply("carol"), "dave"));
+        Function<String, String> both = bound.andThen(Lambdas::shout);
+        System.out.println(both.apply("erin"));
+        Supplier<Supplier<String>> nested = () -> () -> "nested " + base;
+        System.out.println(nested.get().get());
+    }
+}
@@ -0,0 +1,40 @@
+import java.util.function.BiFunction;
+import java.util.function.Function;
+import java.util.function.IntFunction;
+import java.util.function.Supplier;
+import java.util.function.ToIntFunction;
+
+// Method references where the interface and the method don't agree on boxing, so the lambda has to convert.
+// The boxes passed in are made with their constructors, so the only boxing and unboxing is what the lambdas do.
+@SuppressWarnings("removal")
+public class MethodRefs {
+    static int negate(int x) {
+        return -x;
+    }
+    static long triple(long x) {
+        return x * 3;
+    }
+    static int seven() {
+        return 7;
+    }
+    static Integer boxed(int x) {
+        return new Integer(x + 1);
+    }
+
+    public static void main(String[] args) {
+        Function<Integer, Integer> abs = Math::abs;
+        System.out.println("abs: " + abs.apply(new Integer(-5)).intValue());
+        BiFunction<Integer, Integer, Integer> max = Math::max;
+        System.out.println("max: " + max.apply(new Integer(3), new Integer(9)).intValue());
+        Function<Integer, Integer> negate = MethodRefs::negate;
+        System.out.println("negate: " + negate.apply(new Integer(12)).intValue());
+        Supplier<Integer> seven = MethodRefs::seven;
+        System.out.println("seven: " + seven.get().intValue());
+        ToIntFunction<Integer> unbox = MethodRefs::negate;
+        System.out.println("unbox: " + unbox.applyAsInt(new Integer(8)));
+        IntFunction<Integer> box = MethodRefs::boxed;
+        System.out.println("box: " + box.apply(41).intValue());
+        Function<Integer, Long> widen = MethodRefs::triple;
+        System.out.println("widen: " + widen.apply(new Integer(5)).longValue());
+    }
+}
@@ -0,0 +1,36 @@
+// Records get toString, hashCode and equals from ObjectMethods.
+public class Records {
+    record Point(int x, int y) {}
+    record Everything(boolean z, byte b, char c, short s, int i, long j, float f, double d, String text, Point point) {}
+    record Empty() {}
+
+    // A String that's equal to "text" without being the same object.
+    static String text(String start) {
+        return start + "xt";
+    }
+
+    public static void main(String[] args) {
+        Point p = new Point(3, -4);
+        System.out.println(p.toString());
+        System.out.println("hash: " + p.hashCode());
+        System.out.println("equal: " + p.equals(new Point(3, -4)));
+        System.out.println("different: " + p.equals(new Point(4, 3)));
+        System.out.println("null: " + p.equals(null));
+        System.out.println("other type: " + p.equals("Point[x=3, y=-4]"));
+
+        Everything e = new Everything(true, (byte) 7, 'q', (short) 300, 42, 1234567890123L, 0.1f, 2.5, "text", p);
+        System.out.println(e.toString());
+        System.out.println("hash: " + e.hashCode());
+        System.out.println("equal: " + e.equals(new Everything(true, (byte) 7, 'q', (short) 300, 42, 1234567890123L, 0.1f, 2.5, text("te"), new Point(3, -4))));
+        System.out.println("different: " + e.equals(new Everything(true, (byte) 7, 'q', (short) 300, 42, 1234567890123L, 0.1f, 2.5, "text", new Point(3, 4))));
+
+        Everything nan = new Everything(false, (byte) 0, 'a', (short) 0, 0, 0L, Float.NaN, Double.NaN, null, null);
+        System.out.println(nan.toString());
+        System.out.println("hash: " + nan.hashCode());
+        System.out.println("nan equal: " + nan.equals(new Everything(false, (byte) 0, 'a', (short) 0, 0, 0L, Float.NaN, Double.NaN, null, null)));
+
+        System.out.println(new Empty().toString());
+        System.out.println("hash: " + new Empty().hashCode());
+        System.out.println("equal: " + new Empty().equals(new Empty()));
+    }
+}
@@ -0,0 +1,37 @@
+public class TypeSwitch {
+    sealed interface Shape permits Circle, Square {}
+    record Circle(int r) implements Shape {}
+    record Square(int side) implements Shape {}
+
+    static String describe(Object o) {
+        return switch (o) {
+            case null -> "null";
+            case String s && s.length() > 3 -> "long string " + s;
+            case String s -> "string " + s;
+            case Integer i -> "int " + i.intValue();
+            case int[] a -> "int array of " + a.length;
+            case Shape s -> "shape " + s.toString();
+            default -> "something else";
+        };
+    }
+
+    static int area(Shape s) {
+        return switch (s) {
+            case Circle c -> 3 * c.r() * c.r();
+            case Square q -> q.side() * q.side();
+        };
+    }
+
+    @SuppressWarnings("removal")
+    public static void main(String[] args) {
+        System.out.println(describe(null));
+        System.out.println(describe("hi"));
+        System.out.println(describe("hello"));
+        System.out.println(describe(new Integer(5)));
+        System.out.println(describe(new int[3]));
+        System.out.println(describe(new Circle(2)));
+        System.out.println(describe(new Object()));
+        System.out.println(area(new Circle(2)));
+        System.out.println(area(new Square(3)));
+    }
+}
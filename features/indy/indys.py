@@ -0,0 +1,97 @@
+#!/usr/bin/env python3
+# Writes Indys.class, which links an invokedynamic call site through Bootstraps.constant, a bootstrap method we don't know about.
+# It also loads an int and a long from dynamically-computed constants whose bootstrap method takes and returns an Object,
+# and links one more call site through Bootstraps.found, which finds its target with the Lookup it's given.
+import struct
+
+pool = []
+index = {}
+
+def add(key, data):
+    if key not in index:
+        pool.append(data)
+        index[key] = len(pool)
+        # Longs and doubles take two entries.
+        if data[0] in (5, 6):
+            pool.append(None)
+    return index[key]
+
+def utf8(s):
+    b = s.encode()
+    return add(('utf8', s), bytes([1]) + struct.pack('>H', len(b)) + b)
+def integer(i):
+    return add(('int', i), bytes([3]) + struct.pack('>i', i))
+def long(l):
+    return add(('long', l), bytes([5]) + struct.pack('>q', l))
+def cls(name):
+    return add(('class', name), bytes([7]) + struct.pack('>H', utf8(name)))
+def string(s):
+    return add(('string', s), bytes([8]) + struct.pack('>H', utf8(s)))
+def name_and_type(name, desc):
+    return add(('nat', name, desc), bytes([12]) + struct.pack('>HH', utf8(name), utf8(desc)))
+def method(owner, name, desc):
+    return add(('method', owner, name, desc), bytes([10]) + struct.pack('>HH', cls(owner), name_and_type(name, desc)))
+def method_handle(kind, ref):
+    return add(('mh', kind, ref), bytes([15, kind]) + struct.pack('>H', ref))
+def dynamic(bootstrap, name, desc):
+    return add(('condy', bootstrap, name, desc), bytes([17]) + struct.pack('>HH', bootstrap, name_and_type(name, desc)))
+def invoke_dynamic(bootstrap, name, desc):
+    return add(('indy', bootstrap, name, desc), bytes([18]) + struct.pack('>HH', bootstrap, name_and_type(name, desc)))
+
+REF_INVOKE_STATIC = 6
+LOOKUP = 'Ljava/lang/invoke/MethodHandles$Lookup;'
+constant = method_handle(REF_INVOKE_STATIC, method('Bootstraps', 'constant',
+    '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/invoke/MethodType;Ljava/lang/invoke/MethodHandle;)Ljava/lang/invoke/CallSite;'))
+boxed = method_handle(REF_INVOKE_STATIC, method('Bootstraps', 'boxed',
+    '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;Ljava/lang/Object;)Ljava/lang/Object;'))
+found = method_handle(REF_INVOKE_STATIC, method('Bootstraps', 'found',
+    '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/invoke/MethodType;)Ljava/lang/invoke/CallSite;'))
+bootstraps = [
+    (constant, [method_handle(REF_INVOKE_STATIC, method('Bootstraps', 'twice', '(I)I'))]),
+    (boxed, [integer(42)]),
+    (boxed, [long(1234567890123)]),
+    (found, []),
+]
+
+def ldc_w(i):
+    return bytes([0x13]) + struct.pack('>H', i)
+def ldc2_w(i):
+    return bytes([0x14]) + struct.pack('>H', i)
+def invokestatic(i):
+    return bytes([0xb8]) + struct.pack('>H', i)
+def invokedynamic(i):
+    return bytes([0xba]) + struct.pack('>HH', i, 0)
+
+show = method('Bootstraps', 'show', '(Ljava/lang/String;I)V')
+show_long = method('Bootstraps', 'showLong', '(Ljava/lang/String;J)V')
+
+code = b''
+code += ldc_w(string('call site')) + bytes([0x10, 21]) + invokedynamic(invoke_dynamic(0, 'twice', '(I)I')) + invokestatic(show)
+code += ldc_w(string('int constant')) + ldc_w(dynamic(1, 'answer', 'I')) + invokestatic(show)
+code += ldc_w(string('long constant')) + ldc2_w(dynamic(2, 'big', 'J')) + invokestatic(show_long)
+code += ldc_w(string('found')) + bytes([0x10, 7]) + invokedynamic(invoke_dynamic(3, 'thrice', '(I)I')) + invokestatic(show)
+code += bytes([0xb1])
+
+this = cls('Indys')
+sup = cls('java/lang/Object')
+main_name = utf8('main')
+main_desc = utf8('([Ljava/lang/String;)V')
+code_name = utf8('Code')
+bsm_name = utf8('BootstrapMethods')
+
+code_attr = struct.pack('>HHI', 4, 1, len(code)) + code + struct.pack('>HH', 0, 0)
+bsm_attr = struct.pack('>H', len(bootstraps))
+for ref, args in bootstraps:
+    bsm_attr += struct.pack('>HH', ref, len(args)) + b''.join(struct.pack('>H', a) for a in args)
+
+out_bytes = struct.pack('>IHH', 0xCAFEBABE, 0, 61)
+out_bytes += struct.pack('>H', len(pool) + 1) + b''.join(e for e in pool if e is not None)
+out_bytes += struct.pack('>HHH', 0x21, this, sup)
+out_bytes += struct.pack('>HH', 0, 0)
+out_bytes += struct.pack('>H', 1)
+out_bytes += struct.pack('>HHHH', 0x09, main_name, main_desc, 1)
+out_bytes += struct.pack('>HI', code_name, len(code_attr)) + code_attr
+out_bytes += struct.pack('>H', 1) + struct.pack('>HI', bsm_name, len(bsm_attr)) + bsm_attr
+
+with open('Indys.class', 'wb') as f:
+    f.write(out_bytes)
@@ -0,0 +1,65 @@
+// When static initializers run, and what happens when one throws.
+public class Init {
+    static int zero = 0;
+
+    static class Parent {
+        static { System.out.println("Parent <clinit>"); }
+        static int parentValue = 1;
+    }
+
+    static class Child extends Parent {
+        static { System.out.println("Child <clinit>"); }
+        static int childValue = 2;
+        static final int CONSTANT = 3;
+        static void hello() { System.out.println("Child.hello"); }
+    }
+
+    interface Named {
+        String NAME = make("Named.NAME");
+        static String make(String s) {
+            System.out.println("Named <clinit>");
+            return s;
+        }
+    }
+
+    static class Implementer implements Named {
+        static { System.out.println("Implementer <clinit>"); }
+    }
+
+    static class Built {
+        static { System.out.println("Built <clinit>"); }
+        Built() { System.out.println("Built()"); }
+    }
+
+    static class Broken {
+        static int value = 10 / zero;
+    }
+
+    public static void main(String[] args) {
+        System.out.println("main");
+        // Constants are inlined, and arrays and class literals aren't active uses.
+        System.out.println(Child.CONSTANT);
+        Child[] children = new Child[2];
+        Class<?> c = Child.class;
+        System.out.println(children.length + " " + (c != null));
+        // A superclass gets initialized first, but only once.
+        System.out.println(Child.childValue);
+        System.out.println(Parent.parentValue);
+        Child.hello();
+        // Initializing a class doesn't initialize the interfaces it implements.
+        new Implementer();
+        System.out.println(Named.NAME);
+        new Built();
+        new Built();
+        try {
+            System.out.println(Broken.value);
+        } catch (ExceptionInInitializerError e) {
+            System.out.println("ExceptionInInitializerError caused by " + e.getCause().getClass().getName());
+        }
+        try {
+            System.out.println(Broken.value);
+        } catch (NoClassDefFoundError e) {
+            System.out.println("NoClassDefFoundError the second time");
+        }
+    }
+}
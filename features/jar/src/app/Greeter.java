@@ -0,0 +1,13 @@
+package app;
+
+public class Greeter {
+    private final String name;
+
+    public Greeter(String name) {
+        this.name = name;
+    }
+
+    public String greet() {
+        return "Hello from the " + name + "!";
+    }
+}
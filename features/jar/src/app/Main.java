@@ -0,0 +1,10 @@
+package app;
+
+// Run from a jar, which has to find Main through the manifest and Greeter through the jar itself.
+public class Main {
+    public static void main(String[] args) {
+        Greeter greeter = new Greeter("jar");
+        System.out.println(greeter.greet());
+        System.out.println(Main.class.getName());
+    }
+}
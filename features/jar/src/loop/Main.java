@@ -0,0 +1,8 @@
+package loop;
+
+// Loop.jar's manifest has "Class-Path: ../jar/Loop.jar", so the jar names itself.
+public class Main {
+    public static void main(String[] args) {
+        System.out.println("Loaded from a jar that's on its own Class-Path");
+    }
+}
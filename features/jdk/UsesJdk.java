@@ -0,0 +1,13 @@
+import java.util.ArrayList;
+
+// Everything this uses comes from java.base, so it only runs if the JDK's classes could be loaded.
+public class UsesJdk {
+    public static void main(String[] args) {
+        System.out.println("concatenated " + 42);
+        ArrayList<String> list = new ArrayList<>();
+        list.add("a");
+        list.add("b");
+        System.out.println(list.size() + " " + list.get(1));
+        System.out.println(Math.max(3, 7) + " " + Math.abs(-2.5));
+    }
+}
@@ -0,0 +1,19 @@
+import java.lang.invoke.MethodHandles;
+
+// Helpers for Ldcs, which is generated by ldcs.py since javac never loads these constants with ldc.
+public class Check {
+    public static void same(String what, Object a, Object b) {
+        System.out.println(what + ": " + (a == b));
+    }
+    public static Object make(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        return new Object();
+    }
+    public static long makeLong(MethodHandles.Lookup lookup, String name, Class<?> type) {
+        return 1234567890123L;
+    }
+    public static void show(long value) {
+        System.out.println("long: " + value);
+    }
+    public static void target() {
+    }
+}
@@ -0,0 +1,84 @@
+#!/usr/bin/env python3
+# Writes Ldcs.class, which loads the same MethodHandle, MethodType and dynamically-computed constants
+# with two different ldc instructions each, and has Check.same print whether both gave back the same object.
+# It also loads a long from a dynamically-computed constant with ldc2_w.
+import struct
+
+pool = []
+index = {}
+
+def add(key, data):
+    if key not in index:
+        pool.append(data)
+        index[key] = len(pool)
+        # Longs and doubles take two entries.
+        if data[0] in (5, 6):
+            pool.append(None)
+    return index[key]
+
+def utf8(s):
+    b = s.encode()
+    return add(('utf8', s), bytes([1]) + struct.pack('>H', len(b)) + b)
+def cls(name):
+    return add(('class', name), bytes([7]) + struct.pack('>H', utf8(name)))
+def string(s):
+    return add(('string', s), bytes([8]) + struct.pack('>H', utf8(s)))
+def name_and_type(name, desc):
+    return add(('nat', name, desc), bytes([12]) + struct.pack('>HH', utf8(name), utf8(desc)))
+def method(owner, name, desc):
+    return add(('method', owner, name, desc), bytes([10]) + struct.pack('>HH', cls(owner), name_and_type(name, desc)))
+def method_handle(kind, ref):
+    return add(('mh', kind, ref), bytes([15, kind]) + struct.pack('>H', ref))
+def method_type(desc):
+    return add(('mt', desc), bytes([16]) + struct.pack('>H', utf8(desc)))
+def dynamic(bootstrap, name, desc):
+    return add(('condy', bootstrap, name, desc), bytes([17]) + struct.pack('>HH', bootstrap, name_and_type(name, desc)))
+
+REF_INVOKE_STATIC = 6
+LOOKUP = 'Ljava/lang/invoke/MethodHandles$Lookup;'
+bootstraps = [
+    (method_handle(REF_INVOKE_STATIC, method('Check', 'make', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)Ljava/lang/Object;')), []),
+    (method_handle(REF_INVOKE_STATIC, method('Check', 'makeLong', '(' + LOOKUP + 'Ljava/lang/String;Ljava/lang/Class;)J')), []),
+]
+
+def ldc(i):
+    return bytes([0x13]) + struct.pack('>H', i)
+def invokestatic(i):
+    return bytes([0xb8]) + struct.pack('>H', i)
+
+same = method('Check', 'same', '(Ljava/lang/String;Ljava/lang/Object;Ljava/lang/Object;)V')
+handle = method_handle(REF_INVOKE_STATIC, method('Check', 'target', '()V'))
+mtype = method_type('(I)Ljava/lang/String;')
+condy = dynamic(0, 'object', 'Ljava/lang/Object;')
+condy_long = dynamic(1, 'number', 'J')
+show = method('Check', 'show', '(J)V')
+
+code = b''
+for name, constant in (('handle', handle), ('type', mtype), ('dynamic', condy)):
+    code += ldc(string(name)) + ldc(constant) + ldc(constant) + invokestatic(same)
+code += bytes([0x14]) + struct.pack('>H', condy_long) + invokestatic(show)
+code += bytes([0xb1])
+
+this = cls('Ldcs')
+sup = cls('java/lang/Object')
+main_name = utf8('main')
+main_desc = utf8('([Ljava/lang/String;)V')
+code_name = utf8('Code')
+bsm_name = utf8('BootstrapMethods')
+
+code_attr = struct.pack('>HHI', 4, 1, len(code)) + code + struct.pack('>HH', 0, 0)
+bsm_attr = struct.pack('>H', len(bootstraps))
+for ref, args in bootstraps:
+    bsm_attr += struct.pack('>HH', ref, len(args)) + b''.join(struct.pack('>H', a) for a in args)
+
+out_bytes = struct.pack('>IHH', 0xCAFEBABE, 0, 61)
+out_bytes += struct.pack('>H', len(pool) + 1) + b''.join(e for e in pool if e is not None)
+out_bytes += struct.pack('>HHH', 0x21, this, sup)
+out_bytes += struct.pack('>HH', 0, 0)
+out_bytes += struct.pack('>H', 1)
+out_bytes += struct.pack('>HHHH', 0x09, main_name, main_desc, 1)
+out_bytes += struct.pack('>HI', code_name, len(code_attr)) + code_attr
+out_bytes += struct.pack('>H', 1) + struct.pack('>HI', bsm_name, len(bsm_attr)) + bsm_attr
+
+with open('Ldcs.class', 'wb') as f:
+    f.write(out_bytes)
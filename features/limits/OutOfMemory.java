@@ -0,0 +1,28 @@
+// Run with -Xmx16m. Keeps everything it allocates reachable until the heap runs out, and then lets it all go.
+public class OutOfMemory {
+    public static void main(String[] args) {
+        Object[] head = null;
+        int chunks = 0;
+        try {
+            while (true) {
+                Object[] node = new Object[2];
+                node[0] = head;
+                node[1] = new long[16 * 1024];
+                head = node;
+                chunks++;
+            }
+        } catch (OutOfMemoryError e) {
+            head = null;
+            System.out.println("OutOfMemoryError after more than 100 chunks: " + (chunks > 100));
+        }
+        try {
+            long[] huge = new long[256 * 1024 * 1024];
+            System.out.println(huge.length);
+        } catch (OutOfMemoryError e) {
+            System.out.println("OutOfMemoryError for one big array");
+        }
+        // Once the garbage is gone, there's room again.
+        long[] after = new long[16 * 1024];
+        System.out.println("allocated " + after.length + " after");
+    }
+}
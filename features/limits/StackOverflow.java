@@ -0,0 +1,28 @@
+// Run with -Xss1m. Recursion that never ends throws StackOverflowError, and the thread can carry on after catching it.
+public class StackOverflow {
+    static int depth;
+
+    static void recurse() {
+        depth++;
+        recurse();
+    }
+
+    static int fib(int n) {
+        return n < 2 ? n : fib(n - 1) + fib(n - 2);
+    }
+
+    public static void main(String[] args) {
+        try {
+            recurse();
+        } catch (StackOverflowError e) {
+            System.out.println("StackOverflowError deeper than 1000: " + (depth > 1000));
+        }
+        System.out.println("fib after: " + fib(20));
+        depth = 0;
+        try {
+            recurse();
+        } catch (StackOverflowError e) {
+            System.out.println("and again: " + (depth > 1000));
+        }
+    }
+}
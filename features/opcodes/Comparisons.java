@@ -0,0 +1,36 @@
+// lcmp, fcmpl/g, dcmpl/g and every conditional branch, including what NaN does to each of them.
+public class Comparisons {
+    static String ints(int a, int b) {
+        return "" + (a == b) + (a != b) + (a < b) + (a >= b) + (a > b) + (a <= b);
+    }
+
+    static String zero(int a) {
+        return "" + (a == 0) + (a != 0) + (a < 0) + (a >= 0) + (a > 0) + (a <= 0);
+    }
+
+    static String longs(long a, long b) {
+        return "" + (a == b) + (a != b) + (a < b) + (a >= b) + (a > b) + (a <= b);
+    }
+
+    static String floats(float a, float b) {
+        return "" + (a == b) + (a != b) + (a < b) + (a >= b) + (a > b) + (a <= b);
+    }
+
+    static String doubles(double a, double b) {
+        return "" + (a == b) + (a != b) + (a < b) + (a >= b) + (a > b) + (a <= b);
+    }
+
+    static String references(Object a, Object b) {
+        return "" + (a == b) + (a != b) + (a == null) + (a != null);
+    }
+
+    public static void main(String[] args) {
+        System.out.println(ints(1, 2) + " " + ints(2, 2) + " " + ints(3, -2));
+        System.out.println(zero(-1) + " " + zero(0) + " " + zero(1));
+        System.out.println(longs(1L << 40, 1L << 41) + " " + longs(5, 5) + " " + longs(-1, Long.MIN_VALUE));
+        System.out.println(floats(1.5f, 2.5f) + " " + floats(0f, -0f) + " " + floats(Float.NaN, 1f) + " " + floats(1f, Float.NaN));
+        System.out.println(doubles(1.5, 2.5) + " " + doubles(0.0, -0.0) + " " + doubles(Double.NaN, 1.0) + " " + doubles(1.0, Double.NaN));
+        Object o = new Object();
+        System.out.println(references(o, o) + " " + references(o, new Object()) + " " + references(null, o));
+    }
+}
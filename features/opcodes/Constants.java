@@ -0,0 +1,294 @@
+// aconst_null, iconst_*, lconst_*, fconst_*, dconst_*, bipush, sipush, ldc, ldc_w and ldc2_w.
+public class Constants {
+    static int[] many = new int[300];
+    static String s0 = "padding 0";
+    static String s1 = "padding 1";
+    static String s2 = "padding 2";
+    static String s3 = "padding 3";
+    static String s4 = "padding 4";
+    static String s5 = "padding 5";
+    static String s6 = "padding 6";
+    static String s7 = "padding 7";
+    static String s8 = "padding 8";
+    static String s9 = "padding 9";
+    static String s10 = "padding 10";
+    static String s11 = "padding 11";
+    static String s12 = "padding 12";
+    static String s13 = "padding 13";
+    static String s14 = "padding 14";
+    static String s15 = "padding 15";
+    static String s16 = "padding 16";
+    static String s17 = "padding 17";
+    static String s18 = "padding 18";
+    static String s19 = "padding 19";
+    static String s20 = "padding 20";
+    static String s21 = "padding 21";
+    static String s22 = "padding 22";
+    static String s23 = "padding 23";
+    static String s24 = "padding 24";
+    static String s25 = "padding 25";
+    static String s26 = "padding 26";
+    static String s27 = "padding 27";
+    static String s28 = "padding 28";
+    static String s29 = "padding 29";
+    static String s30 = "padding 30";
+    static String s31 = "padding 31";
+    static String s32 = "padding 32";
+    static String s33 = "padding 33";
+    static String s34 = "padding 34";
+    static String s35 = "padding 35";
+    static String s36 = "padding 36";
+    static String s37 = "padding 37";
+    static String s38 = "padding 38";
+    static String s39 = "padding 39";
+    static String s40 = "padding 40";
+    static String s41 = "padding 41";
+    static String s42 = "padding 42";
+    static String s43 = "padding 43";
+    static String s44 = "padding 44";
+    static String s45 = "padding 45";
+    static String s46 = "padding 46";
+    static String s47 = "padding 47";
+    static String s48 = "padding 48";
+    static String s49 = "padding 49";
+    static String s50 = "padding 50";
+    static String s51 = "padding 51";
+    static String s52 = "padding 52";
+    static String s53 = "padding 53";
+    static String s54 = "padding 54";
+    static String s55 = "padding 55";
+    static String s56 = "padding 56";
+    static String s57 = "padding 57";
+    static String s58 = "padding 58";
+    static String s59 = "padding 59";
+    static String s60 = "padding 60";
+    static String s61 = "padding 61";
+    static String s62 = "padding 62";
+    static String s63 = "padding 63";
+    static String s64 = "padding 64";
+    static String s65 = "padding 65";
+    static String s66 = "padding 66";
+    static String s67 = "padding 67";
+    static String s68 = "padding 68";
+    static String s69 = "padding 69";
+    static String s70 = "padding 70";
+    static String s71 = "padding 71";
+    static String s72 = "padding 72";
+    static String s73 = "padding 73";
+    static String s74 = "padding 74";
+    static String s75 = "padding 75";
+    static String s76 = "padding 76";
+    static String s77 = "padding 77";
+    static String s78 = "padding 78";
+    static String s79 = "padding 79";
+    static String s80 = "padding 80";
+    static String s81 = "padding 81";
+    static String s82 = "padding 82";
+    static String s83 = "padding 83";
+    static String s84 = "padding 84";
+    static String s85 = "padding 85";
+    static String s86 = "padding 86";
+    static String s87 = "padding 87";
+    static String s88 = "padding 88";
+    static String s89 = "padding 89";
+    static String s90 = "padding 90";
+    static String s91 = "padding 91";
+    static String s92 = "padding 92";
+    static String s93 = "padding 93";
+    static String s94 = "padding 94";
+    static String s95 = "padding 95";
+    static String s96 = "padding 96";
+    static String s97 = "padding 97";
+    static String s98 = "padding 98";
+    static String s99 = "padding 99";
+    static String s100 = "padding 100";
+    static String s101 = "padding 101";
+    static String s102 = "padding 102";
+    static String s103 = "padding 103";
+    static String s104 = "padding 104";
+    static String s105 = "padding 105";
+    static String s106 = "padding 106";
+    static String s107 = "padding 107";
+    static String s108 = "padding 108";
+    static String s109 = "padding 109";
+    static String s110 = "padding 110";
+    static String s111 = "padding 111";
+    static String s112 = "padding 112";
+    static String s113 = "padding 113";
+    static String s114 = "padding 114";
+    static String s115 = "padding 115";
+    static String s116 = "padding 116";
+    static String s117 = "padding 117";
+    static String s118 = "padding 118";
+    static String s119 = "padding 119";
+    static String s120 = "padding 120";
+    static String s121 = "padding 121";
+    static String s122 = "padding 122";
+    static String s123 = "padding 123";
+    static String s124 = "padding 124";
+    static String s125 = "padding 125";
+    static String s126 = "padding 126";
+    static String s127 = "padding 127";
+    static String s128 = "padding 128";
+    static String s129 = "padding 129";
+    static String s130 = "padding 130";
+    static String s131 = "padding 131";
+    static String s132 = "padding 132";
+    static String s133 = "padding 133";
+    static String s134 = "padding 134";
+    static String s135 = "padding 135";
+    static String s136 = "padding 136";
+    static String s137 = "padding 137";
+    static String s138 = "padding 138";
+    static String s139 = "padding 139";
+    static String s140 = "padding 140";
+    static String s141 = "padding 141";
+    static String s142 = "padding 142";
+    static String s143 = "padding 143";
+    static String s144 = "padding 144";
+    static String s145 = "padding 145";
+    static String s146 = "padding 146";
+    static String s147 = "padding 147";
+    static String s148 = "padding 148";
+    static String s149 = "padding 149";
+    static String s150 = "padding 150";
+    static String s151 = "padding 151";
+    static String s152 = "padding 152";
+    static String s153 = "padding 153";
+    static String s154 = "padding 154";
+    static String s155 = "padding 155";
+    static String s156 = "padding 156";
+    static String s157 = "padding 157";
+    static String s158 = "padding 158";
+    static String s159 = "padding 159";
+    static String s160 = "padding 160";
+    static String s161 = "padding 161";
+    static String s162 = "padding 162";
+    static String s163 = "padding 163";
+    static String s164 = "padding 164";
+    static String s165 = "padding 165";
+    static String s166 = "padding 166";
+    static String s167 = "padding 167";
+    static String s168 = "padding 168";
+    static String s169 = "padding 169";
+    static String s170 = "padding 170";
+    static String s171 = "padding 171";
+    static String s172 = "padding 172";
+    static String s173 = "padding 173";
+    static String s174 = "padding 174";
+    static String s175 = "padding 175";
+    static String s176 = "padding 176";
+    static String s177 = "padding 177";
+    static String s178 = "padding 178";
+    static String s179 = "padding 179";
+    static String s180 = "padding 180";
+    static String s181 = "padding 181";
+    static String s182 = "padding 182";
+    static String s183 = "padding 183";
+    static String s184 = "padding 184";
+    static String s185 = "padding 185";
+    static String s186 = "padding 186";
+    static String s187 = "padding 187";
+    static String s188 = "padding 188";
+    static String s189 = "padding 189";
+    static String s190 = "padding 190";
+    static String s191 = "padding 191";
+    static String s192 = "padding 192";
+    static String s193 = "padding 193";
+    static String s194 = "padding 194";
+    static String s195 = "padding 195";
+    static String s196 = "padding 196";
+    static String s197 = "padding 197";
+    static String s198 = "padding 198";
+    static String s199 = "padding 199";
+    static String s200 = "padding 200";
+    static String s201 = "padding 201";
+    static String s202 = "padding 202";
+    static String s203 = "padding 203";
+    static String s204 = "padding 204";
+    static String s205 = "padding 205";
+    static String s206 = "padding 206";
+    static String s207 = "padding 207";
+    static String s208 = "padding 208";
+    static String s209 = "padding 209";
+    static String s210 = "padding 210";
+    static String s211 = "padding 211";
+    static String s212 = "padding 212";
+    static String s213 = "padding 213";
+    static String s214 = "padding 214";
+    static String s215 = "padding 215";
+    static String s216 = "padding 216";
+    static String s217 = "padding 217";
+    static String s218 = "padding 218";
+    static String s219 = "padding 219";
+    static String s220 = "padding 220";
+    static String s221 = "padding 221";
+    static String s222 = "padding 222";
+    static String s223 = "padding 223";
+    static String s224 = "padding 224";
+    static String s225 = "padding 225";
+    static String s226 = "padding 226";
+    static String s227 = "padding 227";
+    static String s228 = "padding 228";
+    static String s229 = "padding 229";
+    static String s230 = "padding 230";
+    static String s231 = "padding 231";
+    static String s232 = "padding 232";
+    static String s233 = "padding 233";
+    static String s234 = "padding 234";
+    static String s235 = "padding 235";
+    static String s236 = "padding 236";
+    static String s237 = "padding 237";
+    static String s238 = "padding 238";
+    static String s239 = "padding 239";
+    static String s240 = "padding 240";
+    static String s241 = "padding 241";
+    static String s242 = "padding 242";
+    static String s243 = "padding 243";
+    static String s244 = "padding 244";
+    static String s245 = "padding 245";
+    static String s246 = "padding 246";
+    static String s247 = "padding 247";
+    static String s248 = "padding 248";
+    static String s249 = "padding 249";
+    static String s250 = "padding 250";
+    static String s251 = "padding 251";
+    static String s252 = "padding 252";
+    static String s253 = "padding 253";
+    static String s254 = "padding 254";
+    static String s255 = "padding 255";
+    static String s256 = "padding 256";
+    static String s257 = "padding 257";
+    static String s258 = "padding 258";
+    static String s259 = "padding 259";
+
+    public static void main(String[] args) {
+        Object nothing = null;
+        System.out.println(nothing == null);
+        System.out.println(-1 + " " + 0 + " " + 1 + " " + 2 + " " + 3 + " " + 4 + " " + 5);
+        long l0 = 0L, l1 = 1L;
+        System.out.println(l0 + " " + l1);
+        float f0 = 0f, f1 = 1f, f2 = 2f;
+        System.out.println(f0 + " " + f1 + " " + f2);
+        double d0 = 0d, d1 = 1d;
+        System.out.println(d0 + " " + d1);
+        byte b = -128;
+        short s = 32767;
+        System.out.println(b + " " + s + " " + (byte) 100 + " " + (short) -1000);
+        int big = 123456789;
+        float f = 2.5f;
+        String str = "a constant string";
+        System.out.println(big + " " + f + " " + str);
+        long bigLong = 1234567890123456789L;
+        double pi = 3.141592653589793;
+        System.out.println(bigLong + " " + pi);
+        System.out.println(Long.MIN_VALUE + " " + Integer.MIN_VALUE + " " + Double.MAX_VALUE);
+        System.out.println(Constants.class == Constants.class);
+        // Enough constants that some of them need ldc_w.
+        int total = s259.length();
+        total += 100000 + 100001 + 100002 + 100003 + 100004 + 100005 + 100006 + 100007 + 100008 + 100009;
+        total += 100010 + 100011 + 100012 + 100013 + 100014 + 100015 + 100016 + 100017 + 100018 + 100019;
+        total += 100020 + 100021 + 100022 + 100023 + 100024 + 100025 + 100026 + 100027 + 100028 + 100029;
+        System.out.println(total + " " + many.length);
+    }
+}
@@ -0,0 +1,61 @@
+// goto, tableswitch, lookupswitch, and returns of every type.
+public class Control {
+    static int table(int i) {
+        switch (i) {
+            case -1: return 100;
+            case 0: return 200;
+            case 1: return 300;
+            case 3: return 400;
+            default: return -1;
+        }
+    }
+
+    static String lookup(int i) {
+        switch (i) {
+            case -1000000: return "very small";
+            case 7: return "seven";
+            case 1000000: return "very big";
+            default: return "other";
+        }
+    }
+
+    static String strings(String s) {
+        switch (s) {
+            case "one": return "1";
+            case "two": return "2";
+            default: return "?";
+        }
+    }
+
+    static long l(long x) { return x + 1; }
+    static float f(float x) { return x * 2; }
+    static double d(double x) { return x / 2; }
+    static Object a(Object x) { return x; }
+    static boolean z(int x) { return x > 0; }
+    static char c(int x) { return (char) x; }
+    static void v() { System.out.println("void"); }
+
+    public static void main(String[] args) {
+        for (int i = -2; i <= 4; i++) {
+            System.out.print(table(i) + " ");
+        }
+        System.out.println();
+        System.out.println(lookup(-1000000) + " " + lookup(7) + " " + lookup(1000000) + " " + lookup(8));
+        System.out.println(strings("one") + strings("two") + strings("three"));
+        int sum = 0;
+        outer:
+        for (int i = 0; i < 10; i++) {
+            for (int j = 0; j < 10; j++) {
+                if (j > i) continue outer;
+                if (i == 8) break outer;
+                sum += j;
+            }
+        }
+        int k = 0;
+        do { k += 3; } while (k < 20);
+        while (k > 0) { k -= 7; }
+        System.out.println(sum + " " + k);
+        System.out.println(l(1) + " " + f(1.5f) + " " + d(3) + " " + a("a") + " " + z(1) + " " + c(65));
+        v();
+    }
+}
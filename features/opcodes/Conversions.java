@@ -0,0 +1,18 @@
+// Every primitive conversion instruction, including the rounding and saturating ones. The values stay away from the ones that
+// Java 17 prints with more digits than it needs to, which was fixed in 19.
+public class Conversions {
+    public static void main(String[] args) {
+        int i = -16777217;
+        System.out.println((long) i + " " + (float) i + " " + (double) i);
+        System.out.println((byte) i + " " + (int) (char) i + " " + (short) i);
+        long l = 5000000000000000007L;
+        System.out.println((int) l + " " + (float) l + " " + (double) l);
+        float f = -2.75f;
+        System.out.println((int) f + " " + (long) f + " " + (double) f);
+        System.out.println((int) Float.NaN + " " + (long) Float.POSITIVE_INFINITY + " " + (int) 1e20f);
+        double d = 9.99e18;
+        System.out.println((int) d + " " + (long) d + " " + (float) d);
+        System.out.println((int) Double.NaN + " " + (long) Double.NEGATIVE_INFINITY + " " + (float) 1e300);
+        System.out.println((float) 0.1 + " " + (double) 0.1f);
+    }
+}
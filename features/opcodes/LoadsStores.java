@@ -0,0 +1,69 @@
+// Loads and stores of locals (including the _0 to _3 forms) and of every kind of array.
+public class LoadsStores {
+    static void ints(int a, int b, int c, int d, int e) {
+        int f = a + b + c + d + e;
+        System.out.println(a + " " + b + " " + c + " " + d + " " + e + " " + f);
+    }
+
+    static void longs(long a, long b) {
+        long c = a * b;
+        long d = c - a;
+        System.out.println(a + " " + b + " " + c + " " + d);
+    }
+
+    static void floats(float a, float b, float c, float d) {
+        float e = a + b + c + d;
+        System.out.println(a + " " + b + " " + c + " " + d + " " + e);
+    }
+
+    static void doubles(double a, double b) {
+        double c = a / b;
+        double d = c * 4;
+        System.out.println(a + " " + b + " " + c + " " + d);
+    }
+
+    static void references(String a, String b, String c, String d) {
+        String e = a + b;
+        Object f = c;
+        System.out.println(e + " " + f + " " + d);
+    }
+
+    public static void main(String[] args) {
+        ints(1, 2, 3, 4, 5);
+        longs(6000000000L, 7);
+        floats(0.5f, 1.25f, 2.75f, 100f);
+        doubles(1.0, 3.0);
+        references("a", "b", "c", "d");
+
+        int[] is = new int[3];
+        is[0] = 10; is[1] = -20; is[2] = is[0] + is[1];
+        System.out.println(is[0] + " " + is[1] + " " + is[2]);
+        long[] ls = new long[2];
+        ls[1] = 1L << 40;
+        ls[0] = ls[1] + 1;
+        System.out.println(ls[0] + " " + ls[1]);
+        float[] fs = { 1.5f, -2.25f };
+        fs[0] = fs[0] * fs[1];
+        System.out.println(fs[0] + " " + fs[1]);
+        double[] ds = { 0.1, 0.2 };
+        ds[1] = ds[0] + ds[1];
+        System.out.println(ds[0] + " " + ds[1]);
+        String[] ss = { "x", null, "z" };
+        ss[1] = ss[0] + ss[2];
+        System.out.println(ss[0] + " " + ss[1] + " " + ss[2]);
+        byte[] bs = { 127, -128 };
+        bs[0]++;
+        bs[1]--;
+        System.out.println(bs[0] + " " + bs[1]);
+        boolean[] zs = new boolean[2];
+        zs[1] = true;
+        zs[0] = !zs[1];
+        System.out.println(zs[0] + " " + zs[1]);
+        char[] cs = { 'a', 'b' };
+        cs[0] = (char) (cs[1] + 1);
+        System.out.println(cs[0] + " " + cs[1] + " " + (int) cs[0]);
+        short[] shs = { 32767, -32768 };
+        shs[0]++;
+        System.out.println(shs[0] + " " + shs[1]);
+    }
+}
@@ -0,0 +1,34 @@
+// The arithmetic, bitwise and shift instructions for every type, including the edge cases the spec calls out.
+public class Maths {
+    public static void main(String[] args) {
+        int a = 17, b = -5;
+        System.out.println((a + b) + " " + (a - b) + " " + (a * b) + " " + (a / b) + " " + (a % b) + " " + (-a));
+        System.out.println((Integer.MAX_VALUE + 1) + " " + (Integer.MIN_VALUE / -1) + " " + (Integer.MIN_VALUE % -1));
+        System.out.println((a << 3) + " " + (b >> 1) + " " + (b >>> 28) + " " + (a << 33) + " " + (a & b) + " " + (a | b) + " " + (a ^ b));
+        long c = 1L << 40, d = -3;
+        System.out.println((c + d) + " " + (c - d) + " " + (c * d) + " " + (c / d) + " " + (c % d) + " " + (-c));
+        System.out.println((Long.MAX_VALUE + 1) + " " + (Long.MIN_VALUE / -1));
+        System.out.println((c << 3) + " " + (d >> 1) + " " + (d >>> 60) + " " + (c << 65) + " " + (c & d) + " " + (c | d) + " " + (c ^ d));
+        float e = 7.5f, f = -2f;
+        System.out.println((e + f) + " " + (e - f) + " " + (e * f) + " " + (e / f) + " " + (e % f) + " " + (-e));
+        System.out.println((e / 0) + " " + (-e / 0) + " " + (0f / 0) + " " + (-0f) + " " + (e % 0));
+        double g = 7.5, h = 0.1;
+        System.out.println((g + h) + " " + (g - h) + " " + (g * h) + " " + (g / h) + " " + (g % h) + " " + (-g));
+        System.out.println((g / 0) + " " + (0.0 / 0) + " " + (-0.0) + " " + (1e308 * 10));
+        int i = 5;
+        i++;
+        i += 100;
+        i -= 1000;
+        System.out.println(i);
+        try {
+            System.out.println(a / (b + 5));
+        } catch (ArithmeticException ex) {
+            System.out.println("int divide by zero");
+        }
+        try {
+            System.out.println(c % (d + 3));
+        } catch (ArithmeticException ex) {
+            System.out.println("long divide by zero");
+        }
+    }
+}
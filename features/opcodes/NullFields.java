@@ -0,0 +1,31 @@
+public class NullFields {
+    int value;
+    NullFields next;
+
+    static int read(NullFields f) {
+        return f.value;
+    }
+    static void write(NullFields f, int value) {
+        f.value = value;
+    }
+    public static void main(String[] args) {
+        // Each loop runs the instructions again after they've been quickened.
+        int caught = 0;
+        for (int i = 0; i < 3; i++) {
+            NullFields f = new NullFields();
+            write(f, i);
+            System.out.println(read(f));
+            try {
+                read(f.next);
+            } catch (NullPointerException e) {
+                caught++;
+            }
+            try {
+                write(f.next, i);
+            } catch (NullPointerException e) {
+                caught++;
+            }
+        }
+        System.out.println("caught " + caught);
+    }
+}
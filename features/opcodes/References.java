@@ -0,0 +1,82 @@
+// Fields, statics, every kind of invoke, new, arrays of references, checkcast, instanceof, athrow and monitors.
+public class References {
+    interface Shape {
+        double area();
+        default String describe() { return "shape with area " + area(); }
+    }
+
+    static abstract class Base implements Shape {
+        static int made;
+        protected String name;
+
+        Base(String name) {
+            this.name = name;
+            made++;
+        }
+
+        public String toString() { return name; }
+    }
+
+    static class Square extends Base {
+        private final double side;
+
+        Square(double side) {
+            super("square");
+            this.side = side;
+        }
+
+        public double area() { return side * side; }
+    }
+
+    static class Circle extends Base {
+        private final double r;
+
+        Circle(double r) {
+            super("circle");
+            this.r = r;
+        }
+
+        public double area() { return 3 * r * r; }
+        public String describe() { return "round " + super.describe(); }
+    }
+
+    private int secret() { return 7; }
+
+    public static void main(String[] args) {
+        Shape[] shapes = { new Square(2), new Circle(1) };
+        for (Shape s : shapes) {
+            System.out.println(s + ": " + s.describe());
+        }
+        System.out.println(Base.made + " " + shapes.length);
+        Base b = (Base) shapes[1];
+        b.name = "renamed";
+        System.out.println(b.name + " " + (b instanceof Circle) + " " + (b instanceof Square) + " " + (shapes instanceof Object[]));
+        Object o = shapes[0];
+        System.out.println((o instanceof Shape) + " " + (o instanceof Base) + " " + (o instanceof String) + " " + (null instanceof Object));
+        System.out.println(new References().secret());
+        try {
+            Object not = "not a shape";
+            Shape s = (Shape) not;
+            System.out.println(s);
+        } catch (ClassCastException e) {
+            System.out.println("class cast");
+        }
+        try {
+            throw new IllegalStateException("thrown");
+        } catch (IllegalStateException e) {
+            System.out.println(e.getMessage());
+        }
+        Object lock = new Object();
+        int counter = 0;
+        synchronized (lock) {
+            synchronized (lock) {
+                counter++;
+            }
+        }
+        System.out.println(counter);
+        String[][] names = new String[2][];
+        names[0] = new String[] { "a" };
+        names[1] = new String[3];
+        System.out.println(names[0][0] + " " + names[1].length + " " + names[1][2]);
+    }
+}
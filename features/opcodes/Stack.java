@@ -0,0 +1,40 @@
+// pop, pop2 and every form of dup, which javac only emits in some places.
+public class Stack {
+    int i;
+    long l;
+    static long sl;
+
+    static int sideEffect() { return 1; }
+    static long longSideEffect() { return 2L; }
+
+    public static void main(String[] args) {
+        // Results that get thrown away use pop and pop2.
+        sideEffect();
+        longSideEffect();
+        Stack s = new Stack();
+        // dup_x1: the value of an assignment to a field is also used.
+        int x = s.i = 5;
+        // dup2_x1: same but with a long.
+        long y = s.l = 6000000000L;
+        // dup2: a long static that's incremented and used.
+        long z = sl++;
+        z = ++sl;
+        int[] ints = { 1, 2, 3 };
+        // dup_x2: the value of an assignment to an array element is also used.
+        int w = ints[1] = 42;
+        long[] longs = { 1, 2 };
+        // dup2_x2: same but with a long.
+        long v = longs[0] = 7000000000L;
+        // dup2 on an array and index, for a compound assignment.
+        ints[2] += 10;
+        longs[1] <<= 3;
+        System.out.println(x + " " + y + " " + z + " " + w + " " + v + " " + s.i + " " + s.l + " " + sl);
+        System.out.println(ints[0] + " " + ints[1] + " " + ints[2] + " " + longs[0] + " " + longs[1]);
+        // Post-increments of fields and array elements.
+        int a = s.i++;
+        long b = s.l--;
+        int c = ints[0]++;
+        long d = longs[1]++;
+        System.out.println(a + " " + b + " " + c + " " + d + " " + s.i + " " + s.l + " " + ints[0] + " " + longs[1]);
+    }
+}
@@ -0,0 +1,45 @@
+// Synchronized methods, static synchronized methods and synchronized blocks keep increments from getting lost.
+public class Sync {
+    private int count;
+    private static int staticCount;
+    private static final Object LOCK = new Object();
+    private static int blockCount;
+
+    synchronized void increment() {
+        count++;
+    }
+
+    // Reentrant, since the monitor is already held when this calls increment.
+    synchronized void incrementTwice() {
+        increment();
+        increment();
+    }
+
+    static synchronized void incrementStatic() {
+        staticCount++;
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        Sync sync = new Sync();
+        Thread[] threads = new Thread[4];
+        for (int t = 0; t < threads.length; t++) {
+            threads[t] = new Thread(() -> {
+                for (int i = 0; i < 500; i++) {
+                    sync.increment();
+                    sync.incrementTwice();
+                    incrementStatic();
+                    synchronized (LOCK) {
+                        int before = blockCount;
+                        Thread.yield();
+                        blockCount = before + 1;
+                    }
+                }
+            });
+            threads[t].start();
+        }
+        for (Thread t : threads) {
+            t.join();
+        }
+        System.out.println(sync.count + " " + staticCount + " " + blockCount);
+    }
+}
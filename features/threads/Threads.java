@@ -0,0 +1,43 @@
+// Starting and joining threads, sleeping, and interrupting a thread that's sleeping.
+public class Threads {
+    static int[] results = new int[3];
+
+    public static void main(String[] args) throws InterruptedException {
+        Thread[] workers = new Thread[3];
+        for (int t = 0; t < workers.length; t++) {
+            int id = t;
+            workers[t] = new Thread(() -> {
+                int sum = 0;
+                for (int i = 0; i <= 100 * (id + 1); i++) {
+                    sum += i;
+                }
+                results[id] = sum;
+            });
+        }
+        System.out.println(workers[0].isAlive());
+        for (Thread w : workers) {
+            w.start();
+        }
+        for (Thread w : workers) {
+            w.join();
+        }
+        System.out.println(results[0] + " " + results[1] + " " + results[2] + " " + workers[0].isAlive());
+
+        boolean[] interrupted = new boolean[1];
+        Thread sleeper = new Thread(() -> {
+            try {
+                Thread.sleep(60000);
+            } catch (InterruptedException e) {
+                interrupted[0] = true;
+            }
+        });
+        sleeper.start();
+        Thread.sleep(50);
+        sleeper.interrupt();
+        sleeper.join();
+        System.out.println("interrupted: " + interrupted[0]);
+
+        Thread.currentThread().interrupt();
+        System.out.println(Thread.interrupted() + " " + Thread.interrupted());
+    }
+}
@@ -0,0 +1,59 @@
+// A producer and a consumer handing values over one at a time with wait and notifyAll.
+public class WaitNotify {
+    private int slot;
+    private boolean full;
+    private int received;
+
+    synchronized void put(int value) throws InterruptedException {
+        while (full) {
+            wait();
+        }
+        slot = value;
+        full = true;
+        notifyAll();
+    }
+
+    synchronized int take() throws InterruptedException {
+        while (!full) {
+            wait();
+        }
+        int value = slot;
+        full = false;
+        received++;
+        notifyAll();
+        return value;
+    }
+
+    public static void main(String[] args) throws InterruptedException {
+        WaitNotify box = new WaitNotify();
+        int[] sum = new int[1];
+        Thread consumer = new Thread(() -> {
+            try {
+                for (int i = 0; i < 20; i++) {
+                    sum[0] += box.take();
+                }
+            } catch (InterruptedException e) {
+                System.out.println("consumer interrupted");
+            }
+        });
+        consumer.start();
+        for (int i = 1; i <= 20; i++) {
+            box.put(i);
+        }
+        consumer.join();
+        System.out.println(sum[0] + " " + box.received);
+
+        // Waiting with a timeout comes back on its own.
+        Object lock = new Object();
+        synchronized (lock) {
+            lock.wait(10);
+        }
+        System.out.println("timed out");
+
+        try {
+            lock.notify();
+        } catch (IllegalMonitorStateException e) {
+            System.out.println("notify without the monitor");
+        }
+    }
+}
@@ -16,6 +16,13 @@ pub struct Cli {
     pub verbose: bool,
     pub should_dump: bool,
     pub classpath: Option<String>,
+    pub boot_classpath: Option<String>,
+    pub jdk: Option<String>,
+    // -Xms and -Xmx, in bytes.
+    pub initial_heap: Option<usize>,
+    pub max_heap: Option<usize>,
+    // -Xss, in bytes.
+    pub stack_size: Option<usize>,
 
     pub flags: u8,
 }
@@ -24,6 +31,23 @@ pub enum ParseError {
     FileDoesNotExist(String),
     MissingFileArgument,
     MissingClassPathArgument,
+    MissingBootClassPathArgument,
+    MissingJdkArgument,
+    InvalidInitialHeapSize(String),
+    InvalidMaxHeapSize(String),
+    InitialHeapTooLarge,
+    InvalidStackSize(String),
+}
+
+// Sizes like -Xmx and -Xss take them: a number of bytes, or of kilobytes, megabytes, or gigabytes with k, m, or g after it.
+fn parse_size(size: &str) -> Option<usize> {
+    let (number, multiplier) = match size.chars().last()? {
+        'k' | 'K' => (&size[..size.len() - 1], 1 << 10),
+        'm' | 'M' => (&size[..size.len() - 1], 1 << 20),
+        'g' | 'G' => (&size[..size.len() - 1], 1 << 30),
+        _ => (size, 1),
+    };
+    number.parse::<usize>().ok()?.checked_mul(multiplier)
 }
 
 pub fn parse_args(args: &[String]) -> Result<Cli, ParseError> {
@@ -34,6 +58,11 @@ pub fn parse_args(args: &[String]) -> Result<Cli, ParseError> {
     let mut fname = String::from("");
     let mut ftype = FileType::Class;
     let mut classpath = None;
+    let mut boot_classpath = None;
+    let mut jdk = None;
+    let mut initial_heap = None;
+    let mut max_heap = None;
+    let mut stack_size = None;
     if args.contains(&String::from("-d")) {
         should_dump = true;
     }
@@ -70,6 +99,12 @@ pub fn parse_args(args: &[String]) -> Result<Cli, ParseError> {
     if args.contains(&String::from("--dump-backtrace")) {
         flags |= jvm::settings::SHOULD_BACKTRACE;
     }
+    if args.contains(&String::from("-ot")) {
+        flags |= jvm::settings::SHOULD_USE_OS_THREADS;
+    }
+    if args.contains(&String::from("--os-threads")) {
+        flags |= jvm::settings::SHOULD_USE_OS_THREADS;
+    }
     if args.contains(&String::from("-vb")) {
         verbose = true;
     }
@@ -88,6 +123,47 @@ pub fn parse_args(args: &[String]) -> Result<Cli, ParseError> {
         }
         classpath = Some(args[sindex + 1].clone());
     }
+    if let Some(sindex) = args.iter().position(|s| s == "-bcp") {
+        if args.len() == sindex + 1 {
+            return Err(ParseError::MissingBootClassPathArgument);
+        }
+        boot_classpath = Some(args[sindex + 1].clone());
+    }
+    if let Some(sindex) = args.iter().position(|s| s == "--boot-classpath") {
+        if args.len() == sindex + 1 {
+            return Err(ParseError::MissingBootClassPathArgument);
+        }
+        boot_classpath = Some(args[sindex + 1].clone());
+    }
+    if let Some(sindex) = args.iter().position(|s| s == "--jdk") {
+        if args.len() == sindex + 1 {
+            return Err(ParseError::MissingJdkArgument);
+        }
+        jdk = Some(args[sindex + 1].clone());
+    }
+    if let Some(arg) = args.iter().find(|s| s.starts_with("-Xms")) {
+        initial_heap = match parse_size(&arg[4..]) {
+            Some(size) => Some(size),
+            None => return Err(ParseError::InvalidInitialHeapSize(arg.clone())),
+        };
+    }
+    if let Some(arg) = args.iter().find(|s| s.starts_with("-Xmx")) {
+        max_heap = match parse_size(&arg[4..]) {
+            Some(size) => Some(size),
+            None => return Err(ParseError::InvalidMaxHeapSize(arg.clone())),
+        };
+    }
+    if let Some(arg) = args.iter().find(|s| s.starts_with("-Xss")) {
+        stack_size = match parse_size(&arg[4..]) {
+            Some(size) => Some(size),
+            None => return Err(ParseError::InvalidStackSize(arg.clone())),
+        };
+    }
+    if let (Some(initial), Some(max)) = (initial_heap, max_heap) {
+        if initial > max {
+            return Err(ParseError::InitialHeapTooLarge);
+        }
+    }
     let mut missing_file = args.is_empty();
     if !missing_file {
         fname = args.last().unwrap().clone();
@@ -122,6 +198,11 @@ pub fn parse_args(args: &[String]) -> Result<Cli, ParseError> {
         should_dump,
         verbose,
         classpath,
+        boot_classpath,
+        jdk,
+        initial_heap,
+        max_heap,
+        stack_size,
         flags,
     })
 }
\ No newline at end of file
@@ -1,4 +1,7 @@
-use crate::jvm::instructions::Instruction;
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::jvm::instructions::Op;
 
 use super::{annotations::TypeAnnotation};
 
@@ -113,6 +116,13 @@ pub struct LineNumber {
     pub line_number: u16,
 }
 
+impl LineNumber {
+    // Like Exception::compress_range, so start_pc counts instructions.
+    pub fn compress(&mut self, translation_map: &HashMap<usize, usize>) {
+        self.start_pc = translation_map.get(&(self.start_pc as usize)).copied().unwrap_or(0) as u16;
+    }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq)]
 pub struct LocalVariable {
     pub start_pc: u16,
@@ -138,12 +148,23 @@ pub struct Exception {
     pub handler_pc: u16,
     pub catch_type: u16,
 }
+impl Exception {
+    // The table is in bytecode offsets, but once the code is decoded the pc counts instructions, like jumps do after compress_range.
+    // end_pc can be the length of the code, which has no instruction, so it becomes the number of instructions.
+    pub fn compress_range(&mut self, translation_map: &HashMap<usize, usize>, code_len: usize) {
+        let compress = |pc: u16| translation_map.get(&(pc as usize)).copied().unwrap_or(code_len) as u16;
+        self.start_pc = compress(self.start_pc);
+        self.end_pc = compress(self.end_pc);
+        self.handler_pc = compress(self.handler_pc);
+    }
+}
 
 #[derive(Clone, PartialEq)]
 pub struct Code {
     pub max_stack: u16,
     pub max_locals: u16,
-    pub code: Vec<Box<dyn Instruction>>,
+    // Every copy of a method shares its instructions, so when one gets quickened, everyone sees it.
+    pub code: Rc<Vec<Op>>,
     pub exception_table: Vec<Exception>,
     // A couple of these attributes can appear in multiples, and so we keep trakc of the total number using a Vec<Vec<>>.
     // This isn't neccesary for line number table, even though it can have multiples, so we just use one Vec<>.
@@ -155,6 +176,16 @@ pub struct Code {
     pub rt_invis_type_annotations: Option<Vec<TypeAnnotation>>,
 }
 
+impl Code {
+    // The source line the instruction at pc came from, if the class has line numbers.
+    pub fn line_number(&self, pc: usize) -> Option<u16> {
+        self.line_number_table.iter()
+            .filter(|l| l.start_pc as usize <= pc)
+            .max_by_key(|l| l.start_pc)
+            .map(|l| l.line_number)
+    }
+}
+
 use std::fmt;
 impl fmt::Display for Code {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
@@ -163,7 +194,7 @@ impl fmt::Display for Code {
         writeln!(f, "Code Length: {}", self.code.len())?;
         writeln!(f, "Code:")?;
         let mut op_idx = 0;
-        for op in &self.code {
+        for op in self.code.iter() {
             writeln!(f, "   {} idx: {}", op_idx, op)?;
             op_idx += 1;
         }
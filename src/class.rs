@@ -1,12 +1,13 @@
 use std::{rc::Rc, any::Any};
 
-use crate::{jvm::JVM, errorcodes::Error, value::Value, reference::object::Object};
+use crate::{jvm::JVM, errorcodes::Error, value::Value, reference::{Reference, object::{Object, customobject::Shape}}};
 
 use self::classfile::{MethodInfo, ClassFile};
 
 pub mod classfile;
 pub mod customclass;
 pub mod natives;
+pub mod rt_const_pool;
  
 pub trait Class {
     fn new(file: classfile::ClassFile, jvm: &mut JVM) -> Result<Self, Error> where Self : Sized;
@@ -17,6 +18,17 @@ pub trait Class {
     fn as_any(&self) ->  &dyn Any;
     fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any>;
     fn as_dyn_rc(self: Rc<Self>) -> Rc<dyn Class>;
+    // Where resolved symbolic references get cached. Native classes don't run bytecode, so they don't need one.
+    fn rt_const_pool(&self) -> Option<&rt_const_pool::RuntimeConstantPool> {
+        None
+    }
+    // Calls visit with every reference in the class's static fields, which are roots for the collector. See crate::heap.
+    #[allow(clippy::type_complexity)]
+    fn trace_statics(&self, _visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {}
+    // How the class's objects are laid out, for classes that can have them. See Shape::of.
+    fn shape(&self) -> Option<Rc<Shape>> {
+        None
+    }
 }
 
 /* 
@@ -36,6 +48,10 @@ pub fn new_class(file: classfile::ClassFile, jvm: &mut JVM) -> Result<Rc<dyn Cla
         "java/lang/StrictMath" => Ok(Rc::new(natives::strict_math::StrictMath::new(file, jvm)?)),
         "java/lang/Double" => Ok(Rc::new(natives::double::Double::new(file, jvm)?)),
         "java/lang/Math" => Ok(Rc::new(natives::math::Math::new(file, jvm)?)),
+        "java/lang/Thread" => Ok(Rc::new(natives::thread::Thread::new(file, jvm)?)),
+        "java/lang/invoke/CallSite" | "java/lang/invoke/ConstantCallSite" | "java/lang/invoke/MutableCallSite" | "java/lang/invoke/VolatileCallSite" => {
+            Ok(Rc::new(natives::call_site::CallSite::new(file, jvm)?))
+        },
         _ => Ok(Rc::new(customclass::CustomClass::new(file, jvm)?)),
     }
 }
@@ -15,7 +15,7 @@ use crate::data_access::*;
 use crate::errorcodes::{Error, Opcode};
 use crate::flags;
 use crate::jvm::JVM;
-use crate::jvm::instructions::{Instruction, self};
+use crate::jvm::instructions::{Op, self};
 
 
 #[derive(Clone, Debug, PartialEq)]
@@ -57,7 +57,7 @@ pub struct MethodInfo {
 }
 
 impl MethodInfo {
-    pub fn code(& self) -> Result<&Vec<Box<dyn Instruction>>, Error> {
+    pub fn code(& self) -> Result<&Vec<Op>, Error> {
         if let Some(code) = &self.code {
             return Ok(&code.code);
         }
@@ -66,7 +66,7 @@ impl MethodInfo {
         }
         Err(Error::NativeMethodCodeAccess)
     }
-    pub fn code_at(&self, index: usize) -> Result<&Box<dyn Instruction>, Error> {
+    pub fn code_at(&self, index: usize) -> Result<&Op, Error> {
         if let Some(code) = &self.code {
             return Ok(&code.code[index]);
         }
@@ -75,9 +75,10 @@ impl MethodInfo {
         }
         Err(Error::NativeMethodCodeAccess)
     }
-    pub fn code_at_mut(&mut self, index: usize) -> Result<&mut Box<dyn Instruction>, Error> {
+    pub fn code_at_mut(&mut self, index: usize) -> Result<&mut Op, Error> {
         if let Some(code) = &mut self.code {
-            return Ok(&mut code.code[index]);
+            // The code is shared with every other copy of this method, which is what we want for quickening.
+            return Ok(unsafe { &mut Rc::get_mut_unchecked(&mut code.code)[index] });
         }
         if self.access_flags.flags & flags::method::ACC_ABSTRACT > 0 {
             return Err(Error::AbstractMethodCodeAccess);
@@ -664,7 +665,7 @@ impl ClassFile {
                         code = Some(Code {
                             max_stack,
                             max_locals,
-                            code: code_data,
+                            code: Rc::new(code_data),
                             exception_table,
                             line_number_table,
                             local_variable_table,
@@ -1146,8 +1147,12 @@ impl ClassFile {
             let mut addr_hmap = HashMap::with_capacity(op_bytes.len());
             let mut true_pcs = Vec::new();
             let mut base_compress = 0;
+            let mut old_len = 0;
             while op_bytes.len() > 0 {
-                let old_len = op_bytes.len();
+                // An instruction after wide starts at the wide, so that's where it's measured from.
+                if !was_wide {
+                    old_len = op_bytes.len();
+                }
                 match instructions::new_instruction(&mut op_bytes, &self.constant_pool, jvm, was_wide, true_pc) {
                     Err(Error::Wide) => was_wide = true,
                     Err(e) => {
@@ -1156,7 +1161,7 @@ impl ClassFile {
                             unsafe {(*self_ptr).cp_entry(name)?.as_utf8()?},
                             unsafe {(*self_ptr).cp_entry(desc)?.as_utf8()?}
                         );
-                        for op in &code.code {
+                        for op in code.code.iter() {
                             println!("{}", op.name());
                         }
                         panic!("Hit error: {:?}", e);
@@ -1168,7 +1173,7 @@ impl ClassFile {
                         
                         // println!("found instruction {instruction}");
 
-                        code.code.push(instruction);
+                        Rc::get_mut(&mut code.code).unwrap().push(instruction);
                         true_pc += old_len - op_bytes.len();
                         base_compress += (old_len - 1) - op_bytes.len();
                         was_wide = false;
@@ -1177,9 +1182,15 @@ impl ClassFile {
             }
             // println!("Compressing ranges");
             let mut true_pcs = true_pcs.into_iter();
-            for instruction in &mut code.code {
+            for instruction in Rc::get_mut(&mut code.code).unwrap() {
                 instruction.compress_range(true_pcs.next().unwrap(), &addr_hmap);
             }
+            for exception in &mut code.exception_table {
+                exception.compress_range(&addr_hmap, code.code.len());
+            }
+            for line in &mut code.line_number_table {
+                line.compress(&addr_hmap);
+            }
         }    
         Ok(())
     }
@@ -1450,8 +1461,12 @@ impl ClassFile {
                         let mut was_wide = false;
                         let mut addr_hmap = HashMap::with_capacity(op_bytes.len());
                         let mut base_compress = 0;
+                        let mut old_len = 0;
                         while op_bytes.len() > 0 {
-                            let old_len = op_bytes.len();
+                            // An instruction after wide starts at the wide, so that's where it's measured from.
+                            if !was_wide {
+                                old_len = op_bytes.len();
+                            }
                             match instructions::new_instruction(&mut op_bytes, &cpool, jvm, was_wide, true_pc) {
                                 Err(Error::Wide) => was_wide = true,
                                 Err(e) => return Err(e),
@@ -1466,7 +1481,7 @@ impl ClassFile {
                             }
                         }
 
-                        // TODO: Remap other parts of Code, like the exception table. 
+                        // TODO: Remap other parts of Code, like the line number table. 
 
                         let exception_table_length = read_u16(data_ptr, &mut location);
                         let mut exception_table = Vec::with_capacity(exception_table_length as usize);
@@ -1478,6 +1493,9 @@ impl ClassFile {
                                 catch_type: read_u16(data_ptr, &mut location),
                             });
                         }
+                        for exception in &mut exception_table {
+                            exception.compress_range(&addr_hmap, code_data.len());
+                        }
                         let mut line_number_table = Vec::new();
                         let mut local_variable_table = Vec::new();
                         let mut local_variable_type_table = Vec::new();
@@ -1601,7 +1619,7 @@ impl ClassFile {
                         code = Some(Code {
                             max_stack,
                             max_locals,
-                            code: code_data,
+                            code: Rc::new(code_data),
                             exception_table,
                             line_number_table,
                             local_variable_table,
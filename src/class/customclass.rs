@@ -1,17 +1,43 @@
 use colored::Colorize;
 
-use std::{collections::HashMap, rc::Rc};
+use std::{collections::{HashMap, HashSet}, rc::Rc};
 
 use crate::attributes::code::stack_map_table;
 use crate::llvm::valuemarker::ValueMarker;
 use crate::{constant_pool::{NameAndType, Entry}, value::{Value, VarValue}, errorcodes::Opcode, 
-            flags, reference::{Reference, array::Array, Monitor}, access_macros, frame::Frame};
+            flags, reference::{Reference, array::Array, Monitor, object::customobject::Shape}, access_macros, frame::Frame};
 
 use super::*;
 
+// A virtual method, as it sits in a vtable.
+#[derive(Clone)]
+pub struct MethodSlot {
+    // The class that declares the method, or None if it's the class that owns the table (which can't hold an Rc to itself).
+    pub class: Option<Rc<dyn Class>>,
+    // Where the method is in that class's methods.
+    pub index: usize,
+    pub name: String,
+    pub descriptor: String,
+    // Set when there are several default methods to pick from, so calling this is an IncompatibleClassChangeError.
+    pub ambiguous: bool,
+}
+
 pub struct CustomClass {
     class_file: Rc<classfile::ClassFile>,
     static_fields: HashMap<NameAndType, Rc<Value<dyn Class, dyn Object>>>, 
+    super_class: Option<Rc<dyn Class>>,
+    // Every superinterface, direct or not.
+    interfaces: Vec<Rc<dyn Class>>,
+    // A subclass's vtable always starts with its superclass's, so an index into one works for all of its subclasses.
+    vtable: Vec<MethodSlot>,
+    // False when some superclass is native. Those don't have tables for ours to start with, so calls on us have to be searched.
+    has_tables: bool,
+    // For each superinterface, the vtable index of each of its methods (None for static and private ones).
+    #[allow(clippy::type_complexity)]
+    itable: Vec<(Rc<dyn Class>, Vec<Option<usize>>)>,
+    // Like the vtable, this starts with the superclass's.
+    shape: Rc<Shape>,
+    rt_const_pool: rt_const_pool::RuntimeConstantPool,
     #[cfg(not(target_family = "wasm"))]
     context: &'static Context,
     #[cfg(not(target_family = "wasm"))]
@@ -74,9 +100,27 @@ impl Class for CustomClass {
                 static_fields.insert(name_and_type, Rc::new(value));
             }
         }
+        // Linking needs the superclass and superinterfaces loaded, see https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.3.5
+        let super_class = match file.super_name() {
+            Some(super_name) => Some(jvm.resolve_class_reference(super_name)?),
+            None => None,
+        };
+        let interfaces = CustomClass::collect_interfaces(&file, &super_class, jvm)?;
+        let vtable = CustomClass::build_vtable(&file, &super_class, &interfaces)?;
+        let itable = CustomClass::build_itable(&file, &super_class, &interfaces, &vtable)?;
+        let has_tables = match &super_class {
+            Some(super_class) => super_class.as_any().downcast_ref::<CustomClass>().is_some_and(|c| c.has_tables),
+            None => true,
+        };
+        let super_shape = match &super_class {
+            Some(super_class) => Some(Shape::of(super_class, jvm)?),
+            None => None,
+        };
+        let shape = Rc::new(Shape::new(&file, super_shape.as_deref())?);
+        let rt_const_pool = rt_const_pool::RuntimeConstantPool::new(&file);
         #[cfg(target_family = "wasm")]
         let class = {
-            CustomClass { class_file: Rc::new(file), static_fields}
+            CustomClass { class_file: Rc::new(file), static_fields, super_class, interfaces, vtable, has_tables, itable, shape, rt_const_pool }
         };
         #[cfg(not(target_family = "wasm"))]
         let mut class = {
@@ -84,7 +128,8 @@ impl Class for CustomClass {
             let builder = jvm.context.create_builder();
             let execution_engine = module.create_jit_execution_engine(OptimizationLevel::None).unwrap();
     
-            CustomClass { class_file: Rc::new(file), static_fields, context: jvm.context, module, builder, execution_engine}
+            CustomClass { class_file: Rc::new(file), static_fields, super_class, interfaces, vtable, has_tables, itable, shape, rt_const_pool,
+                context: jvm.context, module, builder, execution_engine}
         };
 
         #[cfg(not(target_family = "wasm"))]
@@ -186,6 +231,11 @@ impl Class for CustomClass {
         let c_file = self.get_class_file();
 
         if method.access_flags & flags::method::ACC_NATIVE {
+            // A few of these we do ourselves, the rest get skipped.
+            match jvm.execute_native(method, Rc::clone(&self) as Rc<dyn Class>) {
+                Err(Error::UnsatisfiedLinkError(..)) => {},
+                result => return result.map(|_| true),
+            }
             eprintln!("{}", 
             format!("WARNING: Native method {}.{}{} was called, skipping", 
                 c_file.name(),
@@ -196,6 +246,7 @@ impl Class for CustomClass {
             return Ok(false);
         }
 
+        jvm.reserve_stack(Frame::size_for(method))?;
         let thread = access_macros::current_thread_mut!(jvm);
         // Fill out the local variables.
         
@@ -347,6 +398,195 @@ impl Class for CustomClass {
     fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any> {
         self
     }
+    fn rt_const_pool(&self) -> Option<&rt_const_pool::RuntimeConstantPool> {
+        Some(&self.rt_const_pool)
+    }
+    fn trace_statics(&self, visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {
+        for value in self.static_fields.values() {
+            if let Value::Reference(r) = value.as_ref() {
+                visit(r);
+            }
+        }
+    }
+    fn shape(&self) -> Option<Rc<Shape>> {
+        Some(Rc::clone(&self.shape))
+    }
+}
+
+// Whether two Rcs point at the same class.
+pub fn same_class(a: &Rc<dyn Class>, b: &Rc<dyn Class>) -> bool {
+    Rc::as_ptr(a) as *const u8 == Rc::as_ptr(b) as *const u8
+}
+
+// Methods that can go in a vtable or itable, which leaves out constructors, static initializers, static methods, and private methods.
+fn is_virtual(class_file: &ClassFile, method: &MethodInfo) -> Result<bool, Error> {
+    Ok((method.access_flags.flags & (flags::method::ACC_STATIC | flags::method::ACC_PRIVATE)) == 0
+        && !class_file.cp_entry(method.name_index)?.as_utf8()?.starts_with('<'))
+}
+
+impl CustomClass {
+    pub fn vtable(&self) -> &Vec<MethodSlot> {
+        &self.vtable
+    }
+    pub fn has_tables(&self) -> bool {
+        self.has_tables
+    }
+    // Finds the vtable index for the method interface declares at index.
+    pub fn itable_index(&self, interface: &Rc<dyn Class>, index: usize) -> Option<usize> {
+        let (_, methods) = self.itable.iter().find(|(i, _)| same_class(i, interface))?;
+        *methods.get(index)?
+    }
+    pub fn implements(&self, interface: &Rc<dyn Class>) -> bool {
+        self.interfaces.iter().any(|i| same_class(i, interface))
+    }
+    fn collect_interfaces(file: &ClassFile, super_class: &Option<Rc<dyn Class>>, jvm: &mut JVM) -> Result<Vec<Rc<dyn Class>>, Error> {
+        let mut interfaces: Vec<Rc<dyn Class>> = Vec::new();
+        let mut add = |interface: &Rc<dyn Class>| if !interfaces.iter().any(|i| same_class(i, interface)) {
+            interfaces.push(Rc::clone(interface));
+        };
+        for interface_index in file.interfaces() {
+            let interface_name_index = file.cp_entry(*interface_index)?.as_class()?;
+            let interface = jvm.resolve_class_reference(file.cp_entry(*interface_name_index)?.as_utf8()?)?;
+            add(&interface);
+            if let Some(interface) = interface.as_any().downcast_ref::<CustomClass>() {
+                interface.interfaces.iter().for_each(&mut add);
+            }
+        }
+        if let Some(super_class) = super_class.as_ref().and_then(|c| c.as_any().downcast_ref::<CustomClass>()) {
+            super_class.interfaces.iter().for_each(&mut add);
+        }
+        Ok(interfaces)
+    }
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.5 for what overrides what.
+    fn build_vtable(file: &ClassFile, super_class: &Option<Rc<dyn Class>>, interfaces: &[Rc<dyn Class>]) -> Result<Vec<MethodSlot>, Error> {
+        if file.is_interface() {
+            return Ok(Vec::new());
+        }
+        let mut vtable = match super_class {
+            Some(super_class) => match super_class.as_any().downcast_ref::<CustomClass>() {
+                Some(c) => c.vtable.iter().map(|slot| MethodSlot {
+                    class: Some(slot.class.clone().unwrap_or_else(|| Rc::clone(super_class))),
+                    ..slot.clone()
+                }).collect(),
+                None => Vec::new(),
+            },
+            None => Vec::new(),
+        };
+        // Our methods replace the ones they override, and go on the end otherwise.
+        for (index, method) in file.methods.iter().enumerate() {
+            if !is_virtual(file, method)? {
+                continue;
+            }
+            let name = file.cp_entry(method.name_index)?.as_utf8()?;
+            let descriptor = file.cp_entry(method.descriptor_index)?.as_utf8()?;
+            let slot = MethodSlot { class: None, index, name: name.clone(), descriptor: descriptor.clone(), ambiguous: false };
+            let mut overrode = false;
+            for existing in vtable.iter_mut().filter(|s| &s.name == name && &s.descriptor == descriptor) {
+                // Everything we inherited has a class.
+                let existing_class = existing.class.as_ref().unwrap().get_class_file();
+                let existing_method = &existing_class.methods[existing.index];
+                if (existing_method.access_flags.flags & flags::method::ACC_FINAL) == 0
+                    && JVM::can_override(file.name(), existing_class.name(), existing_method) {
+                    *existing = slot.clone();
+                    overrode = true;
+                }
+            }
+            if !overrode {
+                vtable.push(slot);
+            }
+        }
+        // Then, interface methods that no class implements get the default method, if there's one to pick.
+        let mut seen = HashSet::new();
+        for interface in interfaces {
+            let interface_file = interface.get_class_file();
+            for method in &interface_file.methods {
+                if !is_virtual(&interface_file, method)? {
+                    continue;
+                }
+                let name = interface_file.cp_entry(method.name_index)?.as_utf8()?;
+                let descriptor = interface_file.cp_entry(method.descriptor_index)?.as_utf8()?;
+                if !seen.insert((name.clone(), descriptor.clone())) {
+                    continue;
+                }
+                let from_interface = |slot: &MethodSlot| match &slot.class {
+                    Some(c) => c.get_class_file().is_interface(),
+                    None => false,
+                };
+                if vtable.iter().any(|s| &s.name == name && &s.descriptor == descriptor && !from_interface(s)) {
+                    continue;
+                }
+                let mut candidates = Vec::new();
+                for candidate in interfaces {
+                    let candidate_file = candidate.get_class_file();
+                    for (index, m) in candidate_file.methods.iter().enumerate() {
+                        if is_virtual(&candidate_file, m)? && candidate_file.cp_entry(m.name_index)?.as_utf8()? == name
+                            && candidate_file.cp_entry(m.descriptor_index)?.as_utf8()? == descriptor {
+                            candidates.push((Rc::clone(candidate), index));
+                        }
+                    }
+                }
+                // The maximally-specific ones are those that aren't in a superinterface of another one.
+                let maximal: Vec<&(Rc<dyn Class>, usize)> = candidates.iter().filter(|(c, _)| !candidates.iter().any(|(other, _)| {
+                    match other.as_any().downcast_ref::<CustomClass>() {
+                        Some(other) => other.implements(c),
+                        None => false,
+                    }
+                })).collect();
+                let non_abstract: Vec<&&(Rc<dyn Class>, usize)> = maximal.iter().filter(|(c, index)| {
+                    (c.get_class_file().methods[*index].access_flags.flags & flags::method::ACC_ABSTRACT) == 0
+                }).collect();
+                let ((class, index), ambiguous) = match non_abstract.len() {
+                    0 => (maximal[0], false),
+                    1 => (*non_abstract[0], false),
+                    _ => (*non_abstract[0], true),
+                };
+                let slot = MethodSlot { class: Some(Rc::clone(class)), index: *index, name: name.clone(), descriptor: descriptor.clone(), ambiguous };
+                match vtable.iter_mut().find(|s| &s.name == name && &s.descriptor == descriptor) {
+                    Some(existing) => *existing = slot,
+                    None => vtable.push(slot),
+                }
+            }
+        }
+        Ok(vtable)
+    }
+    #[allow(clippy::type_complexity)]
+    fn build_itable(file: &ClassFile, super_class: &Option<Rc<dyn Class>>, interfaces: &[Rc<dyn Class>], vtable: &[MethodSlot]) 
+    -> Result<Vec<(Rc<dyn Class>, Vec<Option<usize>>)>, Error> {
+        if file.is_interface() {
+            return Ok(Vec::new());
+        }
+        let mut itable = Vec::new();
+        for interface in interfaces {
+            let interface_file = interface.get_class_file();
+            let mut methods = Vec::new();
+            for method in &interface_file.methods {
+                if !is_virtual(&interface_file, method)? {
+                    methods.push(None);
+                    continue;
+                }
+                let name = interface_file.cp_entry(method.name_index)?.as_utf8()?;
+                let descriptor = interface_file.cp_entry(method.descriptor_index)?.as_utf8()?;
+                let matches = |s: &MethodSlot| &s.name == name && &s.descriptor == descriptor;
+                // Like select_method, the nearest class that declares the method wins, and if there isn't one, we use the default method.
+                let mut found = vtable.iter().position(|s| s.class.is_none() && matches(s));
+                let mut current = super_class.clone();
+                while found.is_none() {
+                    let c = match current.as_ref().and_then(|c| c.as_any().downcast_ref::<CustomClass>()) {
+                        Some(c) => c,
+                        None => break,
+                    };
+                    found = c.vtable.iter().position(|s| s.class.is_none() && matches(s));
+                    current = c.super_class.clone();
+                }
+                if found.is_none() {
+                    found = vtable.iter().position(|s| matches(s) && s.class.as_ref().is_some_and(|c| c.get_class_file().is_interface()));
+                }
+                methods.push(found);
+            }
+            itable.push((Rc::clone(interface), methods));
+        }
+        Ok(itable)
+    }
 }
 
 #[cfg(not(target_family = "wasm"))]
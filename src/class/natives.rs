@@ -1,5 +1,8 @@
+pub mod call_site;
 pub mod double;
+pub mod lambda;
 pub mod math;
 pub mod strict_math;
 pub mod system;
+pub mod thread;
 
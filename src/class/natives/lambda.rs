@@ -0,0 +1,112 @@
+use std::rc::Rc;
+
+use super::super::*;
+use crate::{errorcodes::Opcode, constant_pool::Entry, flags};
+
+// The class we make for each lambda call site, standing in for the hidden classes that LambdaMetafactory spins.
+// It has no code of its own, every method it declares gets handled by the lambda object (see object::natives::lambda).
+pub struct LambdaClass {
+    file: Rc<ClassFile>
+}
+
+impl LambdaClass {
+    // Builds the class file for a class called name, extending Object, that implements the given interfaces with the given methods.
+    pub fn generate_class_file(name: &str, interfaces: &[String], methods: &[(String, String)]) -> ClassFile {
+        let mut constant_pool = vec![
+            Entry::Utf8(String::from(name)),
+            Entry::Class(1),
+            Entry::Utf8(String::from("java/lang/Object")),
+            Entry::Class(3),
+        ];
+        let mut interface_indices = Vec::new();
+        for interface in interfaces {
+            constant_pool.push(Entry::Utf8(interface.clone()));
+            constant_pool.push(Entry::Class(constant_pool.len() as u16));
+            interface_indices.push(constant_pool.len() as u16);
+        }
+        let mut method_infos = Vec::new();
+        for (method_name, method_desc) in methods {
+            constant_pool.push(Entry::Utf8(method_name.clone()));
+            constant_pool.push(Entry::Utf8(method_desc.clone()));
+            let len = constant_pool.len() as u16;
+            method_infos.push(MethodInfo {
+                access_flags: flags::method::AccessFlags { flags: flags::method::ACC_PUBLIC },
+                name_index: len - 1,
+                descriptor_index: len,
+                code: None,
+                exceptions: None,
+                rt_vis_param_annotations: None,
+                rt_invis_param_annotations: None,
+                annotation_default: None,
+                method_parameters: None,
+                synthetic: true,
+                deprecated: false,
+                signature: None,
+                rt_vis_annotations: None,
+                rt_invis_annotations: None,
+                rt_vis_type_annotations: None,
+                rt_invis_type_annotations: None,
+            });
+        }
+        ClassFile {
+            minor_version: 0,
+            major_version: 61,
+            constant_pool,
+            access_flags: flags::class::AccessFlags { flags: flags::class::ACC_FINAL | flags::class::ACC_SUPER | flags::class::ACC_SYNTHETIC },
+            this_class_index: 2,
+            super_class_index: 4,
+            interfaces: interface_indices,
+            fields: Vec::new(),
+            methods: method_infos,
+            source_file: None,
+            inner_classes: None,
+            enclosing_method: None,
+            source_debug_extension: None,
+            bootstrap_methods: None,
+            module: None,
+            module_packages: None,
+            module_main_class: None,
+            nest_host: None,
+            nest_members: None,
+            record: None,
+            permitted_subclasses: None,
+            synthetic: true,
+            deprecated: false,
+            signature: None,
+            rt_vis_annotations: None,
+            rt_invis_annotations: None,
+            rt_vis_type_annotations: None,
+            rt_invis_type_annotations: None,
+        }
+    }
+}
+
+impl Class for LambdaClass {
+    fn new(file: ClassFile, _jvm: &mut JVM) -> Result<Self, Error> where Self : Sized {
+        Ok( LambdaClass {
+            file: Rc::new(file),
+        })
+    }
+    fn get_static(&self, _name: &str, _descriptor: &str, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::NativeMethod))
+    }
+    fn put_static(&mut self, _name: &str, _descriptor: &str, _value:  Value<dyn Class, dyn Object>, _jvm: &mut JVM) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::NativeMethod))
+    }
+    fn exec_method(self: Rc<Self>, _jvm: &mut JVM, _method: &MethodInfo) -> Result<bool, Error> {
+        // Lambda classes don't have any static methods.
+        Err(Error::NoSuchMethodError(Opcode::NativeMethod))
+    }
+    fn get_class_file(&self) -> Rc<ClassFile> {
+        Rc::clone(&self.file)
+    }
+    fn as_any(&self) ->  &dyn Any {
+        self
+    }
+    fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+    fn as_dyn_rc(self: Rc<Self>) -> Rc<dyn Class> {
+        self
+    }
+}
@@ -195,8 +195,24 @@ impl Class for System {
             _ => Err(Error::NoSuchFieldError(Opcode::NativeMethod)),
         }
     }
-    fn exec_method(self: Rc<Self>, _jvm: &mut JVM, _method: &MethodInfo) -> Result<bool, Error> {
-        Err(Error::Todo(Opcode::NativeMethod))
+    fn exec_method(self: Rc<Self>, jvm: &mut JVM, method: &MethodInfo) -> Result<bool, Error> {
+        let name = self.file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = self.file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let thread = current_thread_mut!(jvm);
+        let frame = current_frame_mut!(thread);
+        match (name, desc) {
+            // The same hash Object.hashCode gives, even if the class overrides it. See Monitor::identity_hash.
+            ("identityHashCode", "(Ljava/lang/Object;)I") => {
+                let object = frame.op_stack.pop().unwrap().to_reference()?;
+                let hash = match object.monitor() {
+                    Ok(monitor) => monitor.identity_hash(),
+                    Err(_) => 0,
+                };
+                frame.op_stack.push(Value::Int(hash));
+            },
+            _ => return Err(Error::Todo(Opcode::NativeMethod)),
+        }
+        Ok(true)
     }
     fn get_class_file(&self) -> Rc<ClassFile> {
         Rc::clone(&self.file)
@@ -0,0 +1,365 @@
+use std::cell::Cell;
+use std::rc::Rc;
+use std::time::{Duration, Instant};
+
+use colored::Colorize;
+
+use super::super::*;
+use crate::{errorcodes::Opcode, constant_pool::NameAndType, frame::Frame, thread::ThreadState,
+    class::rt_const_pool::FieldRef, jvm::invoke, reference::{Reference, object::{self, natives, customobject::Shape}, Monitor}};
+
+// Values of threadStatus, which are JVMTI thread states. See jdk.internal.misc.VM.toThreadState.
+const STATUS_NEW: i32 = 0;
+const STATUS_RUNNABLE: i32 = 0x5;
+const STATUS_TERMINATED: i32 = 0x2;
+
+const MIN_PRIORITY: i32 = 1;
+const NORM_PRIORITY: i32 = 5;
+const MAX_PRIORITY: i32 = 10;
+
+// java.lang.Thread, backed by the interpreter's threads (see crate::thread).
+// Thread objects are still CustomObjects with the fields from Thread.class, and this takes over the methods that need the VM.
+// Anything we don't handle runs its own code, like run(), which calls the target.
+pub struct Thread {
+    file: Rc<ClassFile>,
+    // For the "Thread-N" names that unnamed threads get.
+    next_number: Cell<i32>,
+    // The next tid. The main thread always gets 1, even though its object only gets made once someone asks for it.
+    next_id: Cell<i64>,
+    shape: Rc<Shape>,
+}
+
+impl Thread {
+    fn from_jvm(jvm: &mut JVM) -> Result<Rc<Thread>, Error> {
+        match jvm.resolve_class_reference("java/lang/Thread")?.as_any_rc().downcast::<Thread>() {
+            Ok(c) => Ok(c),
+            Err(_) => Err(Error::IncompatibleClassChangeError(Opcode::NativeMethod)),
+        }
+    }
+    fn field(self: &Rc<Self>, name: &str, descriptor: &str) -> FieldRef {
+        FieldRef::new(Rc::clone(self) as Rc<dyn Class>, NameAndType { name: String::from(name), descriptor: String::from(descriptor) }, false)
+    }
+    fn get(self: &Rc<Self>, jvm: &mut JVM, object: &Reference<dyn Class, dyn Object>, name: &str, descriptor: &str) -> Result<Value<dyn Class, dyn Object>, Error> {
+        object.as_object()?.get_field(&self.field(name, descriptor), jvm)
+    }
+    fn put(self: &Rc<Self>, jvm: &mut JVM, object: &Reference<dyn Class, dyn Object>, name: &str, descriptor: &str, value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        let mut object = Rc::clone(object.as_object()?);
+        unsafe { Rc::get_mut_unchecked(&mut object) }.put_field(&self.field(name, descriptor), jvm, value)
+    }
+    // Fills in a new Thread, like its constructors do. Threads start out with the same daemon status as the thread that made them.
+    fn init_object(self: &Rc<Self>, jvm: &mut JVM, object: &Reference<dyn Class, dyn Object>, target: Reference<dyn Class, dyn Object>,
+        name: Reference<dyn Class, dyn Object>, tid: i64) -> Result<(), Error> {
+        let name = if name.is_null() {
+            let number = self.next_number.get();
+            self.next_number.set(number + 1);
+            let name = natives::string::String::new_from_string(format!("Thread-{number}"), jvm)?;
+            Reference::Object(name as Rc<dyn Object>, Rc::new(Monitor::new()))
+        }
+        else {
+            name
+        };
+        let is_daemon = jvm.current_thread().is_daemon;
+        self.put(jvm, object, "name", "Ljava/lang/String;", Value::Reference(name))?;
+        self.put(jvm, object, "target", "Ljava/lang/Runnable;", Value::Reference(target))?;
+        self.put(jvm, object, "priority", "I", Value::Int(NORM_PRIORITY))?;
+        self.put(jvm, object, "daemon", "Z", Value::Byte(is_daemon as i32))?;
+        self.put(jvm, object, "tid", "J", Value::Long(tid))?;
+        self.put(jvm, object, "threadStatus", "I", Value::Int(STATUS_NEW))
+    }
+    fn new_id(&self) -> i64 {
+        let id = self.next_id.get();
+        self.next_id.set(id + 1);
+        id
+    }
+    // The java.lang.Thread for the current thread. The main thread's gets made the first time it's asked for.
+    fn current_thread_object(self: &Rc<Self>, jvm: &mut JVM) -> Result<Reference<dyn Class, dyn Object>, Error> {
+        let object = jvm.current_thread().object.clone();
+        if !object.is_null() {
+            return Ok(object);
+        }
+        let object = Reference::Object(object::new_object_with_name("java/lang/Thread", jvm)?, Rc::new(Monitor::new()));
+        let name = natives::string::String::new_from_string(String::from("main"), jvm)?;
+        self.init_object(jvm, &object, Reference::Null, Reference::Object(name as Rc<dyn Object>, Rc::new(Monitor::new())), 1)?;
+        self.put(jvm, &object, "threadStatus", "I", Value::Int(STATUS_RUNNABLE))?;
+        current_thread_mut!(jvm).object = object.clone();
+        Ok(object)
+    }
+    // Marks a thread as finished, once the JVM takes it off the schedule.
+    pub fn terminate(jvm: &mut JVM, object: &Reference<dyn Class, dyn Object>) -> Result<(), Error> {
+        Thread::from_jvm(jvm)?.put(jvm, object, "threadStatus", "I", Value::Int(STATUS_TERMINATED))
+    }
+    // The name, tid and priority of a thread, for thread dumps. The main thread might not have an object yet, 
+    // but we know what it would say.
+    pub fn describe(jvm: &mut JVM, object: &Reference<dyn Class, dyn Object>) -> Result<(String, i64, i32), Error> {
+        if object.is_null() {
+            return Ok((String::from("main"), 1, NORM_PRIORITY));
+        }
+        let class = Thread::from_jvm(jvm)?;
+        let name = match class.get(jvm, object, "name", "Ljava/lang/String;")?.as_reference()? {
+            Reference::Object(s, _) => s.as_any().downcast_ref::<natives::string::String>().map(|s| s.backing_string().clone()),
+            _ => None,
+        };
+        let tid = *class.get(jvm, object, "tid", "J")?.as_long()?;
+        let priority = *class.get(jvm, object, "priority", "I")?.as_int()?;
+        Ok((name.unwrap_or_default(), tid, priority))
+    }
+    // Throws an InterruptedException if the current thread has been interrupted, clearing it.
+    pub fn check_interrupted(jvm: &mut JVM, message: &str) -> Result<(), Error> {
+        let thread = current_thread_mut!(jvm);
+        if thread.interrupted {
+            thread.interrupted = false;
+            return jvm.throw_exception_with_message("java/lang/InterruptedException", String::from(message));
+        }
+        Ok(())
+    }
+    fn sleep(jvm: &mut JVM, millis: i64, nanos: i32) -> Result<(), Error> {
+        if millis < 0 {
+            return jvm.throw_exception_with_message("java/lang/IllegalArgumentException", String::from("timeout value is negative"));
+        }
+        if !(0..=999999).contains(&nanos) {
+            return jvm.throw_exception_with_message("java/lang/IllegalArgumentException", String::from("nanosecond timeout value out of range"));
+        }
+        Thread::check_interrupted(jvm, "sleep interrupted")?;
+        let duration = Duration::from_millis(millis as u64) + Duration::from_nanos(nanos as u64);
+        if jvm.can_block() {
+            current_thread_mut!(jvm).state = ThreadState::Sleeping(Instant::now() + duration);
+        }
+        else {
+            // Nothing else can run anyway, so we hold up the whole JVM.
+            #[cfg(not(target_family = "wasm"))]
+            std::thread::sleep(duration);
+            // There's no sleeping on wasm, so we wait it out.
+            #[cfg(target_family = "wasm")]
+            {
+                let until = Instant::now() + duration;
+                while Instant::now() < until {}
+            }
+        }
+        Ok(())
+    }
+    fn join(self: &Rc<Self>, jvm: &mut JVM, object: &Reference<dyn Class, dyn Object>, millis: i64, nanos: i32) -> Result<(), Error> {
+        if millis < 0 {
+            return jvm.throw_exception_with_message("java/lang/IllegalArgumentException", String::from("timeout value is negative"));
+        }
+        if !(0..=999999).contains(&nanos) {
+            return jvm.throw_exception_with_message("java/lang/IllegalArgumentException", String::from("nanosecond timeout value out of range"));
+        }
+        Thread::check_interrupted(jvm, "join interrupted")?;
+        let id = match jvm.thread_with_object(object) {
+            Some(thread) => thread.id,
+            None => return Ok(()),
+        };
+        // We can't wait from inside an instruction, since the thread we're joining can't run until it's done.
+        // Returning would let the caller go on as if the thread had finished, so we say why we can't instead.
+        if !jvm.can_block() {
+            return jvm.throw_exception_with_message("java/lang/UnsupportedOperationException",
+                String::from("can't join a thread from a class initializer or bootstrap method"));
+        }
+        let deadline = if millis == 0 && nanos == 0 {
+            None
+        } else {
+            Some(Instant::now() + Duration::from_millis(millis as u64) + Duration::from_nanos(nanos as u64))
+        };
+        current_thread_mut!(jvm).state = ThreadState::Joining(id, deadline);
+        Ok(())
+    }
+    fn start(self: &Rc<Self>, jvm: &mut JVM, object: Reference<dyn Class, dyn Object>) -> Result<(), Error> {
+        if self.get(jvm, &object, "threadStatus", "I")?.to_int()? != STATUS_NEW {
+            return jvm.throw_exception("java/lang/IllegalThreadStateException", "()V", vec![]);
+        }
+        self.put(jvm, &object, "threadStatus", "I", Value::Int(STATUS_RUNNABLE))?;
+        // The new thread starts in run(), which subclasses usually override.
+        let run = match JVM::find_declared_method(&(Rc::clone(self) as Rc<dyn Class>), "run", "()V")? {
+            Some(run) => run,
+            None => return Err(Error::NoSuchMethodError(Opcode::NativeMethod)),
+        };
+        let receiver = object.as_object()?.class();
+        let (class, run) = jvm.select_method(receiver, Rc::clone(self) as Rc<dyn Class>, run, "run", "()V", Opcode::NativeMethod)?;
+        let is_daemon = self.get(jvm, &object, "daemon", "Z")?.to_int()? != 0;
+        jvm.spawn_thread(object, is_daemon, class, &run)
+    }
+    // Runs the instance methods that need the VM on a Thread object (or an object of one of its subclasses).
+    // Gives back None for the ones that can just run their own code.
+    pub fn exec_on_object(self: Rc<Self>, jvm: &mut JVM, method: &MethodInfo) -> Result<Option<bool>, Error> {
+        let name = self.file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = self.file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        if !matches!(name, "<init>" | "start" | "isAlive" | "interrupt" | "isInterrupted" | "join" | "setDaemon" | "setName"
+            | "setPriority" | "checkAccess") {
+            return Ok(None);
+        }
+        let params = invoke::parameter_descriptors(desc)?;
+        let thread = current_thread_mut!(jvm);
+        let frame: &mut Frame = current_frame_mut!(thread);
+        if frame.op_stack.len() < params.len() + 1 {
+            return Err(Error::StackUnderflow(Opcode::NativeMethod));
+        }
+        let args = frame.op_stack.split_off(frame.op_stack.len() - params.len());
+        let this = frame.op_stack.pop().unwrap().to_reference()?;
+        let mut args = params.into_iter().zip(args);
+        match (name, desc) {
+            ("<init>", _) => {
+                // The constructors only differ by what they're given, and we don't have thread groups, stack sizes or thread locals.
+                let mut target = Reference::Null;
+                let mut thread_name = Reference::Null;
+                for (param, arg) in args {
+                    match param {
+                        "Ljava/lang/Runnable;" => target = arg.to_reference()?,
+                        "Ljava/lang/String;" => thread_name = arg.to_reference()?,
+                        _ => {},
+                    }
+                }
+                let tid = self.new_id();
+                self.init_object(jvm, &this, target, thread_name, tid)?;
+            },
+            ("start", "()V") => self.start(jvm, this)?,
+            ("isAlive", "()Z") => {
+                let alive = jvm.thread_with_object(&this).is_some();
+                let thread = current_thread_mut!(jvm);
+                current_frame_mut!(thread).op_stack.push(Value::Byte(alive as i32));
+            },
+            ("interrupt", "()V") => {
+                // Sleeping and joining threads get woken up with an InterruptedException, see JVM::wake_thread.
+                if let Some(thread) = jvm.thread_with_object(&this) {
+                    thread.interrupted = true;
+                }
+            },
+            ("isInterrupted", "()Z") => {
+                let interrupted = jvm.thread_with_object(&this).is_some_and(|t| t.interrupted);
+                let thread = current_thread_mut!(jvm);
+                current_frame_mut!(thread).op_stack.push(Value::Byte(interrupted as i32));
+            },
+            ("join", "()V") => self.join(jvm, &this, 0, 0)?,
+            ("join", "(J)V") => {
+                let millis = args.next().unwrap().1.to_long()?;
+                self.join(jvm, &this, millis, 0)?;
+            },
+            ("join", "(JI)V") => {
+                let millis = args.next().unwrap().1.to_long()?;
+                let nanos = args.next().unwrap().1.to_int()?;
+                self.join(jvm, &this, millis, nanos)?;
+            },
+            ("setDaemon", "(Z)V") => {
+                if jvm.thread_with_object(&this).is_some() {
+                    return jvm.throw_exception("java/lang/IllegalThreadStateException", "()V", vec![]);
+                }
+                let on = args.next().unwrap().1.to_int()?;
+                self.put(jvm, &this, "daemon", "Z", Value::Byte(on))?;
+            },
+            ("setName", "(Ljava/lang/String;)V") => {
+                let thread_name = args.next().unwrap().1.to_reference()?;
+                if thread_name.is_null() {
+                    return jvm.throw_exception_with_message("java/lang/NullPointerException", String::from("name cannot be null"));
+                }
+                self.put(jvm, &this, "name", "Ljava/lang/String;", Value::Reference(thread_name))?;
+            },
+            ("setPriority", "(I)V") => {
+                let priority = args.next().unwrap().1.to_int()?;
+                if !(MIN_PRIORITY..=MAX_PRIORITY).contains(&priority) {
+                    return jvm.throw_exception("java/lang/IllegalArgumentException", "()V", vec![]);
+                }
+                self.put(jvm, &this, "priority", "I", Value::Int(priority))?;
+            },
+            // There's no security manager, so everyone has access.
+            ("checkAccess", "()V") => {},
+            _ => return Err(Error::NoSuchMethodError(Opcode::NativeMethod)),
+        }
+        Ok(Some(true))
+    }
+}
+
+impl Class for Thread {
+    fn new(file: ClassFile, jvm: &mut JVM) -> Result<Self, Error> where Self : Sized {
+        let shape = Shape::load(&file, jvm)?;
+        Ok( Thread {
+            file: Rc::new(file),
+            next_number: Cell::new(0),
+            next_id: Cell::new(2),
+            shape,
+        })
+    }
+    fn get_static(&self, name: &str, _descriptor: &str, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        match name {
+            "MIN_PRIORITY" => Ok(Value::Int(MIN_PRIORITY)),
+            "NORM_PRIORITY" => Ok(Value::Int(NORM_PRIORITY)),
+            "MAX_PRIORITY" => Ok(Value::Int(MAX_PRIORITY)),
+            _ => Err(Error::NoSuchFieldError(Opcode::NativeMethod)),
+        }
+    }
+    fn put_static(&mut self, _name: &str, _descriptor: &str, _value:  Value<dyn Class, dyn Object>, _jvm: &mut JVM) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::NativeMethod))
+    }
+    fn exec_method(self: Rc<Self>, jvm: &mut JVM, method: &MethodInfo) -> Result<bool, Error> {
+        let name = self.file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = self.file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let mut was_natively_executed = true;
+        match (name, desc) {
+            ("currentThread", "()Ljava/lang/Thread;") => {
+                let object = self.current_thread_object(jvm)?;
+                let thread = current_thread_mut!(jvm);
+                current_frame_mut!(thread).op_stack.push(Value::Reference(object));
+            },
+            ("sleep", "(J)V") => {
+                let thread = current_thread_mut!(jvm);
+                let millis = current_frame_mut!(thread).op_stack.pop().unwrap().to_long()?;
+                Thread::sleep(jvm, millis, 0)?;
+            },
+            ("sleep", "(JI)V") => {
+                let thread = current_thread_mut!(jvm);
+                let frame: &mut Frame = current_frame_mut!(thread);
+                let nanos = frame.op_stack.pop().unwrap().to_int()?;
+                let millis = frame.op_stack.pop().unwrap().to_long()?;
+                Thread::sleep(jvm, millis, nanos)?;
+            },
+            ("yield", "()V") => {
+                if jvm.can_block() {
+                    current_thread_mut!(jvm).state = ThreadState::Yielding;
+                }
+            },
+            ("onSpinWait", "()V") => {},
+            ("interrupted", "()Z") => {
+                let thread = current_thread_mut!(jvm);
+                let interrupted = thread.interrupted;
+                thread.interrupted = false;
+                current_frame_mut!(thread).op_stack.push(Value::Byte(interrupted as i32));
+            },
+            ("holdsLock", "(Ljava/lang/Object;)Z") => {
+                let current_thread_id = jvm.current_thread_id();
+                let thread = current_thread_mut!(jvm);
+                let frame: &mut Frame = current_frame_mut!(thread);
+                let object = frame.op_stack.pop().unwrap().to_reference()?;
+                if object.is_null() {
+                    return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]);
+                }
+                frame.op_stack.push(Value::Byte(object.monitor()?.is_owned_by(current_thread_id) as i32));
+            },
+            _ => {
+                eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class Thread").red());
+                let thread = current_thread_mut!(jvm);
+                let frame: &mut Frame = current_frame_mut!(thread);
+                let num_args = method.num_args(&self.file)?;
+                frame.op_stack.truncate(frame.op_stack.len().saturating_sub(num_args));
+                let ret = &desc[desc.rfind(')').unwrap() + 1..];
+                if ret != "V" {
+                    frame.op_stack.push(Value::new(ret));
+                }
+                was_natively_executed = false;
+            }
+        }
+        Ok(was_natively_executed)
+    }
+    fn get_class_file(&self) -> Rc<ClassFile> {
+        Rc::clone(&self.file)
+    }
+    fn as_any(&self) ->  &dyn Any {
+        self
+    }
+    fn as_any_rc(self: Rc<Self>) -> Rc<dyn Any> {
+        self
+    }
+    fn as_dyn_rc(self: Rc<Self>) -> Rc<dyn Class> {
+        self
+    }
+    fn shape(&self) -> Option<Rc<Shape>> {
+        Some(Rc::clone(&self.shape))
+    }
+}
@@ -0,0 +1,102 @@
+use std::{cell::RefCell, fmt};
+
+use crate::constant_pool::NameAndType;
+
+use super::*;
+use super::customclass::same_class;
+use crate::reference::object::customobject::Slot;
+
+// A field that a Fieldref resolved to.
+pub struct FieldRef {
+    // The class or interface that declares the field, which isn't always the one the Fieldref names.
+    pub class: Rc<dyn Class>,
+    pub name_and_type: NameAndType,
+    pub is_static: bool,
+    // Where the field is in objects of the class, for instance fields. See customobject::Shape.
+    pub slot: Option<Slot>,
+}
+
+impl FieldRef {
+    pub fn new(class: Rc<dyn Class>, name_and_type: NameAndType, is_static: bool) -> FieldRef {
+        let slot = match is_static {
+            true => None,
+            false => class.shape().and_then(|shape| shape.slot(&name_and_type)),
+        };
+        FieldRef { class, name_and_type, is_static, slot }
+    }
+}
+
+// A method that a Methodref or InterfaceMethodref resolved to.
+pub struct MethodRef {
+    // The class the reference names, which invokespecial and invokeinterface still need.
+    pub symbolic_class: Rc<dyn Class>,
+    // The class that declares the method, and where the method is in its methods.
+    pub class: Rc<dyn Class>,
+    pub index: usize,
+    pub name: String,
+    pub descriptor: String,
+}
+
+impl MethodRef {
+    pub fn method(&self) -> MethodInfo {
+        self.class.get_class_file().methods[self.index].clone()
+    }
+}
+
+impl PartialEq for FieldRef {
+    fn eq(&self, other: &Self) -> bool {
+        same_class(&self.class, &other.class) && self.name_and_type == other.name_and_type
+    }
+}
+impl fmt::Debug for FieldRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}:{}", self.class.get_class_file().name(), self.name_and_type.name, self.name_and_type.descriptor)
+    }
+}
+impl PartialEq for MethodRef {
+    fn eq(&self, other: &Self) -> bool {
+        same_class(&self.class, &other.class) && self.index == other.index
+    }
+}
+impl fmt::Debug for MethodRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{}{}", self.class.get_class_file().name(), self.name, self.descriptor)
+    }
+}
+
+#[derive(Clone)]
+pub enum Resolved {
+    Class(Rc<dyn Class>),
+    Field(Rc<FieldRef>),
+    Method(Rc<MethodRef>),
+    // The java.lang.invoke.MethodType or MethodHandle that a constant resolved to.
+    MethodType(Reference<dyn Class, dyn Object>),
+    MethodHandle(Reference<dyn Class, dyn Object>),
+    // Whatever the bootstrap method of a dynamically-computed constant gave back.
+    Dynamic(Value<dyn Class, dyn Object>),
+    // Resolution failed, and every later attempt has to fail the same way.
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3
+    Failed(Error),
+}
+
+// The run-time constant pool of a class. Symbolic references get resolved the first time they're used, through
+// JVM::resolve_class_entry, JVM::resolve_field_ref, JVM::resolve_method_ref and JVM::load_constant, and then every instruction that uses them later
+// gets the result from here instead of going through the class file again.
+pub struct RuntimeConstantPool {
+    // Indexed the same way as the class file's constant pool, so index 0 is unused.
+    entries: RefCell<Vec<Option<Resolved>>>,
+}
+
+impl RuntimeConstantPool {
+    pub fn new(class_file: &ClassFile) -> RuntimeConstantPool {
+        RuntimeConstantPool { entries: RefCell::new(vec![None; class_file.constant_pool.len() + 1]) }
+    }
+    pub fn get(&self, index: u16) -> Option<Resolved> {
+        self.entries.borrow().get(index as usize).cloned().flatten()
+    }
+    pub fn set(&self, index: u16, resolved: Resolved) {
+        if let Some(entry) = self.entries.borrow_mut().get_mut(index as usize) {
+            *entry = Some(resolved);
+        }
+    }
+}
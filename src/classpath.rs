@@ -0,0 +1,194 @@
+use std::fs::{self, File};
+use std::io::Read;
+use std::path::Path;
+
+use colored::Colorize;
+
+use crate::errorcodes::Error;
+use crate::jar::Jar;
+use crate::jimage::{self, JImage};
+
+// The default location of the JDK classes, extracted by classlibs/update17.sh.
+pub const DEFAULT_BOOT_CLASS_PATH: &str = "./classlibs/17/modules/java.base/";
+// Same as the `java` launcher on unix.
+pub const SEPARATOR: char = ':';
+// jmods are zips with a small header in front, see jdk.internal.jmod.JmodFile
+const JMOD_MAGIC: [u8; 4] = [0x4A, 0x4D, 0x01, 0x00];
+
+#[derive(Clone, Debug, PartialEq)]
+pub enum ClassPathEntry {
+    Directory(String),
+    Jar(Jar),
+    // A jmod has its classes under classes/, next to native libraries, config files, etc.
+    Jmod(Jar),
+    // A whole JDK's worth of modules, from lib/modules.
+    JImage(JImage),
+}
+
+impl ClassPathEntry {
+    pub fn path(&self) -> &str {
+        match self {
+            ClassPathEntry::Directory(d) => d,
+            ClassPathEntry::Jar(j) | ClassPathEntry::Jmod(j) => j.path(),
+            ClassPathEntry::JImage(i) => i.path(),
+        }
+    }
+    // Takes a name like java/lang/Object, and returns the bytes of the class file if this entry has it.
+    pub fn find_class(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        match self {
+            ClassPathEntry::Directory(d) => Ok(fs::read(Path::new(d).join(format!("{name}.class"))).ok()),
+            ClassPathEntry::Jar(j) => j.read_entry(&format!("{name}.class")),
+            ClassPathEntry::Jmod(j) => j.read_entry(&format!("classes/{name}.class")),
+            ClassPathEntry::JImage(i) => i.find_class(name),
+        }
+    }
+}
+
+// An ordered list of places to look for classes. The first entry that has a class wins, like `java -cp`.
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct ClassPath {
+    entries: Vec<ClassPathEntry>,
+}
+
+impl ClassPath {
+    pub fn new() -> ClassPath {
+        ClassPath { entries: Vec::new() }
+    }
+    // See https://docs.oracle.com/en/java/javase/17/docs/specs/man/java.html for how java treats the class path.
+    // Entries that don't exist are ignored, and an entry ending in `*` means every jar in that directory.
+    pub fn parse(s: &str) -> Result<ClassPath, Error> {
+        let mut cp = ClassPath::new();
+        for entry in s.split(SEPARATOR) {
+            // An empty entry means the current directory.
+            let entry = if entry.is_empty() { "." } else { entry };
+            if let Some(dir) = entry.strip_suffix('*') {
+                let dir = if dir.is_empty() { "." } else { dir };
+                let mut jars: Vec<String> = match fs::read_dir(dir) {
+                    Ok(rd) => rd.filter_map(|e| e.ok())
+                        .map(|e| e.path().to_string_lossy().into_owned())
+                        .filter(|p| p.ends_with(".jar") || p.ends_with(".JAR"))
+                        .collect(),
+                    Err(_) => continue,
+                };
+                // read_dir has no defined order, so we sort to make lookups reproducible.
+                jars.sort();
+                for jar in jars {
+                    // A broken jar in the directory shouldn't stop the rest of the class path from loading, so we skip it like a missing entry.
+                    match Jar::open(&jar).and_then(|j| cp.push_jar(j)) {
+                        Ok(()) => {},
+                        Err(e) => eprintln!("{}", format!("WARNING: Skipping {jar} on the class path: {e:?}").as_str().red()),
+                    }
+                }
+            }
+            else {
+                cp.push(entry)?;
+            }
+        }
+        Ok(cp)
+    }
+    // Uses the classes from a JDK installation. Newer JDKs only have lib/modules, while jlinked images might only have jmods.
+    pub fn from_jdk(home: &str) -> Result<ClassPath, Error> {
+        let mut cp = ClassPath::new();
+        let modules = Path::new(home).join("lib").join("modules");
+        if modules.is_file() {
+            cp.push_jimage(JImage::open(&modules.to_string_lossy())?);
+            return Ok(cp);
+        }
+        let mut jmods: Vec<String> = match fs::read_dir(Path::new(home).join("jmods")) {
+            Ok(rd) => rd.filter_map(|e| e.ok())
+                .map(|e| e.path().to_string_lossy().into_owned())
+                .filter(|p| p.ends_with(".jmod"))
+                .collect(),
+            Err(_) => return Err(Error::IllegalJImageFile(format!("{home} has neither lib/modules nor jmods"))),
+        };
+        // A package only lives in one module, so the order here doesn't change which class we find.
+        jmods.sort();
+        for jmod in jmods {
+            cp.push_jmod(Jar::open(&jmod)?);
+        }
+        Ok(cp)
+    }
+    // Adds a directory, jar, jmod, or jimage to the end of the class path.
+    pub fn push(&mut self, path: &str) -> Result<(), Error> {
+        let p = Path::new(path);
+        if p.is_dir() {
+            self.push_directory(path);
+        }
+        else if p.is_file() {
+            let mut magic = [0; 4];
+            if let Ok(mut f) = File::open(p) {
+                // Anything too small to have a magic number can't be a jar either, but Jar::open will tell us that.
+                let _ = f.read_exact(&mut magic);
+            }
+            if magic == JMOD_MAGIC {
+                self.push_jmod(Jar::open(path)?);
+            }
+            else if u32::from_le_bytes(magic) == jimage::MAGIC {
+                self.push_jimage(JImage::open(path)?);
+            }
+            else {
+                self.push_jar(Jar::open(path)?)?;
+            }
+        }
+        Ok(())
+    }
+    pub fn push_directory(&mut self, path: &str) {
+        if !self.contains(path) {
+            self.entries.push(ClassPathEntry::Directory(String::from(path)));
+        }
+    }
+    // Adds a jar to the class path, along with everything in its manifest's Class-Path.
+    // Like java, we silently ignore Class-Path entries that don't exist.
+    pub fn push_jar(&mut self, jar: Jar) -> Result<(), Error> {
+        if self.contains(jar.path()) {
+            return Ok(());
+        }
+        let manifest = jar.manifest()?;
+        let dependencies: Vec<String> = manifest.class_path.iter().map(|url| jar.resolve_relative(url)).collect();
+        self.entries.push(ClassPathEntry::Jar(jar));
+        for dep in dependencies {
+            if Path::new(&dep).is_dir() {
+                self.push_directory(&dep);
+            }
+            else if let Ok(dep_jar) = Jar::open(&dep) {
+                self.push_jar(dep_jar)?;
+            }
+        }
+        Ok(())
+    }
+    pub fn push_jmod(&mut self, jmod: Jar) {
+        if !self.contains(jmod.path()) {
+            self.entries.push(ClassPathEntry::Jmod(jmod));
+        }
+    }
+    pub fn push_jimage(&mut self, image: JImage) {
+        if !self.contains(image.path()) {
+            self.entries.push(ClassPathEntry::JImage(image));
+        }
+    }
+    // Adds everything in another class path to the end of this one.
+    pub fn append(&mut self, other: ClassPath) {
+        for entry in other.entries {
+            if !self.contains(entry.path()) {
+                self.entries.push(entry);
+            }
+        }
+    }
+    // The same file can be named more than one way (dir/../dir/a.jar is dir/a.jar), so we compare where the paths really lead.
+    // Otherwise a jar whose Class-Path leads back to itself keeps adding itself under longer and longer names.
+    pub fn contains(&self, path: &str) -> bool {
+        let canonical = fs::canonicalize(path).ok();
+        self.entries.iter().any(|e| e.path() == path || (canonical.is_some() && fs::canonicalize(e.path()).ok() == canonical))
+    }
+    pub fn entries(&self) -> &Vec<ClassPathEntry> {
+        &self.entries
+    }
+    pub fn find_class(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        for entry in &self.entries {
+            if let Some(bytes) = entry.find_class(name)? {
+                return Ok(Some(bytes));
+            }
+        }
+        Ok(None)
+    }
+}
@@ -208,6 +208,7 @@ pub enum Opcode {
     // These aren't actual opcodes, but are functions where the error was detected.
     ArrayGet,
     ClassLoad,
+    ClassInit,
     ResolveClassRef,
     BlockedThread,
     ExceptionHandle,
@@ -223,10 +224,12 @@ pub enum Error {
     AbstractMethodError(Opcode),
     ArithmeticException(Opcode),
     ArrayIndexOutOfBoundsException(Opcode),
+    BootstrapMethodError(Opcode, String),
     Breakpoint,
     ClassCastException(Opcode),
     DoubleMutableReference(Opcode),
     DoubleMutableReferenceToMonitor(Opcode),
+    ExceptionInInitializerError(Opcode, String),
     FrameStackUnderflow(Opcode),
     ImpDep1,
     ImpDep2,
@@ -260,10 +263,14 @@ pub enum Error {
     IllegalEntryCastToPackage,
     IllegalEntryCastToString,
     IllegalEntryCastToUtf8,
+    IllegalAccessError(Opcode),
     IllegalFrameType,
+    IllegalJarFile(String),
+    IllegalJImageFile(String),
     IllegalMagicNumber(u32),
     IllegalMethodType(Opcode),
     IllegalMonitorStateException(Opcode),
+    IllegalOpcode(u8),
     IllegalReferenceCastToArray,
     IllegalReferenceCastToInterface,
     IllegalReferenceCastToObject,
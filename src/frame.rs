@@ -2,6 +2,7 @@ use std::rc::Rc;
 use std::vec::Vec;
 
 use crate::class::{Class, classfile::MethodInfo};
+use crate::reference::Monitor;
 use crate::reference::object::Object;
 use crate::value::{Value, VarValue};
 
@@ -13,6 +14,8 @@ pub struct Frame {
     pub rt_const_pool: Rc<dyn Class>, 
     pub current_method: MethodInfo, // It should be fine for this to be a straight-up methodinfo, because methodinfos should be immutable.
     pub pc: usize,
+    // The monitor this frame's method entered, if it's synchronized. It's exited when the frame is popped.
+    pub monitor: Option<Rc<Monitor>>,
 }
 impl Frame {
     pub fn new(pool_ref: Rc<dyn Class>, method: MethodInfo, num_locals: usize) -> Frame {
@@ -21,7 +24,29 @@ impl Frame {
     pub fn new_with_stack_size(pool_ref: Rc<dyn Class>, method: MethodInfo, num_locals: usize, stack_size: usize) -> Frame {
         let local_variables = vec![VarValue::Uninit; num_locals];
         Frame { local_variables, op_stack: Vec::with_capacity(stack_size), 
-            rt_const_pool: pool_ref, current_method: method, pc: 0 }
+            rt_const_pool: pool_ref, current_method: method, pc: 0, monitor: None }
+    }
+    // Roughly how much memory a frame for this method takes up, for -Xss. See JVM::reserve_stack.
+    pub fn size_for(method: &MethodInfo) -> usize {
+        let (max_locals, max_stack) = method.code.as_ref().map_or((0, 0), |code| (code.max_locals as usize, code.max_stack as usize));
+        std::mem::size_of::<Frame>() + max_locals * std::mem::size_of::<VarValue<dyn Class, dyn Object>>()
+            + max_stack * std::mem::size_of::<Value<dyn Class, dyn Object>>()
+    }
+    pub fn size(&self) -> usize {
+        Frame::size_for(&self.current_method)
+    }
+
+    // Where the frame is, for stack traces: the method's name, the source file its class says it came from, and the line it's on.
+    pub fn method_name(&self) -> String {
+        let class_file = self.rt_const_pool.get_class_file();
+        class_file.cp_entry(self.current_method.name_index).and_then(|e| e.as_utf8()).map_or(String::from("<unknown>"), |n| n.clone())
+    }
+    pub fn source_file(&self) -> Option<String> {
+        let class_file = self.rt_const_pool.get_class_file();
+        class_file.source_file.and_then(|i| class_file.cp_entry(i).and_then(|e| e.as_utf8()).ok().cloned())
+    }
+    pub fn line_number(&self) -> Option<u16> {
+        self.current_method.code.as_ref().and_then(|c| c.line_number(self.pc))
     }
 
     // We should replace this call, it does nothing now
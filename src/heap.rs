@@ -0,0 +1,189 @@
+use std::collections::HashMap;
+use std::rc::{Rc, Weak};
+
+use crate::class::Class;
+use crate::reference::Reference;
+use crate::reference::array::Array;
+use crate::reference::object::Object;
+
+// The heap has to grow by at least this much between collections.
+const MIN_COLLECTION_BYTES: usize = 8 * 1024 * 1024;
+
+// The Java heap. Objects and arrays still live in Rcs, which free anything that isn't part of a cycle as soon as
+// nothing points to it. Cycles never get there, so we also keep track of everything we allocate, and once enough has been
+// allocated, we trace from the roots like a mark-sweep collector. Anything we can't reach can only be reachable from other
+// garbage, so sweeping it means clearing its references, which breaks the cycles and lets the Rcs free it.
+//
+// The roots are the threads' frames and the classes' static fields (see JVM::collect_garbage). On top of that,
+// anything with more strong references than the heap accounts for is being held onto somewhere else, like by a native
+// object or an instruction that's in the middle of running, so it counts as a root too. This means we never clear
+// something that's still in use, even if we don't know where all the roots are.
+pub struct Heap {
+    objects: Vec<Tracked<dyn Object>>,
+    arrays: Vec<Tracked<Array<dyn Class, dyn Object>>>,
+    // How much was alive after the last collection, plus everything allocated since then.
+    used: usize,
+    next_collection: usize,
+    // -Xms, which we let the heap grow to before bothering to collect.
+    initial: usize,
+    // -Xmx, which allocations can't go past. See JVM::reserve_heap.
+    max: Option<usize>,
+    pub collections: usize,
+    // How many objects and arrays collections have freed.
+    pub freed: usize,
+}
+
+struct Tracked<T: ?Sized> {
+    value: Weak<T>,
+    // The size when it was allocated. Neither objects nor arrays change size after that.
+    size: usize,
+}
+
+// Something on the heap, which the collector holds onto until it's done, so nothing gets freed out from under it.
+enum Node {
+    Object(Rc<dyn Object>),
+    Array(Rc<Array<dyn Class, dyn Object>>),
+}
+
+impl Node {
+    fn strong_count(&self) -> usize {
+        match self {
+            Node::Object(o) => Rc::strong_count(o),
+            Node::Array(a) => Rc::strong_count(a),
+        }
+    }
+    fn address(&self) -> usize {
+        match self {
+            Node::Object(o) => Rc::as_ptr(o) as *const () as usize,
+            Node::Array(a) => Rc::as_ptr(a) as *const () as usize,
+        }
+    }
+    #[allow(clippy::type_complexity)]
+    fn trace(&self, visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {
+        match self {
+            Node::Object(o) => o.trace(visit),
+            Node::Array(a) => a.trace(visit),
+        }
+    }
+    fn clear_references(&self) {
+        // Nothing that's still in use can see this, so nobody notices it changing.
+        match self {
+            Node::Object(o) => unsafe { Rc::get_mut_unchecked(&mut Rc::clone(o)) }.clear_references(),
+            Node::Array(a) => unsafe { Rc::get_mut_unchecked(&mut Rc::clone(a)) }.clear_references(),
+        }
+    }
+}
+
+// Where the object or array a reference points to is, which is what we know things on the heap by.
+fn address(reference: &Reference<dyn Class, dyn Object>) -> Option<usize> {
+    match reference {
+        Reference::Object(o, _) => Some(Rc::as_ptr(o) as *const () as usize),
+        Reference::Array(a, _) => Some(Rc::as_ptr(a) as *const () as usize),
+        _ => None,
+    }
+}
+
+impl Heap {
+    pub fn new() -> Heap {
+        Heap { objects: Vec::new(), arrays: Vec::new(), used: 0, next_collection: MIN_COLLECTION_BYTES, initial: 0, max: None,
+            collections: 0, freed: 0 }
+    }
+    pub fn track_object(&mut self, object: &Rc<dyn Object>) {
+        let size = object.heap_size();
+        self.used += size;
+        self.objects.push(Tracked { value: Rc::downgrade(object), size });
+    }
+    pub fn track_array(&mut self, array: &Rc<Array<dyn Class, dyn Object>>) {
+        let size = array.heap_size();
+        self.used += size;
+        self.arrays.push(Tracked { value: Rc::downgrade(array), size });
+    }
+    // Arrays made by multianewarray already have arrays in them, which get tracked too.
+    // Anything else with arrays in it (like a copy made by clone) shares them with something that's already tracked.
+    pub fn track_multi_array(&mut self, array: &Rc<Array<dyn Class, dyn Object>>) {
+        self.track_array(array);
+        array.trace(&mut |element| {
+            if let Reference::Array(inner, _) = element {
+                self.track_multi_array(inner);
+            }
+        });
+    }
+    // Roughly how many bytes the heap is using. Things that have been freed since the last collection still count.
+    pub fn used(&self) -> usize {
+        self.used
+    }
+    pub fn set_initial(&mut self, initial: usize) {
+        self.initial = initial;
+        self.next_collection = self.next_collection.max(initial);
+    }
+    pub fn max(&self) -> Option<usize> {
+        self.max
+    }
+    pub fn set_max(&mut self, max: Option<usize>) {
+        self.max = max;
+    }
+    // Whether size more bytes can be allocated without going over the maximum.
+    pub fn fits(&self, size: usize) -> bool {
+        match self.max {
+            Some(max) => self.used.saturating_add(size) <= max,
+            None => true,
+        }
+    }
+    pub fn should_collect(&self) -> bool {
+        self.used >= self.next_collection
+    }
+    // Frees every cycle that can't be reached from the roots, and gives back how many objects and arrays were in them.
+    pub fn collect(&mut self, roots: Vec<Reference<dyn Class, dyn Object>>) -> usize {
+        let mut nodes: Vec<Node> = self.objects.iter().filter_map(|t| t.value.upgrade().map(Node::Object))
+            .chain(self.arrays.iter().filter_map(|t| t.value.upgrade().map(Node::Array)))
+            .collect();
+        let index: HashMap<usize, usize> = nodes.iter().enumerate().map(|(i, node)| (node.address(), i)).collect();
+        // How many of each node's references come from other nodes.
+        let mut internal = vec![0; nodes.len()];
+        for node in &nodes {
+            node.trace(&mut |r| {
+                if let Some(&i) = address(r).and_then(|a| index.get(&a)) {
+                    internal[i] += 1;
+                }
+            });
+        }
+        let mut marked = vec![false; nodes.len()];
+        let mut stack: Vec<usize> = roots.iter().filter_map(|r| address(r).and_then(|a| index.get(&a)).copied()).collect();
+        // One of the strong references is ours, from upgrading it.
+        stack.extend((0..nodes.len()).filter(|&i| nodes[i].strong_count() - 1 > internal[i]));
+        while let Some(i) = stack.pop() {
+            if marked[i] {
+                continue;
+            }
+            marked[i] = true;
+            nodes[i].trace(&mut |r| {
+                if let Some(&child) = address(r).and_then(|a| index.get(&a)) {
+                    if !marked[child] {
+                        stack.push(child);
+                    }
+                }
+            });
+        }
+        let mut freed = 0;
+        for (node, _) in nodes.iter().zip(&marked).filter(|(_, &marked)| !marked) {
+            node.clear_references();
+            freed += 1;
+        }
+        // Now that we let go of them, the garbage gets freed.
+        nodes.clear();
+        drop(roots);
+        self.objects.retain(|t| t.value.strong_count() > 0);
+        self.arrays.retain(|t| t.value.strong_count() > 0);
+        self.used = self.objects.iter().map(|t| t.size).sum::<usize>() + self.arrays.iter().map(|t| t.size).sum::<usize>();
+        self.next_collection = (self.used * 2).max(self.used + MIN_COLLECTION_BYTES).max(self.initial);
+        self.collections += 1;
+        self.freed += freed;
+        freed
+    }
+}
+
+impl Default for Heap {
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -0,0 +1,236 @@
+use std::collections::HashMap;
+use std::fs::{self, File};
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::errorcodes::Error;
+
+// Jars are just zip files with a manifest. We only need to read them, so this is a very small zip reader.
+// See https://pkware.cachefly.net/webdocs/casestudies/APPNOTE.TXT for the format.
+// We don't support zip64, encryption, or anything other than stored and deflated entries, which is all that `jar` produces.
+
+const LOCAL_HEADER_SIGNATURE: u32 = 0x04034b50;
+const LOCAL_HEADER_SIZE: usize = 30;
+const CENTRAL_HEADER_SIGNATURE: u32 = 0x02014b50;
+const END_OF_CENTRAL_DIR_SIGNATURE: u32 = 0x06054b50;
+const END_OF_CENTRAL_DIR_SIZE: usize = 22;
+
+const METHOD_STORED: u16 = 0;
+const METHOD_DEFLATED: u16 = 8;
+
+pub const MANIFEST_PATH: &str = "META-INF/MANIFEST.MF";
+
+#[derive(Clone, Debug, PartialEq)]
+struct JarEntry {
+    method: u16,
+    compressed_size: usize,
+    uncompressed_size: usize,
+    local_header_offset: usize,
+}
+
+#[derive(Clone, Debug, PartialEq, Default)]
+pub struct Manifest {
+    pub main_class: Option<String>,
+    // These are URLs relative to the jar, separated by spaces.
+    pub class_path: Vec<String>,
+    pub attributes: HashMap<String, String>,
+}
+
+// We only keep the central directory in memory, and read entries out of the file when they're asked for.
+// Jars that don't come from the filesystem (like in the web app) are kept entirely in memory.
+#[derive(Clone, Debug, PartialEq)]
+enum JarSource {
+    File,
+    Memory(Vec<u8>),
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct Jar {
+    path: String,
+    source: JarSource,
+    // Where the zip data starts in the file. This is 0 for jars, but jmods (for example) have a header in front of the zip.
+    base: usize,
+    entries: HashMap<String, JarEntry>,
+}
+
+fn read_u16_le(data: &[u8], loc: usize) -> Result<u16, Error> {
+    match data.get(loc..loc + 2) {
+        Some(b) => Ok(u16::from_le_bytes(b.try_into().unwrap())),
+        None => Err(Error::IllegalJarFile(String::from("unexpected end of archive"))),
+    }
+}
+
+fn read_u32_le(data: &[u8], loc: usize) -> Result<u32, Error> {
+    match data.get(loc..loc + 4) {
+        Some(b) => Ok(u32::from_le_bytes(b.try_into().unwrap())),
+        None => Err(Error::IllegalJarFile(String::from("unexpected end of archive"))),
+    }
+}
+
+impl Jar {
+    pub fn open(path: &str) -> Result<Jar, Error> {
+        let len = match fs::metadata(path) {
+            Ok(m) => m.len() as usize,
+            Err(_) => return Err(Error::IllegalJarFile(format!("could not read {path}"))),
+        };
+        Jar { path: String::from(path), source: JarSource::File, base: 0, entries: HashMap::new() }.read_central_directory(len)
+    }
+    pub fn new(data: Vec<u8>, path: String) -> Result<Jar, Error> {
+        let len = data.len();
+        Jar { path, source: JarSource::Memory(data), base: 0, entries: HashMap::new() }.read_central_directory(len)
+    }
+    fn read_range(&self, start: usize, len: usize) -> Result<Vec<u8>, Error> {
+        match &self.source {
+            JarSource::Memory(data) => match data.get(start..start + len) {
+                Some(b) => Ok(b.to_vec()),
+                None => Err(Error::IllegalJarFile(String::from("unexpected end of archive"))),
+            },
+            JarSource::File => {
+                let mut buf = vec![0; len];
+                let mut f = match File::open(&self.path) {
+                    Ok(f) => f,
+                    Err(_) => return Err(Error::IllegalJarFile(format!("could not read {}", self.path))),
+                };
+                if f.seek(SeekFrom::Start(start as u64)).is_err() || f.read_exact(&mut buf).is_err() {
+                    return Err(Error::IllegalJarFile(String::from("unexpected end of archive")));
+                }
+                Ok(buf)
+            },
+        }
+    }
+    fn read_central_directory(mut self, len: usize) -> Result<Jar, Error> {
+        // The end of central directory record is at the very end of the file, followed only by a comment of at most u16::MAX bytes.
+        if len < END_OF_CENTRAL_DIR_SIZE {
+            return Err(Error::IllegalJarFile(format!("{} is too small to be a jar", self.path)));
+        }
+        let tail_start = len.saturating_sub(END_OF_CENTRAL_DIR_SIZE + u16::MAX as usize);
+        let tail = self.read_range(tail_start, len - tail_start)?;
+        let mut eocd = None;
+        for loc in (0..=tail.len() - END_OF_CENTRAL_DIR_SIZE).rev() {
+            if read_u32_le(&tail, loc)? == END_OF_CENTRAL_DIR_SIGNATURE {
+                eocd = Some(loc);
+                break;
+            }
+        }
+        let eocd = match eocd {
+            Some(loc) => loc,
+            None => return Err(Error::IllegalJarFile(format!("{} has no central directory", self.path))),
+        };
+        let entry_count = read_u16_le(&tail, eocd + 10)? as usize;
+        let cd_size = read_u32_le(&tail, eocd + 12)? as usize;
+        let cd_offset = read_u32_le(&tail, eocd + 16)? as usize;
+        // The central directory is right before the end record, so anything more than that is a prefix in front of the zip.
+        self.base = match (tail_start + eocd).checked_sub(cd_size + cd_offset) {
+            Some(b) => b,
+            None => return Err(Error::IllegalJarFile(format!("{} has a corrupt central directory", self.path))),
+        };
+        let data = self.read_range(self.base + cd_offset, cd_size)?;
+
+        let mut loc = 0;
+        self.entries.reserve(entry_count);
+        for _ in 0..entry_count {
+            if read_u32_le(&data, loc)? != CENTRAL_HEADER_SIGNATURE {
+                return Err(Error::IllegalJarFile(format!("{} has a corrupt central directory", self.path)));
+            }
+            let method = read_u16_le(&data, loc + 10)?;
+            let compressed_size = read_u32_le(&data, loc + 20)? as usize;
+            let uncompressed_size = read_u32_le(&data, loc + 24)? as usize;
+            let name_len = read_u16_le(&data, loc + 28)? as usize;
+            let extra_len = read_u16_le(&data, loc + 30)? as usize;
+            let comment_len = read_u16_le(&data, loc + 32)? as usize;
+            let local_header_offset = read_u32_le(&data, loc + 42)? as usize;
+            let name = match data.get(loc + 46..loc + 46 + name_len) {
+                Some(n) => String::from_utf8_lossy(n).into_owned(),
+                None => return Err(Error::IllegalJarFile(String::from("unexpected end of archive"))),
+            };
+            self.entries.insert(name, JarEntry { method, compressed_size, uncompressed_size, local_header_offset });
+            loc += 46 + name_len + extra_len + comment_len;
+        }
+        Ok(self)
+    }
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    pub fn contains(&self, name: &str) -> bool {
+        self.entries.contains_key(name)
+    }
+    pub fn read_entry(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        let entry = match self.entries.get(name) {
+            Some(e) => e,
+            None => return Ok(None),
+        };
+        // The local header repeats the name and has its own extra field, which can differ in length from the central one.
+        let loc = self.base + entry.local_header_offset;
+        let header = self.read_range(loc, LOCAL_HEADER_SIZE)?;
+        if read_u32_le(&header, 0)? != LOCAL_HEADER_SIGNATURE {
+            return Err(Error::IllegalJarFile(format!("{} has a corrupt entry {name}", self.path)));
+        }
+        let name_len = read_u16_le(&header, 26)? as usize;
+        let extra_len = read_u16_le(&header, 28)? as usize;
+        let compressed = self.read_range(loc + LOCAL_HEADER_SIZE + name_len + extra_len, entry.compressed_size)?;
+        let bytes = match entry.method {
+            METHOD_STORED => compressed,
+            METHOD_DEFLATED => match miniz_oxide::inflate::decompress_to_vec(&compressed) {
+                Ok(b) => b,
+                Err(_) => return Err(Error::IllegalJarFile(format!("{} has a corrupt entry {name}", self.path))),
+            },
+            m => return Err(Error::IllegalJarFile(format!("{} uses unsupported compression method {m} for {name}", self.path))),
+        };
+        if bytes.len() != entry.uncompressed_size {
+            return Err(Error::IllegalJarFile(format!("{} has a corrupt entry {name}", self.path)));
+        }
+        Ok(Some(bytes))
+    }
+    pub fn manifest(&self) -> Result<Manifest, Error> {
+        match self.read_entry(MANIFEST_PATH)? {
+            Some(bytes) => Ok(Manifest::parse(&String::from_utf8_lossy(&bytes))),
+            None => Ok(Manifest::default()),
+        }
+    }
+    // Class-Path entries are relative to the directory the jar is in, not the cwd.
+    pub fn resolve_relative(&self, url: &str) -> String {
+        match Path::new(&self.path).parent() {
+            Some(dir) => dir.join(url).to_string_lossy().into_owned(),
+            None => String::from(url),
+        }
+    }
+}
+
+impl Manifest {
+    // See https://docs.oracle.com/en/java/javase/17/docs/specs/jar/jar.html#jar-manifest
+    // We only care about the main section, so we stop at the first blank line.
+    pub fn parse(text: &str) -> Manifest {
+        let mut lines: Vec<String> = Vec::new();
+        for line in text.lines() {
+            let line = line.trim_end_matches('\r');
+            if line.is_empty() {
+                break;
+            }
+            // Lines longer than 72 bytes get wrapped, and continuation lines start with a single space.
+            if let Some(rest) = line.strip_prefix(' ') {
+                if let Some(last) = lines.last_mut() {
+                    last.push_str(rest);
+                }
+                continue;
+            }
+            lines.push(String::from(line));
+        }
+        let mut manifest = Manifest::default();
+        for line in lines {
+            let (key, value) = match line.split_once(':') {
+                Some(kv) => kv,
+                None => continue,
+            };
+            let value = value.trim();
+            // Attribute names are case insensitive.
+            if key.eq_ignore_ascii_case("Main-Class") {
+                manifest.main_class = Some(value.replace('.', "/"));
+            }
+            else if key.eq_ignore_ascii_case("Class-Path") {
+                manifest.class_path = value.split_whitespace().map(String::from).collect();
+            }
+            manifest.attributes.insert(String::from(key), String::from(value));
+        }
+        manifest
+    }
+}
@@ -0,0 +1,254 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+
+use crate::errorcodes::Error;
+
+// A reader for the jimage format that the JDK ships its classes in (lib/modules).
+// There's no real spec for this, so this is based on jdk.internal.jimage.BasicImageReader and ImageLocation in the JDK sources.
+// Everything in the file is in native byte order. We only support little endian images, which is what every JDK we care about uses.
+
+pub const MAGIC: u32 = 0xCAFEDADA;
+const MAJOR_VERSION: u32 = 1;
+const HEADER_SIZE: usize = 7 * 4;
+// Used for the perfect hash that maps names to locations.
+const HASH_MULTIPLIER: u32 = 0x01000193;
+
+// The attribute kinds in a location
+const ATTRIBUTE_END: u8 = 0;
+const ATTRIBUTE_MODULE: u8 = 1;
+const ATTRIBUTE_PARENT: u8 = 2;
+const ATTRIBUTE_BASE: u8 = 3;
+const ATTRIBUTE_EXTENSION: u8 = 4;
+const ATTRIBUTE_OFFSET: u8 = 5;
+const ATTRIBUTE_COMPRESSED: u8 = 6;
+const ATTRIBUTE_UNCOMPRESSED: u8 = 7;
+const ATTRIBUTE_COUNT: usize = 8;
+
+// Compressed resources start with this header, see jdk.internal.jimage.decompressor.CompressedResourceHeader
+const COMPRESSED_MAGIC: u32 = 0xCAFEFAFA;
+const COMPRESSED_HEADER_SIZE: usize = 29;
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct JImage {
+    path: String,
+    table_length: usize,
+    redirect_start: usize,
+    offsets_start: usize,
+    locations_start: usize,
+    strings_start: usize,
+    // Resources start right after the index.
+    index_size: usize,
+    // The header, redirect table, offsets, locations, and strings. The resources themselves are read from the file on demand.
+    index: Vec<u8>,
+}
+
+fn read_u32(data: &[u8], loc: usize) -> Result<u32, Error> {
+    match data.get(loc..loc + 4) {
+        Some(b) => Ok(u32::from_le_bytes(b.try_into().unwrap())),
+        None => Err(Error::IllegalJImageFile(String::from("unexpected end of image"))),
+    }
+}
+
+fn hash(name: &str, seed: u32) -> u32 {
+    let mut h = seed;
+    for b in name.bytes() {
+        h = h.wrapping_mul(HASH_MULTIPLIER) ^ b as u32;
+    }
+    h & 0x7FFFFFFF
+}
+
+impl JImage {
+    pub fn open(path: &str) -> Result<JImage, Error> {
+        let mut f = match File::open(path) {
+            Ok(f) => f,
+            Err(_) => return Err(Error::IllegalJImageFile(format!("could not read {path}"))),
+        };
+        let mut header = [0; HEADER_SIZE];
+        if f.read_exact(&mut header).is_err() {
+            return Err(Error::IllegalJImageFile(format!("{path} is too small to be a jimage")));
+        }
+        if read_u32(&header, 0)? != MAGIC {
+            return Err(Error::IllegalJImageFile(format!("{path} is not a jimage")));
+        }
+        let version = read_u32(&header, 4)?;
+        if version >> 16 != MAJOR_VERSION {
+            return Err(Error::IllegalJImageFile(format!("{path} has unsupported version {}.{}", version >> 16, version & 0xFFFF)));
+        }
+        let table_length = read_u32(&header, 16)? as usize;
+        let locations_size = read_u32(&header, 20)? as usize;
+        let strings_size = read_u32(&header, 24)? as usize;
+
+        let redirect_start = HEADER_SIZE;
+        let offsets_start = redirect_start + table_length * 4;
+        let locations_start = offsets_start + table_length * 4;
+        let strings_start = locations_start + locations_size;
+        let index_size = strings_start + strings_size;
+
+        let mut index = vec![0; index_size];
+        if f.seek(SeekFrom::Start(0)).is_err() || f.read_exact(&mut index).is_err() {
+            return Err(Error::IllegalJImageFile(format!("{path} has a truncated index")));
+        }
+        Ok(JImage { path: String::from(path), table_length, redirect_start, offsets_start, locations_start, strings_start, index_size, index })
+    }
+    pub fn path(&self) -> &str {
+        &self.path
+    }
+    fn string(&self, offset: usize) -> Result<&str, Error> {
+        let start = self.strings_start + offset;
+        let bytes = match self.index.get(start..) {
+            Some(b) => b,
+            None => return Err(Error::IllegalJImageFile(String::from("unexpected end of image"))),
+        };
+        let end = bytes.iter().position(|b| *b == 0).unwrap_or(bytes.len());
+        match std::str::from_utf8(&bytes[..end]) {
+            Ok(s) => Ok(s),
+            Err(_) => Err(Error::InvalidUtf8),
+        }
+    }
+    fn attributes(&self, offset: usize) -> Result<[u64; ATTRIBUTE_COUNT], Error> {
+        let mut attributes = [0; ATTRIBUTE_COUNT];
+        let mut loc = self.locations_start + offset;
+        loop {
+            let byte = match self.index.get(loc) {
+                Some(b) => *b,
+                None => return Err(Error::IllegalJImageFile(String::from("unexpected end of image"))),
+            };
+            let kind = byte >> 3;
+            if kind == ATTRIBUTE_END {
+                break;
+            }
+            if kind as usize >= ATTRIBUTE_COUNT {
+                return Err(Error::IllegalJImageFile(format!("{} has an invalid location attribute {kind}", self.path)));
+            }
+            // Values are stored big endian in as few bytes as possible.
+            let length = (byte & 0x7) as usize + 1;
+            let mut value = 0;
+            for i in 0..length {
+                match self.index.get(loc + 1 + i) {
+                    Some(b) => value = (value << 8) | *b as u64,
+                    None => return Err(Error::IllegalJImageFile(String::from("unexpected end of image"))),
+                }
+            }
+            attributes[kind as usize] = value;
+            loc += 1 + length;
+        }
+        Ok(attributes)
+    }
+    // The hash table is perfect, so a name always leads to some location. We have to check that it's actually the one we asked for.
+    fn verify(&self, attributes: &[u64; ATTRIBUTE_COUNT], name: &str) -> Result<bool, Error> {
+        let mut full_name = String::new();
+        let module = self.string(attributes[ATTRIBUTE_MODULE as usize] as usize)?;
+        if !module.is_empty() {
+            full_name.push('/');
+            full_name.push_str(module);
+            full_name.push('/');
+        }
+        let parent = self.string(attributes[ATTRIBUTE_PARENT as usize] as usize)?;
+        if !parent.is_empty() {
+            full_name.push_str(parent);
+            full_name.push('/');
+        }
+        full_name.push_str(self.string(attributes[ATTRIBUTE_BASE as usize] as usize)?);
+        let extension = self.string(attributes[ATTRIBUTE_EXTENSION as usize] as usize)?;
+        if !extension.is_empty() {
+            full_name.push('.');
+            full_name.push_str(extension);
+        }
+        Ok(full_name == name)
+    }
+    fn find_location(&self, name: &str) -> Result<Option<[u64; ATTRIBUTE_COUNT]>, Error> {
+        if self.table_length == 0 {
+            return Ok(None);
+        }
+        let mut index = hash(name, HASH_MULTIPLIER) as usize % self.table_length;
+        let redirect = read_u32(&self.index, self.redirect_start + index * 4)? as i32;
+        if redirect < 0 {
+            index = (-1 - redirect) as usize;
+        }
+        else if redirect > 0 {
+            index = hash(name, redirect as u32) as usize % self.table_length;
+        }
+        else {
+            return Ok(None);
+        }
+        let offset = read_u32(&self.index, self.offsets_start + index * 4)? as usize;
+        let attributes = self.attributes(offset)?;
+        if self.verify(&attributes, name)? {
+            Ok(Some(attributes))
+        }
+        else {
+            Ok(None)
+        }
+    }
+    fn read_resource(&self, attributes: &[u64; ATTRIBUTE_COUNT]) -> Result<Vec<u8>, Error> {
+        let offset = attributes[ATTRIBUTE_OFFSET as usize] as usize;
+        let compressed_size = attributes[ATTRIBUTE_COMPRESSED as usize] as usize;
+        let uncompressed_size = attributes[ATTRIBUTE_UNCOMPRESSED as usize] as usize;
+        let size = if compressed_size != 0 { compressed_size } else { uncompressed_size };
+
+        let mut buf = vec![0; size];
+        let mut f = match File::open(&self.path) {
+            Ok(f) => f,
+            Err(_) => return Err(Error::IllegalJImageFile(format!("could not read {}", self.path))),
+        };
+        if f.seek(SeekFrom::Start((self.index_size + offset) as u64)).is_err() || f.read_exact(&mut buf).is_err() {
+            return Err(Error::IllegalJImageFile(String::from("unexpected end of image")));
+        }
+        if compressed_size == 0 {
+            return Ok(buf);
+        }
+        self.decompress(buf)
+    }
+    // Images made by jlink --compress can have resources compressed several times over, each with its own header.
+    fn decompress(&self, mut buf: Vec<u8>) -> Result<Vec<u8>, Error> {
+        while read_u32(&buf, 0).ok() == Some(COMPRESSED_MAGIC) {
+            if buf.len() < COMPRESSED_HEADER_SIZE {
+                return Err(Error::IllegalJImageFile(String::from("unexpected end of image")));
+            }
+            let decompressor = self.string(read_u32(&buf, 20)? as usize)?;
+            buf = match decompressor {
+                "zip" => match miniz_oxide::inflate::decompress_to_vec_zlib(&buf[COMPRESSED_HEADER_SIZE..]) {
+                    Ok(b) => b,
+                    Err(_) => return Err(Error::IllegalJImageFile(format!("{} has a corrupt resource", self.path))),
+                },
+                // TODO: compact-cp (--compress=1) shares strings between classes, and would need the whole string table.
+                d => return Err(Error::IllegalJImageFile(format!("{} uses unsupported compression {d}", self.path))),
+            };
+        }
+        Ok(buf)
+    }
+    pub fn find_resource(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        match self.find_location(name)? {
+            Some(attributes) => Ok(Some(self.read_resource(&attributes)?)),
+            None => Ok(None),
+        }
+    }
+    // Returns the modules that have classes in a package (given like java/lang).
+    // The image has a /packages/<package> entry for every package, with a list of (is empty, module name) pairs.
+    pub fn modules_for_package(&self, package: &str) -> Result<Vec<String>, Error> {
+        let content = match self.find_resource(&format!("/packages/{}", package.replace('/', ".")))? {
+            Some(c) => c,
+            None => return Ok(Vec::new()),
+        };
+        let mut modules = Vec::new();
+        for pair in content.chunks_exact(8) {
+            if read_u32(pair, 0)? == 0 {
+                modules.push(String::from(self.string(read_u32(pair, 4)? as usize)?));
+            }
+        }
+        Ok(modules)
+    }
+    // Takes a name like java/lang/Object, and finds the class in whatever module has its package.
+    pub fn find_class(&self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        let package = match name.rfind('/') {
+            Some(i) => &name[..i],
+            None => return Ok(None),
+        };
+        for module in self.modules_for_package(package)? {
+            if let Some(bytes) = self.find_resource(&format!("/{module}/{name}.class"))? {
+                return Ok(Some(bytes));
+            }
+        }
+        Ok(None)
+    }
+}
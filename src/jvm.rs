@@ -5,9 +5,17 @@ use crate::errorcodes::{Error, Opcode};
 use crate::class::{Class, classfile::MethodInfo};
 use crate::frame::Frame;
 use crate::reference::{Reference, Monitor};
-use crate::thread::Thread;
+use crate::reference::object::{self, Object, natives};
+use crate::class::customclass::{self, CustomClass};
+use crate::class::rt_const_pool::{FieldRef, MethodRef, Resolved};
+use crate::constant_pool::{Entry, NameAndType};
+use crate::flags;
+use crate::thread::{Thread, ThreadState};
+use crate::heap::Heap;
 use crate::value::{Value, VarValue};
 use crate::llvm::valuemarker::ValueMarker;
+use crate::classpath::ClassPath;
+use crate::load_class_from_bytes;
 
 use core::panic;
 
@@ -16,8 +24,8 @@ use inkwell::context::Context;
 use web_sys::ReadableStream;
 
 use std::collections::HashMap;
-use std::fs::{File, self};
-use std::io::Read;
+use std::fmt;
+use std::mem;
 use std::option::Option; 
 use std::rc::Rc;
 use std::result::Result;
@@ -25,16 +33,32 @@ use std::string::String;
 use std::time::Instant;
 use std::vec::Vec;
 
-use self::instructions::Instruction;
+use self::instructions::{Flow, Op};
 
-// Just useful for code readability
-mod operations;
 pub mod instructions;
+pub mod invoke;
+#[cfg(not(target_family = "wasm"))]
+pub mod os_threads;
 pub mod settings;
+pub mod thread_dump;
 
 const STEP_SIZE: usize = 10;
+// How much stack each thread gets without -Xss. Our frames are a lot bigger than HotSpot's, 
+// so this is about how much it takes to recurse as deep as HotSpot's default of 1MB lets you.
+const DEFAULT_STACK_SIZE: usize = 16 * 1024 * 1024;
+// Like HotSpot's MaxJavaStackTraceDepth, stack traces only keep this many of the innermost frames.
+const MAX_STACK_TRACE_DEPTH: usize = 1024;
 
-const SHOULD_CLINIT: bool = false; // change if you want to test clinit stuff
+// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.5
+// Classes that we haven't started initializing yet just aren't in the map.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum InitState {
+    // <clinit> runs to completion inside the instruction that triggered it, 
+    // so the only way to see this is a recursive request from the thread doing the initialization.
+    BeingInitialized,
+    Initialized,
+    Erroneous,
+}
 
 pub struct Crash {
     has_crashed: bool,
@@ -42,65 +66,138 @@ pub struct Crash {
     _base_traceback: String,
 } 
 
+// How an invokevirtual or invokeinterface picks its method, worked out when it's first resolved.
+#[derive(Clone)]
+pub enum Dispatch {
+    // Private and final methods can't be overridden, so there's nothing to pick.
+    Direct,
+    // An index into the receiver's vtable.
+    Virtual(usize),
+    // An interface, and the index of the method in it, to look up in the receiver's itable.
+    Interface(Rc<dyn Class>, usize),
+    // We don't have tables for the resolved class, so select_method has to search every time.
+    Select,
+}
+
+// A resolved method that gets dispatched on its receiver. The method is at index in class's methods.
+#[derive(Clone)]
+pub struct VirtualCall {
+    pub class: Rc<dyn Class>,
+    pub index: usize,
+    pub name: String,
+    pub descriptor: String,
+    pub num_args: usize,
+    pub dispatch: Dispatch,
+}
+
+impl fmt::Debug for VirtualCall {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let dispatch = match &self.dispatch {
+            Dispatch::Direct => String::from("direct"),
+            Dispatch::Virtual(slot) => format!("vtable {slot}"),
+            Dispatch::Interface(interface, index) => format!("itable {}#{index}", interface.get_class_file().name()),
+            Dispatch::Select => String::from("select"),
+        };
+        write!(f, "{}.{}{} ({dispatch})", self.class.get_class_file().name(), self.name, self.descriptor)
+    }
+}
+
+impl PartialEq for VirtualCall {
+    fn eq(&self, other: &Self) -> bool {
+        customclass::same_class(&self.class, &other.class) && self.index == other.index
+    }
+}
+
 pub struct JVM {
     pub m_threads: Vec<Thread>,
     pub m_loaded_classes: HashMap<String, Rc<dyn Class>>,
+    pub m_init_states: HashMap<String, InitState>,
+    // The java.lang.Class objects we've handed out, by field descriptor, so each type only ever gets one.
+    m_class_mirrors: HashMap<String, Reference<dyn Class, dyn Object>>,
     m_thrown_error: Error,
     m_crash_info: Crash,
     pub m_thread_index: usize,
+    // The id the next thread we start gets. The main thread is 0.
+    m_next_thread_id: usize,
     m_step_size: usize,
     m_has_halted: bool,
+    // What the process should exit with once we're done.
+    m_exit_code: i32,
+    pub heap: Heap,
+    // -Xss, the most stack a thread can use. See JVM::reserve_stack.
+    pub max_stack_size: usize,
     pub m_main_class_name: String,
     m_flags: u8,
+    // The instructions step1 is in the middle of, innermost last. There's more than one when an instruction runs code itself.
+    m_executing: Vec<*mut Op>,
     pub start_time: Instant,
-    class_path: Option<String>,
+    // The JDK classes. Like a parent class loader, these are searched before the application class path.
+    boot_class_path: ClassPath,
+    class_path: ClassPath,
     #[cfg(not(target_family = "wasm"))]
     pub context: &'static Context,
     pub should_always_jit: bool,
 }
 #[cfg(not(target_family = "wasm"))]
 impl JVM {
-    pub fn new_jvm(n: String, flags: u8, class_path: Option<String>, context: &'static Context) -> JVM {
+    pub fn new_jvm(n: String, flags: u8, class_path: ClassPath, boot_class_path: ClassPath, context: &'static Context) -> JVM {
         JVM {
             m_threads: vec![Thread::new()
             ],
             m_loaded_classes: HashMap::new(),
+            m_init_states: HashMap::new(),
+            m_class_mirrors: HashMap::new(),
             m_thrown_error: Error::None,
             m_crash_info: Crash{ has_crashed: false, 
                 crash_reason: String::from(""), 
                 _base_traceback: String::from("")},
             m_thread_index: 0,
+            m_next_thread_id: 1,
             m_step_size: STEP_SIZE,
             m_has_halted: false,
+            m_exit_code: 0,
+            heap: Heap::new(),
+            max_stack_size: DEFAULT_STACK_SIZE,
             m_main_class_name: n,
             m_flags: flags,
+            m_executing: Vec::new(),
             start_time: Instant::now(),
+            boot_class_path,
             class_path,
             context,
             should_always_jit:  false,
         }
     }
-    pub fn new_with_step_size(n: String, step_size: usize, flags: u8, class_path: Option<String>, context: &'static Context) -> JVM {
+    pub fn new_with_step_size(n: String, step_size: usize, flags: u8, class_path: ClassPath, boot_class_path: ClassPath, context: &'static Context) -> JVM {
         JVM {
-            m_threads: Vec::new(),
+            m_threads: vec![Thread::new()
+            ],
             m_loaded_classes: HashMap::new(),
+            m_init_states: HashMap::new(),
+            m_class_mirrors: HashMap::new(),
             m_thrown_error: Error::None,
             m_crash_info: Crash{ has_crashed: false, 
                 crash_reason: String::from(""), 
                 _base_traceback: String::from("")},
             m_thread_index: 0,
+            m_next_thread_id: 1,
             m_step_size: step_size,
             m_has_halted: false,
+            m_exit_code: 0,
+            heap: Heap::new(),
+            max_stack_size: DEFAULT_STACK_SIZE,
             m_main_class_name: n,
             m_flags: flags,
+            m_executing: Vec::new(),
             start_time: Instant::now(),
+            boot_class_path,
             class_path,
             context,
             should_always_jit:  false,
         }
     }
-    pub fn new_with_main_class(c: ClassFile, code_bytes: Vec<Vec<u8>>, flags: u8, class_path: Option<String>, context: &'static Context) -> Result<JVM, Error> {
-        let mut jvm = Self::new_jvm(String::from(c.name()), flags, class_path, context);
+    pub fn new_with_main_class(c: ClassFile, code_bytes: Vec<Vec<u8>>, flags: u8, class_path: ClassPath, boot_class_path: ClassPath, context: &'static Context) -> Result<JVM, Error> {
+        let mut jvm = Self::new_jvm(String::from(c.name()), flags, class_path, boot_class_path, context);
 
         let name = String::from(c.name());
         let class = class::new_class(c, &mut jvm)?;
@@ -109,69 +206,69 @@ impl JVM {
         // Init the class file.
         unsafe { Rc::get_mut_unchecked(&mut class.get_class_file()) }.init_code(code_bytes, &mut jvm)?;
 
-        // After adding the class, we initialize the class
-        // We should use this error instead of ignoring it.
-        if SHOULD_CLINIT {
-            let mut found_clinit = true;
-            if let Err(e) = jvm.setup_method_call_from_name_on_main("<clinit>", "()V", true) {
-                if e != Error::NoSuchMethodError(Opcode::MethodInvoke) {
-                    println!("{e:#?}");
-                    return Err(e);
-                }
-                found_clinit = false;
-            }
-            if found_clinit {
-                jvm.run_until_method_exit();
-                if jvm.has_encoutered_error() {
-                    println!("Encountered error: {:#?}", jvm.m_thrown_error);
-                }
-            }
-        }
+        // The main class gets initialized right before we call main, see excecute().
         Ok(jvm)
     }
 }
 
 #[cfg(target_family = "wasm")]
 impl JVM {
-    pub fn new_jvm(n: String, flags: u8, class_path: Option<String>) -> JVM {
+    pub fn new_jvm(n: String, flags: u8, class_path: ClassPath, boot_class_path: ClassPath) -> JVM {
         JVM {
             m_threads: vec![Thread::new()
             ],
             m_loaded_classes: HashMap::new(),
+            m_init_states: HashMap::new(),
+            m_class_mirrors: HashMap::new(),
             m_thrown_error: Error::None,
             m_crash_info: Crash{ has_crashed: false, 
                 crash_reason: String::from(""), 
                 _base_traceback: String::from("")},
             m_thread_index: 0,
+            m_next_thread_id: 1,
             m_step_size: STEP_SIZE,
             m_has_halted: false,
+            m_exit_code: 0,
+            heap: Heap::new(),
+            max_stack_size: DEFAULT_STACK_SIZE,
             m_main_class_name: n,
             m_flags: flags,
+            m_executing: Vec::new(),
             start_time: Instant::now(),
+            boot_class_path,
             class_path,
             should_always_jit:  false,
         }
     }
-    pub fn new_with_step_size(n: String, step_size: usize, flags: u8, class_path: Option<String>) -> JVM {
+    pub fn new_with_step_size(n: String, step_size: usize, flags: u8, class_path: ClassPath, boot_class_path: ClassPath) -> JVM {
         JVM {
-            m_threads: Vec::new(),
+            m_threads: vec![Thread::new()
+            ],
             m_loaded_classes: HashMap::new(),
+            m_init_states: HashMap::new(),
+            m_class_mirrors: HashMap::new(),
             m_thrown_error: Error::None,
             m_crash_info: Crash{ has_crashed: false, 
                 crash_reason: String::from(""), 
                 _base_traceback: String::from("")},
             m_thread_index: 0,
+            m_next_thread_id: 1,
             m_step_size: step_size,
             m_has_halted: false,
+            m_exit_code: 0,
+            heap: Heap::new(),
+            max_stack_size: DEFAULT_STACK_SIZE,
             m_main_class_name: n,
             m_flags: flags,
+            m_executing: Vec::new(),
             start_time: Instant::now(),
+            boot_class_path,
             class_path,
             should_always_jit:  false,
         }
     }
-    pub fn new_with_main_class(c: ClassFile, code_bytes: Vec<Vec<u8>>, flags: u8, class_path: Option<String>) -> Result<JVM, Error> {
-        let mut jvm = Self::new_jvm(String::from(c.name()), flags, class_path);
+    pub fn new_with_main_class(c: ClassFile, code_bytes: Vec<Vec<u8>>, flags: u8, class_path: ClassPath, boot_class_path: ClassPath) -> Result<JVM, Error> {
+        let mut jvm = Self::new_jvm(String::from(c.name()), flags, class_path, boot_class_path);
 
         let name = String::from(c.name());
         let class = class::new_class(c, &mut jvm)?;
@@ -180,83 +277,336 @@ impl JVM {
         // Init the class file.
         unsafe { Rc::get_mut_unchecked(&mut class.get_class_file()) }.init_code(code_bytes, &mut jvm)?;
 
-        // After adding the class, we initialize the class
-        // We should use this error instead of ignoring it.
-        if SHOULD_CLINIT {
-            let mut found_clinit = true;
-            if let Err(e) = jvm.setup_method_call_from_name_on_main("<clinit>", "()V", true) {
-                if e != Error::NoSuchMethodError(Opcode::MethodInvoke) {
-                    println!("{e:#?}");
-                    return Err(e);
-                }
-                found_clinit = false;
-            }
-            if found_clinit {
-                jvm.run_until_method_exit();
-                if jvm.has_encoutered_error() {
-                    println!("Encountered error: {:#?}", jvm.m_thrown_error);
-                }
-            }
-        }
+        // The main class gets initialized right before we call main, see excecute().
         Ok(jvm)
     }
 }
 
 impl JVM {
     pub fn load_class_file(&mut self, path: &str) -> Result<(), Error> {         
-        let mut resolved_path = String::from(path);
-        if &path[0..4] == "java" {
-            //TODO: Add java class path
-            // For now we just hardcode the directory, in the future we will have to change that
-            let mut temp_str = match self.class_path.as_ref() {
-                Some(s) => s.clone(),
-                None => String::from("./classlibs/17/modules/java.base/"),
-            };
-            temp_str.push_str(&resolved_path);
-            resolved_path = temp_str;
-        }
-        resolved_path.push_str(".class");
-        let mut f = match File::open(resolved_path.as_str()) {
-            Ok(file) => file,
-            Err(_) => {
-                println!("Path: {path}, Resolved Path: {resolved_path}");
-                return Err(Error::NoClassDefFoundError(Opcode::ClassLoad, String::from(path)));
+        let bytes = match self.boot_class_path.find_class(path)? {
+            Some(b) => b,
+            None => match self.class_path.find_class(path)? {
+                Some(b) => b,
+                None => return Err(Error::NoClassDefFoundError(Opcode::ClassLoad, String::from(path))),
             },
         };
-        let metadata = fs::metadata(&resolved_path).unwrap();
-        let size = if metadata.len() % 8 == 0 {metadata.len() / 8} else {metadata.len() / 8 + 1};
-        let buffer: Vec<u64> = vec![0; size as usize];
-        let (file, code) = unsafe {
-            let buf_bytes = std::slice::from_raw_parts_mut(buffer.as_ptr() as *mut u8, buffer.len() * std::mem::size_of::<u64>());
-            f.read(buf_bytes).unwrap();
-            class::classfile::ClassFile::new(buf_bytes)?
-        };
+        let (file, code) = load_class_from_bytes(&bytes)?;
         let c = class::new_class(file, self)?;
         // Adding the class to the map here seems a bit weird, but if we don't we overflow the stack.
         self.m_loaded_classes.insert(String::from(c.get_class_file().name()), Rc::clone(&c)); 
         // init the code here to prevent endless recursion
         unsafe { Rc::get_mut_unchecked(&mut c.get_class_file())}.init_code(code, self)?;
-        // clinit if a class has it.
-        if SHOULD_CLINIT {
-            let mut found_clinit = true;
-            if let Err(e) = self.setup_method_call_from_name("<clinit>", "()V", c, true) {
-                if e != Error::NoSuchMethodError(Opcode::MethodInvoke) {
+        // Classes are initialized lazily, the first time they're actively used (see initialize_class()).
+        Ok(())
+    }
+    pub fn resolve_class_reference(&mut self, reference: &str) -> Result<Rc<dyn Class>, Error> {
+        if !self.m_loaded_classes.contains_key(reference) {
+            self.load_class_file(reference)?;
+        }
+        Ok(self.m_loaded_classes.get(reference).unwrap().clone())
+    }
+    // Gets the java.lang.Class object for a field descriptor (or V), making it the first time it's asked for.
+    pub fn class_mirror(&mut self, descriptor: &str) -> Result<Reference<dyn Class, dyn Object>, Error> {
+        if let Some(mirror) = self.m_class_mirrors.get(descriptor) {
+            return Ok(mirror.clone());
+        }
+        let element = descriptor.trim_start_matches('[');
+        let mirrored = match &element[0..1] {
+            "B" | "C" | "D" | "F" | "I" | "J" | "S" | "Z" | "V" if element.len() == 1 => {
+                if element == "V" && element.len() != descriptor.len() {
+                    return Err(Error::IllegalDescriptor);
+                }
+                None
+            },
+            "L" if element.ends_with(';') => Some(self.resolve_class_reference(&element[1..element.len() - 1])?),
+            _ => return Err(Error::IllegalDescriptor),
+        };
+        // Arrays don't have a class of their own, so they only hold onto their element class.
+        let mirrored = if element.len() == descriptor.len() { mirrored } else { None };
+        let mirror = natives::class_mirror::ClassMirror::new_with_descriptor(descriptor, mirrored, self)?;
+        let mirror = Reference::Object(mirror as Rc<dyn Object>, Rc::new(Monitor::new()));
+        self.m_class_mirrors.insert(String::from(descriptor), mirror.clone());
+        Ok(mirror)
+    }
+    // Same as class_mirror, but for a name from a CONSTANT_Class_info, which is either a class name or an array descriptor.
+    pub fn class_mirror_from_name(&mut self, name: &str) -> Result<Reference<dyn Class, dyn Object>, Error> {
+        if name.starts_with('[') {
+            self.class_mirror(name)
+        }
+        else {
+            self.class_mirror(&format!("L{name};"))
+        }
+    }
+    // Resolves one of the loadable constants that need more than just copying out of the constant pool.
+    // Resolving happens once for each entry, and not for each ldc, so every ldc of an entry gets the same object back,
+    // and one that failed keeps failing the same way. See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3
+    pub fn load_constant(&mut self, current_class: Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<Value<dyn Class, dyn Object>, Error> {
+        let class_file = current_class.get_class_file();
+        if let Entry::Class(c) = class_file.cp_entry(index)? {
+            // There's only one mirror for each class anyway.
+            return Ok(Value::Reference(self.class_mirror_from_name(class_file.cp_entry(*c)?.as_utf8()?)?));
+        }
+        let resolved = self.resolve_cached(&current_class, index, |jvm| {
+            Ok(match class_file.cp_entry(index)? {
+                Entry::MethodType(d) => Resolved::MethodType(invoke::method_type_object(jvm, class_file.cp_entry(*d)?.as_utf8()?)?),
+                Entry::MethodHandle(_) => {
+                    let handle = invoke::MethodHandle::resolve(jvm, &current_class, index, opcode.clone())?;
+                    Resolved::MethodHandle(invoke::method_handle_object(jvm, handle)?)
+                },
+                Entry::Dynamic(_) => Resolved::Dynamic(invoke::resolve_dynamic_constant(jvm, &current_class, index, opcode.clone())?),
+                _ => return Err(Error::IllegalConstantLoad(opcode.clone())),
+            })
+        })?;
+        match resolved {
+            Resolved::MethodType(r) | Resolved::MethodHandle(r) => Ok(Value::Reference(r)),
+            Resolved::Dynamic(value) => Ok(value),
+            _ => Err(Error::IllegalConstantLoad(opcode)),
+        }
+    }
+    pub fn is_initialized(&self, class: &Rc<dyn Class>) -> bool {
+        self.m_init_states.get(class.get_class_file().name()) == Some(&InitState::Initialized)
+    }
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.5
+    // This gets called on the first new, getstatic, putstatic, or invokestatic that refers to a class.
+    pub fn initialize_class(&mut self, class: Rc<dyn Class>) -> Result<(), Error> {
+        let name = String::from(class.get_class_file().name());
+        match self.m_init_states.get(&name) {
+            Some(InitState::Initialized) | Some(InitState::BeingInitialized) => return Ok(()),
+            Some(InitState::Erroneous) => {
+                return self.throw_exception_with_message("java/lang/NoClassDefFoundError", format!("Could not initialize class {}", name.replace('/', ".")));
+            },
+            None => {},
+        }
+        self.m_init_states.insert(name.clone(), InitState::BeingInitialized);
+        if let Err(e) = self.initialize_supers(Rc::clone(&class)) {
+            self.m_init_states.insert(name, InitState::Erroneous);
+            return Err(e);
+        }
+        // Native classes set up their own statics, and we can't run their <clinit>s anyway.
+        if !class.as_any().is::<CustomClass>() {
+            self.m_init_states.insert(name, InitState::Initialized);
+            return Ok(());
+        }
+        let class_file = class.get_class_file();
+        // Strings can't be made until we have a JVM, so they're filled in here instead of in CustomClass::new.
+        for field in class_file.fields() {
+            if (field.access_flags.flags & flags::field::ACC_STATIC) == 0 {
+                continue;
+            }
+            if let Some(index) = field.constant_value {
+                if let Entry::String(s) = class_file.cp_entry(index)? {
+                    let s = natives::string::String::new_from_string(class_file.cp_entry(*s)?.as_utf8()?.clone(), self)?;
+                    let s = Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new())));
+                    let field_name = class_file.cp_entry(field.name_index)?.as_utf8()?;
+                    let field_desc = class_file.cp_entry(field.descriptor_index)?.as_utf8()?;
+                    unsafe { Rc::get_mut_unchecked(&mut Rc::clone(&class)) }.put_static(field_name, field_desc, s, self)?;
+                }
+            }
+        }
+        let mut clinit = None;
+        for method in class_file.methods() {
+            if class_file.cp_entry(method.name_index)?.as_utf8()? == "<clinit>" {
+                clinit = Some(method.clone());
+                break;
+            }
+        }
+        if let Some(clinit) = clinit {
+            let depth = self.current_thread().m_stack.len();
+            self.setup_method_call(&clinit, Rc::clone(&class), true)?;
+            let exception = match self.run_until_stack_depth(depth) {
+                Ok(e) => e,
+                Err(e) => {
+                    self.m_init_states.insert(name, InitState::Erroneous);
                     return Err(e);
+                },
+            };
+            if let Some(exception) = exception {
+                self.m_init_states.insert(name.clone(), InitState::Erroneous);
+                if depth == 0 {
+                    // There's no frame to throw into, which only happens for the main class.
+                    return Err(Error::ExceptionInInitializerError(Opcode::ClassInit, name));
+                }
+                let exception_class = match &exception {
+                    Value::Reference(Reference::Object(o, _)) => o.class(),
+                    _ => return Err(Error::IncorrectReferenceType(Opcode::ClassInit)),
+                };
+                // Errors get passed on as they are, and everything else gets wrapped.
+                if self.is_subclass(exception_class, "java/lang/Error")? {
+                    let thread = access_macros::current_thread_mut!(self);
+                    let frame = access_macros::current_frame_mut!(thread);
+                    frame.op_stack.push(exception);
+                    return Err(Error::Exception);
+                }
+                return self.throw_exception("java/lang/ExceptionInInitializerError", "(Ljava/lang/Throwable;)V", vec![exception]);
+            }
+        }
+        self.m_init_states.insert(name, InitState::Initialized);
+        Ok(())
+    }
+    // Finds the class or interface that actually declares a static field, which is the one getstatic and putstatic initialize.
+    // If we can't find it, we just give back the class we started with, and let get_static and put_static complain.
+    pub fn static_field_class(&mut self, class: Rc<dyn Class>, name: &str, descriptor: &str) -> Result<Rc<dyn Class>, Error> {
+        match self.find_field(Rc::clone(&class), name, descriptor)? {
+            Some((c, true)) => Ok(c),
+            _ => Ok(class),
+        }
+    }
+    // Finds the class or interface that declares a field, and whether it's static.
+    // Same lookup order as https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.2
+    #[allow(clippy::type_complexity)]
+    fn find_field(&mut self, class: Rc<dyn Class>, name: &str, descriptor: &str) -> Result<Option<(Rc<dyn Class>, bool)>, Error> {
+        let class_file = class.get_class_file();
+        for field in class_file.fields() {
+            if class_file.cp_entry(field.name_index)?.as_utf8()? == name 
+                && class_file.cp_entry(field.descriptor_index)?.as_utf8()? == descriptor {
+                return Ok(Some((class, (field.access_flags.flags & flags::field::ACC_STATIC) > 0)));
+            }
+        }
+        for interface_index in class_file.interfaces() {
+            let interface_name_index = class_file.cp_entry(*interface_index)?.as_class()?;
+            let interface_name = class_file.cp_entry(*interface_name_index)?.as_utf8()?;
+            let interface = self.resolve_class_reference(interface_name)?;
+            if let Some(found) = self.find_field(interface, name, descriptor)? {
+                return Ok(Some(found));
+            }
+        }
+        match class_file.super_name() {
+            Some(super_name) => {
+                let super_class = self.resolve_class_reference(super_name)?;
+                self.find_field(super_class, name, descriptor)
+            },
+            None => Ok(None),
+        }
+    }
+    // Gives back what current_class's symbolic reference at index resolved to, running resolve the first time it's asked for.
+    // Failures are kept too, since later attempts have to fail the same way (see https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3).
+    fn resolve_cached(&mut self, current_class: &Rc<dyn Class>, index: u16, resolve: impl FnOnce(&mut JVM) -> Result<Resolved, Error>) -> Result<Resolved, Error> {
+        let resolved = match current_class.rt_const_pool().and_then(|pool| pool.get(index)) {
+            Some(resolved) => resolved,
+            None => {
+                let resolved = match resolve(self) {
+                    Ok(resolved) => resolved,
+                    // The exception is on the stack, so there's nothing to keep.
+                    Err(Error::Exception) => return Err(Error::Exception),
+                    Err(e) => Resolved::Failed(e),
+                };
+                if let Some(pool) = current_class.rt_const_pool() {
+                    pool.set(index, resolved.clone());
                 }
-                found_clinit = false;
+                resolved
             }
-            if found_clinit {
-                self.run_until_method_exit();
+        };
+        match resolved {
+            Resolved::Failed(e) => Err(e),
+            resolved => Ok(resolved),
+        }
+    }
+    // Resolves the CONSTANT_Class_info at index in current_class's constant pool.
+    pub fn resolve_class_entry(&mut self, current_class: &Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<Rc<dyn Class>, Error> {
+        let resolved = self.resolve_cached(current_class, index, |jvm| {
+            let class_file = current_class.get_class_file();
+            let name_index = class_file.cp_entry(index)?.as_class()?;
+            Ok(Resolved::Class(jvm.resolve_class_reference(class_file.cp_entry(*name_index)?.as_utf8()?)?))
+        })?;
+        match resolved {
+            Resolved::Class(c) => Ok(c),
+            _ => Err(Error::IllegalConstantLoad(opcode)),
+        }
+    }
+    // Resolves the CONSTANT_Fieldref_info at index in current_class's constant pool.
+    pub fn resolve_field_ref(&mut self, current_class: &Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<Rc<FieldRef>, Error> {
+        let class_file = current_class.get_class_file();
+        let resolved = self.resolve_cached(current_class, index, |jvm| {
+            let field_ref = class_file.cp_entry(index)?.as_field_ref()?;
+            let class = jvm.resolve_class_entry(current_class, field_ref.class_index, opcode.clone())?;
+            let name_and_type = class_file.cp_entry(field_ref.name_and_type_index)?.as_name_and_type()?;
+            let name = class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
+            let descriptor = class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
+            match jvm.find_field(class, name, descriptor)? {
+                Some((class, is_static)) => Ok(Resolved::Field(Rc::new(FieldRef::new(
+                    class, 
+                    NameAndType { name: name.clone(), descriptor: descriptor.clone() }, 
+                    is_static,
+                )))),
+                None => Err(Error::NoSuchFieldError(opcode.clone())),
             }
+        })?;
+        match resolved {
+            Resolved::Field(f) => Ok(f),
+            _ => Err(Error::IllegalConstantLoad(opcode)),
+        }
+    }
+    // Resolves the CONSTANT_Methodref_info or CONSTANT_InterfaceMethodref_info at index in current_class's constant pool.
+    pub fn resolve_method_ref(&mut self, current_class: &Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<Rc<MethodRef>, Error> {
+        let class_file = current_class.get_class_file();
+        let resolved = self.resolve_cached(current_class, index, |jvm| {
+            let (method_ref, is_interface) = match class_file.cp_entry(index)? {
+                Entry::MethodRef(method_ref) => (method_ref, false),
+                Entry::InterfaceMethodRef(method_ref) => (method_ref, true),
+                _ => return Err(Error::IllegalConstantLoad(opcode.clone())),
+            };
+            // Arrays don't have a class of their own, and all of their methods come from Object, like clone.
+            // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.3
+            let class_name_index = class_file.cp_entry(method_ref.class_index)?.as_class()?;
+            let symbolic_class = if class_file.cp_entry(*class_name_index)?.as_utf8()?.starts_with('[') {
+                jvm.resolve_class_reference("java/lang/Object")?
+            }
+            else {
+                jvm.resolve_class_entry(current_class, method_ref.class_index, opcode.clone())?
+            };
+            let name_and_type = class_file.cp_entry(method_ref.name_and_type_index)?.as_name_and_type()?;
+            let name = class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
+            let descriptor = class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
+            let (class, _) = if is_interface {
+                jvm.resolve_interface_method(Rc::clone(&symbolic_class), name, descriptor, opcode.clone())?
+            } else {
+                jvm.resolve_class_method(Rc::clone(&symbolic_class), name, descriptor, opcode.clone())?
+            };
+            let index = match JVM::find_declared_method_index(&class, name, descriptor)? {
+                Some(index) => index,
+                None => return Err(Error::NoSuchMethodError(opcode.clone())),
+            };
+            Ok(Resolved::Method(Rc::new(MethodRef { symbolic_class, class, index, name: name.clone(), descriptor: descriptor.clone() })))
+        })?;
+        match resolved {
+            Resolved::Method(m) => Ok(m),
+            _ => Err(Error::IllegalConstantLoad(opcode)),
+        }
+    }
+    // Classes need their superclass initialized first, along with any superinterfaces that have default methods.
+    // Interfaces don't initialize their superinterfaces.
+    fn initialize_supers(&mut self, class: Rc<dyn Class>) -> Result<(), Error> {
+        if class.get_class_file().is_interface() {
+            return Ok(());
+        }
+        if let Some(super_name) = class.get_class_file().super_name() {
+            let super_class = self.resolve_class_reference(super_name)?;
+            self.initialize_class(super_class)?;
+        }
+        let mut interfaces = Vec::new();
+        self.default_method_interfaces(class, &mut interfaces)?;
+        for interface in interfaces {
+            self.initialize_class(interface)?;
         }
         Ok(())
     }
-    // TODO ADD CLASSPATH
-    pub fn resolve_class_reference(&mut self, reference: &str) -> Result<Rc<dyn Class>, Error> {
-        if !self.m_loaded_classes.contains_key(reference) {
-            self.load_class_file(reference)?;
+    // Finds the superinterfaces of a class that declare non-abstract, non-static methods, 
+    // with each interface coming after its own superinterfaces.
+    fn default_method_interfaces(&mut self, class: Rc<dyn Class>, found: &mut Vec<Rc<dyn Class>>) -> Result<(), Error> {
+        let class_file = class.get_class_file();
+        for interface_index in class_file.interfaces() {
+            let interface_name_index = class_file.cp_entry(*interface_index)?.as_class()?;
+            let interface_name = class_file.cp_entry(*interface_name_index)?.as_utf8()?;
+            let interface = self.resolve_class_reference(interface_name)?;
+            self.default_method_interfaces(Rc::clone(&interface), found)?;
+            let has_default = interface.get_class_file().methods().iter().any(|m| 
+                (m.access_flags.flags & (flags::method::ACC_ABSTRACT | flags::method::ACC_STATIC)) == 0);
+            if has_default && !found.iter().any(|i| i.get_class_file().name() == interface_name) {
+                found.push(interface);
+            }
         }
-        Ok(self.m_loaded_classes.get(reference).unwrap().clone())
+        Ok(())
     }
     // Use this for checking that the class derived is above the given class in the heiriarchy. 
     pub fn resolve_with_derived_class(&self, _reference: &str, _derived: Rc<dyn Class>) -> Result<Rc<dyn Class>, &'static str> {
@@ -273,6 +623,50 @@ impl JVM {
     pub fn current_thread(&self) -> &Thread {
         &self.m_threads[self.m_thread_index]
     }
+    // The id that monitors know the current thread by, which unlike m_thread_index doesn't change as other threads finish.
+    pub fn current_thread_id(&self) -> usize {
+        self.current_thread().id
+    }
+    // Whether the thread with this id is still running (or blocked).
+    pub fn is_alive(&self, id: usize) -> bool {
+        self.m_threads.iter().any(|t| t.id == id)
+    }
+    // Finds the thread that a java.lang.Thread is running on, if it's been started and hasn't finished.
+    pub fn thread_with_object(&mut self, object: &Reference<dyn Class, dyn Object>) -> Option<&mut Thread> {
+        self.m_threads.iter_mut().find(|t| !t.object.is_null() && Reference::ptr_eq(&t.object, object))
+    }
+    // Starts a new thread that calls method on object, for Thread.start. It gets its first turn once the scheduler comes around to it.
+    pub fn spawn_thread(&mut self, object: Reference<dyn Class, dyn Object>, is_daemon: bool, class: Rc<dyn Class>, method: &MethodInfo) -> Result<(), Error> {
+        let code = match &method.code {
+            Some(code) => code,
+            None => return Err(Error::AbstractMethodError(Opcode::MethodInvoke)),
+        };
+        let mut frame = Frame::new_with_stack_size(class, method.clone(), code.max_locals.into(), code.max_stack.into());
+        frame.local_variables[0] = VarValue::Reference(object.clone());
+        let mut thread = Thread::new_with_object(self.m_next_thread_id, object, is_daemon);
+        self.m_next_thread_id += 1;
+        thread.push_frame(frame);
+        self.m_threads.push(thread);
+        Ok(())
+    }
+    // Whether the current thread can block. It can't while an instruction is running code itself (like a <clinit>), 
+    // since nothing else runs until that's done.
+    pub fn can_block(&self) -> bool {
+        self.m_executing.len() <= 1
+    }
+    // Takes the current thread out of the schedule once it's done, and marks its java.lang.Thread as terminated.
+    fn exit_thread(&mut self) {
+        let thread = self.m_threads.remove(self.m_thread_index);
+        if !thread.object.is_null() {
+            if let Err(e) = class::natives::thread::Thread::terminate(self, &thread.object) {
+                self.m_thrown_error = e;
+            }
+        }
+        // The scheduler moves on to whatever thread is now at m_thread_index, which might be off the end.
+        if self.m_thread_index >= self.m_threads.len() {
+            self.m_thread_index = 0;
+        }
+    }
     pub fn throw(&mut self, err: Error) {
         self.m_thrown_error = err;
     }
@@ -287,145 +681,361 @@ impl JVM {
         (self.m_thrown_error != Error::None) || self.m_crash_info.has_crashed   
     }
     pub fn step(&mut self, step_size: usize) {
-        // First, before running the cycle, we check if the thread is waiting on a monitor. 
-        // If it is, then we attempt to own it and check the result. If we don't own it, we can't progress, so we just exit.
-        {
-            let current_thread_index = self.m_thread_index;
-            let thread = access_macros::current_thread_mut!(self);
-            if thread.current_monitor.is_some() {
-                let monitor_rc = match &mut thread.current_monitor {
-                    Some(mrc) => mrc,
-                    None => unreachable!(),
-                };
-                let monitor = match Rc::get_mut(monitor_rc) {
-                    Some(m) => m,
-                    None => {
-                        self.m_thrown_error = Error::DoubleMutableReferenceToMonitor(Opcode::BlockedThread);
-                        return;
-                    },
-                };
-                let success = monitor.try_enter(current_thread_index);
-                if !success {
-                    // Move on to the next thread.
-                    return;
-                }
-            }
+        // First, before running the cycle, we check if the thread is blocked on a monitor, asleep, or joining another thread.
+        // If it still can't run, we just exit and move on to the next thread.
+        if !self.wake_thread() {
+            return;
         }
         for _ in 0..step_size {
-            let old_pc = self.current_thread().pc();
             let old_frame_num = self.current_thread().m_stack.len();
-            self.step1();
+            let flow = self.step1();
             if self.has_encoutered_error() | self.m_has_halted {
                 break;
             }
             let thread = current_thread_mut!(self);
             if thread.m_stack.len() == 0 {
                 // Done with code, exit.
-                self.m_threads.remove(self.m_thread_index);
+                self.exit_thread();
+                return;
+            }
+            if let Some(flow) = flow {
+                self.advance_pc(flow, old_frame_num);
+            }
+            if self.current_thread().is_blocked() {
+                // Blocked, so let the other threads run.
                 return;
             }
-            if (self.current_thread().pc() == old_pc && self.current_thread().m_stack.len() == old_frame_num) || self.current_thread().m_stack.len() < old_frame_num {
-                // Means we haven't made any jumps, increment. 
-                // Technically this allows for jumps down. This is intentional, because otherwise we would jump down to the instruction that jumped us up,
-                // creating an infinite loop.
-                // The only way this breaks is if an instruction jumps to itself.
-                // FIXME: Deal with these types of infinite loops.
-                let thread = current_thread_mut!(self);
-                thread.inc_pc(1); // IMPORTANT: Account for this in instructions.
+        }
+    }
+    // Gets the current thread ready to run if whatever it was blocked on is done, and gives back whether it can run.
+    fn wake_thread(&mut self) -> bool {
+        let current_thread_id = self.current_thread_id();
+        let now = Instant::now();
+        let thread = current_thread_mut!(self);
+        if let Some(monitor) = &thread.current_monitor {
+            // The instruction that blocked runs again and enters it.
+            if !monitor.can_enter(current_thread_id) {
+                return false;
             }
+            thread.current_monitor = None;
         }
+        let state = thread.state;
+        let (interruptible, woken) = match state {
+            ThreadState::Runnable => return true,
+            ThreadState::Yielding => (false, true),
+            ThreadState::Sleeping(until) => (true, now >= until),
+            ThreadState::Joining(id, deadline) => (true, deadline.is_some_and(|d| now >= d) || !self.is_alive(id)),
+            ThreadState::Waiting(deadline) => {
+                let Some((monitor, count)) = thread.waiting_on.clone() else {
+                    return false;
+                };
+                if monitor.is_waiting(current_thread_id) && deadline.is_none_or(|d| now < d) && !thread.interrupted {
+                    return false;
+                }
+                // Whatever woke it up, it has to get the monitor back before it can go on, and it stays waiting until it does.
+                monitor.stop_waiting(current_thread_id);
+                if !monitor.reenter(current_thread_id, count) {
+                    return false;
+                }
+                thread.waiting_on = None;
+                (true, true)
+            },
+        };
+        let thread = current_thread_mut!(self);
+        if interruptible && thread.interrupted {
+            // Sleep, join and wait have already returned, so we move back onto the invoke to throw from it.
+            thread.state = ThreadState::Runnable;
+            thread.interrupted = false;
+            current_frame_mut!(thread).pc -= 1;
+            if let Err(e) = self.throw_exception::<()>("java/lang/InterruptedException", "()V", vec![]) {
+                self.m_thrown_error = e;
+            }
+            return false;
+        }
+        if woken {
+            thread.state = ThreadState::Runnable;
+        }
+        woken
+    }
+    // Moves the pc on after an instruction ran, depending on what it did. See Flow.
+    fn advance_pc(&mut self, flow: Flow, old_frame_num: usize) {
+        let thread = current_thread_mut!(self);
+        let next = match flow {
+            Flow::Next | Flow::Return => true,
+            Flow::Jump => false,
+            Flow::Invoke => thread.m_stack.len() <= old_frame_num,
+        };
+        if next {
+            if let Err(e) = thread.inc_pc(1) {
+                self.m_thrown_error = e;
+            }
+        }
+    }
+    pub fn exit_code(&self) -> i32 {
+        self.m_exit_code
     }
     pub fn run(&mut self) {
         while !self.m_crash_info.has_crashed {
-            let old_num_threads = self.m_threads.len();
+            let current_thread_id = self.current_thread_id();
             self.step(self.m_step_size);
-            if self.m_thrown_error != Error::None {
-                if self.m_thrown_error == Error::Exception {
-                    let err = self.handle_exception();
-                    if err.is_err() {
-                        // Set up a crash reason. this function should only crash if something really bad went wrong.
-                    }
+            if self.end_turn() {
+                return;
+            }
+            // If the thread finished, the next one has already moved into its place.
+            if self.m_threads.get(self.m_thread_index).is_some_and(|t| t.id == current_thread_id) {
+                self.m_thread_index += 1;
+            }
+            if self.m_thread_index >= self.m_threads.len() {
+                self.m_thread_index = 0;
+                if self.report_deadlocks() {
+                    return;
                 }
-                else {
-                    // Some error has occured in our implementation.
-                    // This just means that we should crash.
-                    self.m_crash_info.has_crashed = true;
-                    // TODO: Add crash reason.
-                    self.m_crash_info.crash_reason = format!("{:#?}", self.m_thrown_error);
+                self.idle();
+            }
+        }
+    }
+    // What run does after each thread's turn. Gives back whether the JVM is done, because it crashed or halted,
+    // or because the thread that just ran was the last non-daemon one.
+    fn end_turn(&mut self) -> bool {
+        // Between steps, nothing is in the middle of an instruction, so it's a good time to collect.
+        if self.heap.should_collect() {
+            self.collect_garbage();
+        }
+        if self.m_thrown_error != Error::None {
+            if self.m_thrown_error == Error::Exception {
+                // It's been thrown, so now it's up to the handler.
+                self.m_thrown_error = Error::None;
+                let err = self.handle_exception();
+                if err.is_err() {
+                    // Set up a crash reason. this function should only crash if something really bad went wrong.
                 }
             }
-            if self.m_crash_info.has_crashed {
-                println!("JVM Crashing due to error {}", self.m_crash_info.crash_reason);
-                if (self.m_flags & settings::SHOULD_BACKTRACE) > 0 {
-                    println!("Backtrace:");
-                    for frame in access_macros::current_thread_mut!(self).m_stack.iter().rev() {
-                        let current_class = &frame.rt_const_pool;
-                        // TODO: Fix these unwrap calls.
-                        println!("Method name: {}, Method descriptor: {}, Method class: {}", current_class.get_class_file().cp_entry(frame.current_method.name_index).unwrap().as_utf8().unwrap(),
-                        current_class.get_class_file().cp_entry(frame.current_method.descriptor_index).unwrap().as_utf8().unwrap(), current_class.get_class_file().name());
-                        println!("Local variables:");
-                        for local in frame.local_variables.iter().rev() {
-                            println!("  {local:#?}");
-                        }
-                        println!("Operand stack:");
-                        for operand in frame.op_stack.iter().rev() {
-                            println!("  {operand:#?}");
-                        }
-                        println!("Current pc and instruction: {}, {}", frame.pc, frame.current_method.code_at(frame.pc).unwrap());
-                        println!("Current code: {}", frame.current_method.code.as_ref().unwrap());
-                        //println!("Current class: {}", current_class.get_class_file());
+            else {
+                // Some error has occured in our implementation.
+                // This just means that we should crash.
+                self.m_crash_info.has_crashed = true;
+                // TODO: Add crash reason.
+                self.m_crash_info.crash_reason = format!("{:#?}", self.m_thrown_error);
+            }
+        }
+        if self.m_crash_info.has_crashed {
+            println!("JVM Crashing due to error {}", self.m_crash_info.crash_reason);
+            // Same as a deadlock, whatever ran us should know it didn't go to plan.
+            self.m_exit_code = 1;
+            if (self.m_flags & settings::SHOULD_BACKTRACE) > 0 {
+                println!("Backtrace:");
+                for frame in access_macros::current_thread_mut!(self).m_stack.iter().rev() {
+                    let current_class = &frame.rt_const_pool;
+                    // TODO: Fix these unwrap calls.
+                    println!("Method name: {}, Method descriptor: {}, Method class: {}", current_class.get_class_file().cp_entry(frame.current_method.name_index).unwrap().as_utf8().unwrap(),
+                    current_class.get_class_file().cp_entry(frame.current_method.descriptor_index).unwrap().as_utf8().unwrap(), current_class.get_class_file().name());
+                    println!("Local variables:");
+                    for local in frame.local_variables.iter().rev() {
+                        println!("  {local:#?}");
+                    }
+                    println!("Operand stack:");
+                    for operand in frame.op_stack.iter().rev() {
+                        println!("  {operand:#?}");
                     }
+                    println!("Current pc and instruction: {}, {}", frame.pc, frame.current_method.code_at(frame.pc).unwrap());
+                    println!("Current code: {}", frame.current_method.code.as_ref().unwrap());
+                    //println!("Current class: {}", current_class.get_class_file());
                 }
-                return;
             }
-            if self.m_has_halted {
+            return true;
+        }
+        if self.m_has_halted {
+            return true;
+        }
+        // We're done once every non-daemon thread is, and any daemon threads left just stop.
+        if !self.m_threads.iter().any(|t| !t.is_daemon) {
+            return true;
+        }
+        false
+    }
+    // Reports a deadlock, if there is one, and gives back whether there was.
+    fn report_deadlocks(&mut self) -> bool {
+        // Nothing is ever going to let go of the monitors in a deadlock, so all we can do is say so and stop.
+        let deadlocks = self.find_deadlocks();
+        if !deadlocks.is_empty() {
+            match self.deadlock_report(&deadlocks) {
+                Ok(report) => eprint!("{report}"),
+                Err(e) => eprintln!("Found a Java-level deadlock, but couldn't report it: {e:?}"),
+            }
+            self.m_exit_code = 1;
+            return true;
+        }
+        false
+    }
+    // Frees the garbage the Rcs can't, which is cycles, and gives back how many objects and arrays that was. See crate::heap.
+    pub fn collect_garbage(&mut self) -> usize {
+        let mut roots = Vec::new();
+        for thread in &self.m_threads {
+            roots.push(thread.object.clone());
+            for frame in &thread.m_stack {
+                roots.extend(frame.local_variables.iter().filter_map(|v| match v {
+                    VarValue::Reference(r) => Some(r.clone()),
+                    _ => None,
+                }));
+                roots.extend(frame.op_stack.iter().filter_map(|v| v.as_reference().ok()));
+            }
+        }
+        for class in self.m_loaded_classes.values() {
+            class.trace_statics(&mut |r| roots.push(r.clone()));
+        }
+        roots.extend(self.m_class_mirrors.values().cloned());
+        self.heap.collect(roots)
+    }
+    // Makes sure there's room on the heap for size more bytes, collecting first if there isn't,
+    // and throws an OutOfMemoryError if that doesn't free up enough.
+    pub fn reserve_heap(&mut self, size: usize) -> Result<(), Error> {
+        if self.heap.fits(size) {
+            return Ok(());
+        }
+        self.collect_garbage();
+        if self.heap.fits(size) {
+            return Ok(());
+        }
+        // Making the error allocates too, and it shouldn't run out of memory itself.
+        let max = self.heap.max();
+        self.heap.set_max(None);
+        let result = self.throw_exception_with_message("java/lang/OutOfMemoryError", String::from("Java heap space"));
+        self.heap.set_max(max);
+        result
+    }
+    // Makes sure the current thread has room for a frame of size more bytes, and throws a StackOverflowError if it doesn't.
+    pub fn reserve_stack(&mut self, size: usize) -> Result<(), Error> {
+        if self.current_thread().stack_size + size <= self.max_stack_size {
+            return Ok(());
+        }
+        let stack_trace = self.stack_trace()?;
+        // The error's constructor needs frames of its own, so like HotSpot's guard pages, it gets to go past the limit.
+        let max = self.max_stack_size;
+        self.max_stack_size = usize::MAX;
+        let result = self.throw_exception("java/lang/StackOverflowError", "()V", vec![]);
+        self.max_stack_size = max;
+        if result == Err(Error::Exception) {
+            // Throwable's constructor left its stack trace to fillInStackTrace, which we don't have, so we fill it in ourselves.
+            let thread = access_macros::current_thread_mut!(self);
+            if let Some(Value::Reference(Reference::Object(error, _))) = access_macros::current_frame_mut!(thread).op_stack.last() {
+                let mut error = Rc::clone(error);
+                let field = FieldRef::new(
+                    self.resolve_class_reference("java/lang/Throwable")?,
+                    NameAndType { name: String::from("stackTrace"), descriptor: String::from("[Ljava/lang/StackTraceElement;") },
+                    false,
+                );
+                unsafe { Rc::get_mut_unchecked(&mut error) }.put_field(&field, self, Value::Reference(stack_trace))?;
+            }
+        }
+        result
+    }
+    // The current thread's stack as an array of StackTraceElements, innermost first, cut off at MAX_STACK_TRACE_DEPTH frames.
+    pub fn stack_trace(&mut self) -> Result<Reference<dyn Class, dyn Object>, Error> {
+        let class = self.resolve_class_reference("java/lang/StackTraceElement")?;
+        self.initialize_class(Rc::clone(&class))?;
+        let field = |name: &str, descriptor: &str| FieldRef::new(
+            Rc::clone(&class),
+            NameAndType { name: String::from(name), descriptor: String::from(descriptor) },
+            false,
+        );
+        let frames: Vec<(String, String, Option<String>, Option<u16>)> = self.current_thread().m_stack.iter().rev().take(MAX_STACK_TRACE_DEPTH)
+            .map(|f| (f.rt_const_pool.get_class_file().name().replace('/', "."), f.method_name(), f.source_file(), f.line_number()))
+            .collect();
+        let mut array = Array::new_ref(frames.len(), String::from("[Ljava/lang/StackTraceElement;"));
+        for (index, (class_name, method_name, source_file, line_number)) in frames.into_iter().enumerate() {
+            let mut element = object::new_object_with_name("java/lang/StackTraceElement", self)?;
+            let class_name = Reference::Object(natives::string::String::new_from_string(class_name, self)? as Rc<dyn Object>, Rc::new(Monitor::new()));
+            let method_name = Reference::Object(natives::string::String::new_from_string(method_name, self)? as Rc<dyn Object>, Rc::new(Monitor::new()));
+            let source_file = match source_file {
+                Some(source_file) => Reference::Object(natives::string::String::new_from_string(source_file, self)? as Rc<dyn Object>, Rc::new(Monitor::new())),
+                None => Reference::Null,
+            };
+            let element_mut = unsafe { Rc::get_mut_unchecked(&mut element) };
+            element_mut.put_field(&field("declaringClass", "Ljava/lang/String;"), self, Value::Reference(class_name))?;
+            element_mut.put_field(&field("methodName", "Ljava/lang/String;"), self, Value::Reference(method_name))?;
+            element_mut.put_field(&field("fileName", "Ljava/lang/String;"), self, Value::Reference(source_file))?;
+            element_mut.put_field(&field("lineNumber", "I"), self, Value::Int(line_number.map_or(-1, i32::from)))?;
+            array.set(index, Value::Reference(Reference::Object(element, Rc::new(Monitor::new()))))?;
+        }
+        let array = Rc::new(array);
+        self.heap.track_array(&array);
+        Ok(Reference::Array(array, Rc::new(Monitor::new())))
+    }
+    // Called at the end of each round. If every thread is asleep, or waiting on a thread or a notify, there's nothing to run 
+    // until the first one wakes up, so we sleep too instead of spinning.
+    fn idle(&self) {
+        let mut wake: Option<Instant> = None;
+        for thread in &self.m_threads {
+            if thread.current_monitor.is_some() || thread.interrupted {
                 return;
             }
-            if self.m_threads.len() < old_num_threads {
-                // Don't inc idx and possibly exit
-                if self.m_threads.len() == 0 {
-                    return;
-                }
-            } else {
-                self.m_thread_index += 1;
+            let deadline = match thread.state {
+                ThreadState::Sleeping(until) => Some(until),
+                ThreadState::Joining(id, deadline) if self.is_alive(id) => deadline,
+                ThreadState::Waiting(deadline) if thread.waiting_on.as_ref().is_some_and(|(m, _)| m.is_waiting(thread.id)) => deadline,
+                _ => return,
+            };
+            if let Some(deadline) = deadline {
+                wake = Some(wake.map_or(deadline, |w| w.min(deadline)));
             }
-            self.m_thread_index %= self.m_threads.len();
+        }
+        #[cfg(not(target_family = "wasm"))]
+        if let Some(wake) = wake {
+            std::thread::sleep(wake.saturating_duration_since(Instant::now()));
         }
     }
     pub fn run_until_method_exit(&mut self) {
-
         // This function is used for calling methods while in an instructions. 
         // It is most often used for initializing instances of exceptions.
         let starting_call_stack_size = {
             let current_thread = access_macros::current_thread_mut!(self);
             current_thread.m_stack.len()
         };
-        let mut last_call_stack_size = starting_call_stack_size;
+        self.step_until_stack_depth(starting_call_stack_size - 1);
+    }
+    // Steps the current thread until it has at most `depth` frames, or until something goes wrong.
+    fn step_until_stack_depth(&mut self, depth: usize) {
         loop {
-            let old_pc = self.current_thread().pc();
-            self.step1();
+            let old_frame_num = self.current_thread().m_stack.len();
+            let flow = self.step1();
             if self.has_encoutered_error() {
                 break;
             }
-            let current_thread = access_macros::current_thread_mut!(self);
-            let current_call_stack_size = current_thread.m_stack.len();
-            if current_call_stack_size < starting_call_stack_size {
+            // Once we're back down to `depth`, the frame on top is in the middle of the instruction that called us, 
+            // so that instruction moves its own pc on when it finishes.
+            if access_macros::current_thread_mut!(self).m_stack.len() <= depth {
                 break;
             }
-            if (current_thread.pc() == old_pc && current_call_stack_size == last_call_stack_size) || current_call_stack_size < last_call_stack_size {
-                // Means we haven't made any jumps, increment. 
-                // Technically this allows for jumps down. This is intentional, because otherwise we would jump down to the instruction that jumped us up,
-                // creating an infinite loop.
-                // The only way this breaks is if an instruction jumps to itself.
-                // FIXME: Deal with these types of infinite loops.
-                current_thread.inc_pc(1); // IMPORTANT: Account for this in instructions.
+            if let Some(flow) = flow {
+                self.advance_pc(flow, old_frame_num);
             }
-            last_call_stack_size = current_call_stack_size;
         }
     }
-    // This class doesn't take a reference, because execution should be the end of a JVM.
-    pub fn excecute(mut self) {
-        if let Err(e) = self.setup_method_call_from_name_on_main("main", "([Ljava/lang/String;)V", true) {
+    // Like run_until_method_exit, but exceptions thrown by the code we're running get handled as long as they're caught above `depth`.
+    // If an exception makes it all the way down, we give it back instead of passing it on to the frames below.
+    pub fn run_until_stack_depth(&mut self, depth: usize) -> Result<Option<Value<dyn Class, dyn Object>>, Error> {
+        loop {
+            self.step_until_stack_depth(depth);
+            match mem::replace(&mut self.m_thrown_error, Error::None) {
+                Error::None => return Ok(None),
+                Error::Exception => {
+                    if let Some(exception) = self.unwind_exception(depth)? {
+                        return Ok(Some(exception));
+                    }
+                },
+                e => return Err(e),
+            }
+        }
+    }
+    // This class doesn't take a reference, because execution should be the end of a JVM. Gives back the exit code.
+    pub fn excecute(mut self) -> i32 {
+        // The main class has to be initialized before main gets called, see https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.2
+        let main_class_name = self.m_main_class_name.clone();
+        let result = match self.resolve_class_reference(&main_class_name) {
+            Ok(c) => self.initialize_class(c),
+            Err(e) => Err(e),
+        }.and_then(|_| self.setup_method_call_from_name_on_main("main", "([Ljava/lang/String;)V", true));
+        if let Err(e) = result {
             if e != Error::None {
                 if e == Error::Exception {
                     let err = self.handle_exception();
@@ -443,262 +1053,62 @@ impl JVM {
             }
             if self.m_crash_info.has_crashed {
                 println!("JVM Crashing due to error {}", self.m_crash_info.crash_reason);
+                self.m_exit_code = 1;
             }
         }
-        self.run()
+        #[cfg(not(target_family = "wasm"))]
+        if (self.m_flags & settings::SHOULD_USE_OS_THREADS) > 0 {
+            self.run_on_os_threads();
+            return self.m_exit_code;
+        }
+        self.run();
+        self.m_exit_code
     }
 }
 
 
 impl JVM {
-    pub fn step1(&mut self) {
-        let (opcode, cname, name, desc, pc) = {
+    // Runs one instruction, and gives back what it did with control, or None if it didn't finish.
+    pub fn step1(&mut self) -> Option<Flow> {
+        let op = {
             let thread = current_thread_mut!(self);
             if thread.m_stack.is_empty() {
                 self.m_has_halted = true;
-                return;
+                return None;
             }
             let pc = thread.pc();
-
-            let frame = current_frame_mut!(thread);
-            let rt = frame.rt_const_pool.get_class_file();
-            let cname = String::from(rt.name());
-            let name = rt.cp_entry(frame.current_method.name_index).unwrap().as_utf8().unwrap().clone();
-            let desc = rt.cp_entry(frame.current_method.descriptor_index).unwrap().as_utf8().unwrap().clone();
-
-            (frame.current_method.code_at_mut(pc), cname, name, desc, frame.pc)
-        };
-        if let Err(e) = opcode {
-            self.m_thrown_error = e;
-            return;
-        }
-        let op = opcode.unwrap() as *mut Box<dyn Instruction>;
-        //println!("Executing {} at {} in method {}.{}{}", unsafe { (*op).name() }, pc, cname, name, desc);
-        /* 
-        let err = match op {
-            0 => self.nop(),
-            1 => self.aconst_null(),
-            2 => self.iconst_m1(),
-            3 => self.iconst_0(),
-            4 => self.iconst_1(),
-            5 => self.iconst_2(),
-            6 => self.iconst_3(),
-            7 => self.iconst_4(),
-            8 => self.iconst_5(),
-            9 => self.lconst_0(),
-            10 => self.lconst_1(),
-            11 => self.fconst_0(),
-            12 => self.fconst_1(),
-            13 => self.fconst_2(),
-            14 => self.dconst_0(),
-            15 => self.dconst_1(),
-            16 => self.bipush(),
-            17 => self.sipush(),
-            18 => self.ldc(),
-            19 => self.ldc_w(),
-            20 => self.ldc2_w(),
-            21 => self.iload(wide),
-            22 => self.lload(wide),
-            23 => self.fload(wide),
-            24 => self.dload(wide),
-            25 => self.aload(wide),
-            26 => self.iload_0(),
-            27 => self.iload_1(),
-            28 => self.iload_2(),
-            29 => self.iload_3(),
-            30 => self.lload_0(),
-            31 => self.lload_1(),
-            32 => self.lload_2(),
-            33 => self.lload_3(),
-            34 => self.fload_0(),
-            35 => self.fload_1(),
-            36 => self.fload_2(),
-            37 => self.fload_3(),
-            38 => self.dload_0(),
-            39 => self.dload_1(),
-            40 => self.dload_2(),
-            41 => self.dload_3(),
-            42 => self.aload_0(),
-            43 => self.aload_1(),
-            44 => self.aload_2(),
-            45 => self.aload_3(),
-            46 => self.iaload(),
-            47 => self.laload(),
-            48 => self.faload(),
-            49 => self.daload(),
-            50 => self.aaload(),
-            51 => self.baload(),
-            52 => self.caload(),
-            53 => self.saload(),
-            54 => self.istore(wide),
-            55 => self.lstore(wide),
-            56 => self.fstore(wide),
-            57 => self.dstore(wide),
-            58 => self.astore(wide),
-            59 => self.istore_0(),
-            60 => self.istore_1(),
-            61 => self.istore_2(),
-            62 => self.istore_3(),
-            63 => self.lstore_0(),
-            64 => self.lstore_1(),
-            65 => self.lstore_2(),
-            66 => self.lstore_3(),
-            67 => self.fstore_0(),
-            68 => self.fstore_1(),
-            69 => self.fstore_2(),
-            70 => self.fstore_3(),
-            71 => self.dstore_0(),
-            72 => self.dstore_1(),
-            73 => self.dstore_2(),
-            74 => self.dstore_3(),
-            75 => self.astore_0(),
-            76 => self.astore_1(),
-            77 => self.astore_2(),
-            78 => self.astore_3(),
-            79 => self.iastore(),
-            80 => self.lastore(),
-            81 => self.fastore(),
-            82 => self.dastore(),
-            83 => self.aastore(),
-            84 => self.bastore(),
-            85 => self.castore(),
-            86 => self.sastore(),
-            87 => self.pop(),
-            88 => self.pop2(),
-            89 => self.dup(),
-            90 => self.dup_x1(),
-            91 => self.dup_x2(),
-            92 => self.dup2(),
-            93 => self.dup2_x1(),
-            94 => self.dup2_x2(),
-            95 => self.swap(),
-            96 => self.iadd(),
-            97 => self.ladd(),
-            98 => self.fadd(),
-            99 => self.dadd(),
-            100 => self.isub(),
-            101 => self.lsub(),
-            102 => self.fsub(),
-            103 => self.dsub(),
-            104 => self.imul(),
-            105 => self.lmul(),
-            106 => self.fmul(),
-            107 => self.dmul(),
-            108 => self.idiv(),
-            109 => self.ldiv(),
-            110 => self.fdiv(),
-            111 => self.ddiv(),
-            112 => self.irem(),
-            113 => self.lrem(),
-            114 => self.frem(),
-            115 => self.drem(),
-            116 => self.ineg(),
-            117 => self.lneg(),
-            118 => self.fneg(),
-            119 => self.dneg(),
-            120 => self.ishl(),
-            121 => self.lshl(),
-            122 => self.ishr(),
-            123 => self.lshr(),
-            124 => self.iushr(),
-            125 => self.lushr(),
-            126 => self.iand(),
-            127 => self.land(),
-            128 => self.ior(),
-            129 => self.lor(),
-            130 => self.ixor(),
-            131 => self.lxor(),
-            132 => self.iinc(),
-            133 => self.i2l(),
-            134 => self.i2f(),
-            135 => self.i2d(),
-            136 => self.l2i(),
-            137 => self.l2f(),
-            138 => self.l2d(),
-            139 => self.f2i(),
-            140 => self.f2l(),
-            141 => self.f2d(),
-            142 => self.d2i(),
-            143 => self.d2l(),
-            144 => self.d2f(),
-            145 => self.i2b(),
-            146 => self.i2c(),
-            147 => self.i2s(),
-            148 => self.lcmp(),
-            149 => self.fcmpl(),
-            150 => self.fcmpg(),
-            151 => self.dcmpl(),
-            152 => self.dcmpg(),
-            153 => self.ifeq(),
-            154 => self.ifne(),
-            155 => self.iflt(),
-            156 => self.ifge(),
-            157 => self.ifgt(),
-            158 => self.ifle(),
-            159 => self.if_icmpeq(),
-            160 => self.if_icmpne(),
-            161 => self.if_icmplt(),
-            162 => self.if_icmpge(),
-            163 => self.if_icmpgt(),
-            164 => self.if_icmple(),
-            165 => self.if_amcpeq(),
-            166 => self.if_amcpne(),
-            167 => self.goto(),
-            168 => self.jsr(),
-            169 => self.ret(),
-            170 => self.tableswitch(),
-            171 => self.lookupswitch(),
-            172 => self.ireturn(),
-            173 => self.lreturn(),
-            174 => self.freturn(),
-            175 => self.dreturn(),
-            176 => self.areturn(),
-            177 => self.return_(),
-            178 => self.getstatic(),
-            179 => self.putstatic(),
-            180 => self.getfield(),
-            181 => self.putfield(),
-            182 => self.invokevirtual(),
-            183 => self.invokespecial(),
-            184 => self.invokestatic(),
-            185 => self.invokeinterface(),
-            186 => self.invokedynamic(),
-            187 => self.new(),
-            188 => self.newarray(),
-            189 => self.anewarray(),
-            190 => self.arraylength(),
-            191 => self.athrow(),
-            192 => self.checkcast(),
-            193 => self.instanceof(),
-            194 => self.monitorenter(),
-            195 => self.monitorexit(),
-            196 => {
-                let thread = access_macros::current_thread_mut!(self);
-                thread.next_instruction_is_wide = true;                
-                Ok(())
-            },
-            197 => self.multianewarray(),
-            198 => self.ifnull(),
-            199 => self.ifnonnull(),
-            200 => self.goto_w(),
-            201 => self.jsr_w(),
-            202 => self.breakpoint(),
-            
-            254 => self.impdep1(),
-            255 => self.impdep2(),
-            _ => {
-                panic!("Error: Opcode {} not supported yet", op);
-            },
+            match current_frame_mut!(thread).current_method.code_at_mut(pc) {
+                Ok(op) => op as *mut Op,
+                Err(e) => {
+                    self.m_thrown_error = e;
+                    return None;
+                },
+            }
         };
-        */
+        self.m_executing.push(op);
         let err = unsafe {
             // I can't find a way to express to Rust what I want to do here, so we have to use unsafe. 
             // Essentially, the op is always 'within' the JVM and so this will always be a double borrow of self.
             // I don't think there is a way to guarantee to Rust that op.execute will never delete the op through accessing the class that owns it. 
             (*op).execute(self)
         };
-        if let Err(e) = err {
-            self.m_thrown_error = e;
+        self.m_executing.pop();
+        match err {
+            Err(e) => {
+                self.m_thrown_error = e;
+                None
+            },
+            Ok(flow) => unsafe {
+                // The op lives inline in the method's code, so it can only be swapped out once nothing is running it.
+                // It could still be running further up if it called back into the JVM
+                // (like a <clinit> or a bootstrap method that runs the same code), and then the outer run quickens it when it finishes.
+                if !self.m_executing.contains(&op) {
+                    if let Some(quick) = (*op).quicken() {
+                        *op = quick;
+                    }
+                }
+                Some(flow)
+            },
         }
     }
 }
@@ -707,66 +1117,25 @@ impl JVM {
 // https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.instanceof
 impl JVM {
     pub fn check_class(&mut self, object_desc: &str, class_desc: &str) -> Result<bool, Error> {
+        // object_desc is "L" followed by the class name (or an array descriptor), and class_desc is a class name like it would be in the constant pool.
         match object_desc.as_bytes()[0] as char {
             'L' => {
-                match class_desc.as_bytes()[0] as char {
-                    'L' => {
-                        // First, resolve class and object.
-                        let object_class = self.resolve_class_reference(object_desc)?;
-                        let class = self.resolve_class_reference(class_desc)?;
-                        let object_class_file = object_class.get_class_file();
-                        let class_file = class.get_class_file();
-                        match class_file.is_interface() {
-                            true => {
-                                // If class is an interface, then object must implement class.
-                                // We should add a short-circuit in the case that object also refers to an interface, because interfaces can't have interfaces,
-                                for interface_index in object_class_file.interfaces() {
-                                    let class_index = class_file.cp_entry(*interface_index)?.as_class()?;
-                                    let ob_class_desc = class_file.cp_entry(*class_index)?.as_utf8()?;
-                                    if ob_class_desc == class_desc {
-                                        return Ok(true);
-                                    }
-                                }
-                                Ok(false)
-                            },
-                            false => {
-                                // If class is a class, then object must be a subclass.
-                                let mut current_class = object_class;
-                                let mut current_class_file = object_class_file;
-                                while current_class.get_class_file().has_super() {
-                                    #[allow(clippy::vtable_address_comparisons)]
-                                    if Rc::ptr_eq(&current_class, &class) {
-                                        return Ok(true);
-                                    }
-                                    let super_name_index = current_class_file.cp_entry(current_class.get_class_file().super_index().unwrap())?.as_class()?;
-                                    let super_name = current_class_file.cp_entry(*super_name_index)?.as_utf8()?;
-                                    current_class = self.resolve_class_reference(super_name.as_str())?;
-                                    current_class_file = current_class.get_class_file();
-                                }
-                                Ok(false)
-                            }
-                        }
-                    },
-                    '[' => Ok(false),
-                    _ => Err(Error::IllegalDescriptor),
+                if class_desc.starts_with('[') {
+                    return Ok(false);
+                }
+                let object_class = self.resolve_class_reference(&object_desc[1..])?;
+                let class = self.resolve_class_reference(class_desc)?;
+                if class.get_class_file().is_interface() {
+                    // If class is an interface, then object must implement class.
+                    Ok(object_class.get_class_file().name() == class_desc || self.implements(object_class, class_desc)?)
+                }
+                else {
+                    // If class is a class, then object must be a subclass.
+                    self.is_subclass(object_class, class_desc)
                 }
             },
             '[' => {
                 match class_desc.as_bytes()[0] as char {
-                    'L' => {
-                        let class = self.resolve_class_reference(class_desc)?;
-                        match class.get_class_file().is_interface() {
-                            true => {
-                                // If class is an interface, it has to be an interface implemented by arrays. 
-                                // i don't know what those are, so for now, we just return false.
-                                Ok(false)
-                            },
-                            false => {
-                                // If class is a class, it must be object.
-                                Ok(class_desc == "Ljava/lang/thread")
-                            },
-                        }
-                    },
                     '[' => {
                         // In this case, both object and class are arrays. 
                         // This means that either the component types must both be the same primitive type, or they must be references castable by these rules
@@ -776,6 +1145,12 @@ impl JVM {
                             if !((class_component.as_bytes()[0] as char == 'L') | (class_component.as_bytes()[0] as char == '[')) {
                                 return Err(Error::IllegalDescriptor); // Either object and class are both primitive or both references.
                             }
+                            // Components are full descriptors, so they need to be put in the same form as our arguments.
+                            let object_component = object_component.strip_suffix(';').unwrap_or(object_component);
+                            let class_component = match class_component.strip_prefix('L') {
+                                Some(name) => name.strip_suffix(';').unwrap_or(name),
+                                None => class_component,
+                            };
                             return self.check_class(object_component, class_component);
                         }
                         if (class_component.as_bytes()[0] as char == 'L') | (class_component.as_bytes()[0] as char == '[') {
@@ -783,7 +1158,8 @@ impl JVM {
                         }
                         Ok(object_component.as_bytes()[0] == class_component.as_bytes()[0])
                     },
-                    _ => Err(Error::IllegalDescriptor),
+                    // Arrays are Objects, and they implement Cloneable and Serializable.
+                    _ => Ok(matches!(class_desc, "java/lang/Object" | "java/lang/Cloneable" | "java/io/Serializable")),
                 }
             },
             _ => Err(Error::IllegalDescriptor),
@@ -791,10 +1167,266 @@ impl JVM {
     }   
 }
 
+// Method resolution and selection for interface methods.
+// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.4 and https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.6
+impl JVM {
+    // Looks for a method declared in a class itself, without looking at any supers.
+    pub fn find_declared_method(class: &Rc<dyn Class>, name: &str, descriptor: &str) -> Result<Option<MethodInfo>, Error> {
+        Ok(JVM::find_declared_method_index(class, name, descriptor)?.map(|index| class.get_class_file().methods[index].clone()))
+    }
+    pub fn find_declared_method_index(class: &Rc<dyn Class>, name: &str, descriptor: &str) -> Result<Option<usize>, Error> {
+        let class_file = class.get_class_file();
+        for (index, method) in class_file.methods().iter().enumerate() {
+            if class_file.cp_entry(method.name_index)?.as_utf8()? == name && class_file.cp_entry(method.descriptor_index)?.as_utf8()? == descriptor {
+                return Ok(Some(index));
+            }
+        }
+        Ok(None)
+    }
+    // Every interface a class implements, either directly or through its superclasses and superinterfaces. 
+    pub fn all_superinterfaces(&mut self, class: Rc<dyn Class>) -> Result<Vec<Rc<dyn Class>>, Error> {
+        let mut found = Vec::new();
+        let mut current = Some(class);
+        while let Some(c) = current {
+            self.collect_superinterfaces(Rc::clone(&c), &mut found)?;
+            current = match c.get_class_file().super_name() {
+                Some(super_name) => Some(self.resolve_class_reference(super_name)?),
+                None => None,
+            };
+        }
+        Ok(found)
+    }
+    fn collect_superinterfaces(&mut self, class: Rc<dyn Class>, found: &mut Vec<Rc<dyn Class>>) -> Result<(), Error> {
+        let class_file = class.get_class_file();
+        for interface_index in class_file.interfaces() {
+            let interface_name_index = class_file.cp_entry(*interface_index)?.as_class()?;
+            let interface_name = class_file.cp_entry(*interface_name_index)?.as_utf8()?;
+            if found.iter().any(|i| i.get_class_file().name() == interface_name) {
+                continue;
+            }
+            let interface = self.resolve_class_reference(interface_name)?;
+            found.push(Rc::clone(&interface));
+            self.collect_superinterfaces(interface, found)?;
+        }
+        Ok(())
+    }
+    // Checks if class implements the interface called name.
+    pub fn implements(&mut self, class: Rc<dyn Class>, name: &str) -> Result<bool, Error> {
+        Ok(self.all_superinterfaces(class)?.iter().any(|i| i.get_class_file().name() == name))
+    }
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.3
+    // These are the non-private, non-static methods in superinterfaces of class that no other one of these methods overrides.
+    #[allow(clippy::type_complexity)]
+    pub fn maximally_specific_methods(&mut self, class: Rc<dyn Class>, name: &str, descriptor: &str) -> Result<Vec<(Rc<dyn Class>, MethodInfo)>, Error> {
+        let mut candidates = Vec::new();
+        for interface in self.all_superinterfaces(class)? {
+            if let Some(method) = JVM::find_declared_method(&interface, name, descriptor)? {
+                if (method.access_flags.flags & (flags::method::ACC_PRIVATE | flags::method::ACC_STATIC)) == 0 {
+                    candidates.push((interface, method));
+                }
+            }
+        }
+        let mut maximal = Vec::new();
+        for (interface, method) in &candidates {
+            let interface_file = interface.get_class_file();
+            let interface_name = interface_file.name();
+            let mut overridden = false;
+            for (other, _) in &candidates {
+                if other.get_class_file().name() != interface_name && self.implements(Rc::clone(other), interface_name)? {
+                    overridden = true;
+                    break;
+                }
+            }
+            if !overridden {
+                maximal.push((Rc::clone(interface), method.clone()));
+            }
+        }
+        Ok(maximal)
+    }
+    // Resolves a method referred to by a Methodref, giving back the method and the class that declares it.
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.3
+    pub fn resolve_class_method(&mut self, c: Rc<dyn Class>, name: &str, descriptor: &str, opcode: Opcode) -> Result<(Rc<dyn Class>, MethodInfo), Error> {
+        if c.get_class_file().is_interface() {
+            return Err(Error::IncompatibleClassChangeError(opcode));
+        }
+        let mut current = Some(Rc::clone(&c));
+        while let Some(class) = current {
+            if let Some(method) = JVM::find_declared_method(&class, name, descriptor)? {
+                return Ok((class, method));
+            }
+            current = match class.get_class_file().super_name() {
+                Some(super_name) => Some(self.resolve_class_reference(super_name)?),
+                None => None,
+            };
+        }
+        let maximal = self.maximally_specific_methods(c, name, descriptor)?;
+        let mut non_abstract = maximal.iter().filter(|(_, m)| (m.access_flags.flags & flags::method::ACC_ABSTRACT) == 0);
+        if let (Some(found), None) = (non_abstract.next(), non_abstract.next()) {
+            return Ok(found.clone());
+        }
+        match maximal.into_iter().next() {
+            Some(found) => Ok(found),
+            None => Err(Error::NoSuchMethodError(opcode)),
+        }
+    }
+    // Resolves a method referred to by an InterfaceMethodref, giving back the method and the class that declares it.
+    pub fn resolve_interface_method(&mut self, c: Rc<dyn Class>, name: &str, descriptor: &str, opcode: Opcode) -> Result<(Rc<dyn Class>, MethodInfo), Error> {
+        if !c.get_class_file().is_interface() {
+            return Err(Error::IncompatibleClassChangeError(opcode));
+        }
+        if let Some(method) = JVM::find_declared_method(&c, name, descriptor)? {
+            return Ok((c, method));
+        }
+        // Interfaces get all the public methods of Object.
+        let object_class = self.resolve_class_reference("java/lang/Object")?;
+        if let Some(method) = JVM::find_declared_method(&object_class, name, descriptor)? {
+            if (method.access_flags.flags & flags::method::ACC_PUBLIC) > 0 && (method.access_flags.flags & flags::method::ACC_STATIC) == 0 {
+                return Ok((object_class, method));
+            }
+        }
+        let maximal = self.maximally_specific_methods(c, name, descriptor)?;
+        let mut non_abstract = maximal.iter().filter(|(_, m)| (m.access_flags.flags & flags::method::ACC_ABSTRACT) == 0);
+        if let (Some(found), None) = (non_abstract.next(), non_abstract.next()) {
+            return Ok(found.clone());
+        }
+        // Otherwise the spec lets us pick any of them.
+        match maximal.into_iter().next() {
+            Some(found) => Ok(found),
+            None => Err(Error::NoSuchMethodError(opcode)),
+        }
+    }
+    // Picks the method that actually runs when a resolved method is invoked on an object of class receiver.
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.6
+    pub fn select_method(&mut self, receiver: Rc<dyn Class>, resolved_class: Rc<dyn Class>, resolved_method: MethodInfo, name: &str, descriptor: &str, opcode: Opcode) -> Result<(Rc<dyn Class>, MethodInfo), Error> {
+        // Nothing can override private or final methods.
+        if (resolved_method.access_flags.flags & (flags::method::ACC_PRIVATE | flags::method::ACC_FINAL)) > 0 {
+            return Ok((resolved_class, resolved_method));
+        }
+        // First, look for an instance method in the receiver's class or any of its superclasses, up to the resolved class.
+        let resolved_name = String::from(resolved_class.get_class_file().name());
+        let mut candidates = Vec::new();
+        let mut current = Some(Rc::clone(&receiver));
+        while let Some(c) = current {
+            if let Some(method) = JVM::find_declared_method(&c, name, descriptor)? {
+                if (method.access_flags.flags & (flags::method::ACC_PRIVATE | flags::method::ACC_STATIC)) == 0 {
+                    candidates.push((Rc::clone(&c), method));
+                }
+            }
+            if c.get_class_file().name() == resolved_name {
+                break;
+            }
+            current = match c.get_class_file().super_name() {
+                Some(super_name) => Some(self.resolve_class_reference(super_name)?),
+                None => None,
+            };
+        }
+        // Then we take the one nearest the receiver that overrides the resolved method (see 5.4.5).
+        // Working from the top down lets us handle methods that only override it through another override.
+        let mut overrides = vec![false; candidates.len()];
+        for idx in (0..candidates.len()).rev() {
+            let (c, _) = &candidates[idx];
+            let class_file = c.get_class_file();
+            overrides[idx] = class_file.name() == resolved_name
+                || JVM::can_override(class_file.name(), &resolved_name, &resolved_method)
+                || (idx + 1..candidates.len()).any(|above| overrides[above] 
+                    && JVM::can_override(class_file.name(), candidates[above].0.get_class_file().name(), &candidates[above].1));
+        }
+        if let Some(idx) = overrides.iter().position(|o| *o) {
+            return Ok(candidates.swap_remove(idx));
+        }
+        // If there isn't one, we use a default method, as long as there's exactly one to choose from.
+        let mut non_abstract: Vec<(Rc<dyn Class>, MethodInfo)> = self.maximally_specific_methods(receiver, name, descriptor)?.into_iter()
+            .filter(|(_, m)| (m.access_flags.flags & flags::method::ACC_ABSTRACT) == 0)
+            .collect();
+        match non_abstract.len() {
+            0 => Err(Error::AbstractMethodError(opcode)),
+            1 => Ok(non_abstract.remove(0)),
+            _ => Err(Error::IncompatibleClassChangeError(opcode)),
+        }
+    }
+    // Whether a method declared in class can override method from overridden_class, assuming they have the same name and descriptor.
+    // We only have one class loader, so the runtime package is just the package name.
+    pub fn can_override(class: &str, overridden_class: &str, method: &MethodInfo) -> bool {
+        if (method.access_flags.flags & (flags::method::ACC_PUBLIC | flags::method::ACC_PROTECTED)) > 0 {
+            return true;
+        }
+        if (method.access_flags.flags & flags::method::ACC_PRIVATE) > 0 {
+            return false;
+        }
+        let package = |name: &str| match name.rfind('/') {
+            Some(idx) => String::from(&name[..idx]),
+            None => String::new(),
+        };
+        package(class) == package(overridden_class)
+    }
+    // Works out how to dispatch a method resolved by invokevirtual or invokeinterface, so that later calls don't have to search for it.
+    pub fn link_virtual_call(&mut self, class: Rc<dyn Class>, name: &str, descriptor: &str) -> Result<VirtualCall, Error> {
+        let class_file = class.get_class_file();
+        let index = match JVM::find_declared_method_index(&class, name, descriptor)? {
+            Some(index) => index,
+            None => return Err(Error::NoSuchMethodError(Opcode::MethodInvoke)),
+        };
+        let method = &class_file.methods[index];
+        let num_args = method.num_args(&class_file)?;
+        let dispatch = if (method.access_flags.flags & (flags::method::ACC_PRIVATE | flags::method::ACC_FINAL)) > 0 {
+            Dispatch::Direct
+        }
+        else if class_file.is_interface() {
+            Dispatch::Interface(Rc::clone(&class), index)
+        }
+        else {
+            let slot = class.as_any().downcast_ref::<CustomClass>()
+                .and_then(|c| c.vtable().iter().position(|s| s.class.is_none() && s.index == index));
+            match slot {
+                Some(slot) => Dispatch::Virtual(slot),
+                None => Dispatch::Select,
+            }
+        };
+        Ok(VirtualCall { class, index, name: String::from(name), descriptor: String::from(descriptor), num_args, dispatch })
+    }
+    // Picks the method to run for call on an object of class receiver, giving back the class that declares it and its index there.
+    pub fn select_virtual(&mut self, call: &VirtualCall, receiver: Rc<dyn Class>, opcode: Opcode) -> Result<(Rc<dyn Class>, usize), Error> {
+        let tables = receiver.as_any().downcast_ref::<CustomClass>().filter(|c| c.has_tables());
+        let slot = match (&call.dispatch, tables) {
+            (Dispatch::Direct, _) => return Ok((Rc::clone(&call.class), call.index)),
+            (Dispatch::Virtual(slot), Some(c)) => c.vtable().get(*slot),
+            (Dispatch::Interface(interface, index), Some(c)) => match c.itable_index(interface, *index) {
+                Some(slot) => c.vtable().get(slot),
+                None => return Err(Error::IncompatibleClassChangeError(opcode)),
+            },
+            // Classes like lambdas, and subclasses of native classes, don't have tables, so they have to be searched.
+            _ => {
+                let resolved_method = call.class.get_class_file().methods[call.index].clone();
+                let (class, _) = self.select_method(receiver, Rc::clone(&call.class), resolved_method, &call.name, &call.descriptor, opcode.clone())?;
+                return match JVM::find_declared_method_index(&class, &call.name, &call.descriptor)? {
+                    Some(index) => Ok((class, index)),
+                    None => Err(Error::NoSuchMethodError(opcode)),
+                };
+            },
+        };
+        let (class, index) = match slot {
+            Some(slot) if slot.ambiguous => return Err(Error::IncompatibleClassChangeError(opcode)),
+            Some(slot) => (slot.class.clone(), slot.index),
+            None => return Err(Error::IncompatibleClassChangeError(opcode)),
+        };
+        Ok((class.unwrap_or(receiver), index))
+    }
+}
+
 impl JVM {
-    // This functions contains lots of redundant checks that should be removed.
     pub fn handle_exception(&mut self) -> Result<(), Error> {
-        {
+        if self.unwind_exception(0)?.is_some() {
+            // If we got here, it means that we were at the last frame. 
+            // In that case, we remove this thread.
+            self.exit_thread();
+        }
+        Ok(())
+    }
+    // This functions contains lots of redundant checks that should be removed.
+    // Looks for a handler for the exception on top of the current frame's stack, popping frames until one catches it.
+    // If we get down to `depth` frames without finding one, the exception is popped and returned.
+    pub fn unwind_exception(&mut self, depth: usize) -> Result<Option<Value<dyn Class, dyn Object>>, Error> {
+        loop {
             // This function doesn't actually check for any exception that could be thrown by athrow. 
             // athrow has to check its own exceptions, and create any that could occur.
             let thread = access_macros::current_thread_mut!(self);
@@ -826,20 +1458,7 @@ impl JVM {
                                 let current_class_file = current_class.get_class_file();            
                                 let catch_class_name_index = *current_class_file.cp_entry(ex_handler.catch_type)?.as_class()?;
                                 let catch_class_name = current_class_file.cp_entry(catch_class_name_index)?.as_utf8()?;
-                                let catch_class = self.resolve_class_reference(catch_class_name.as_str())?;
-                                // Check if exception refers to catch_class or one of its subclasses
-                                let exception_class = exception.class().clone();
-                                let mut current_exception_class = exception_class;
-                                let mut ret_flag = false;
-                                while current_exception_class.get_class_file().has_super() {
-                                    #[allow(clippy::vtable_address_comparisons)]
-                                    if Rc::ptr_eq(&current_exception_class, &catch_class) {
-                                        ret_flag = true;
-                                        break;
-                                    }
-                                    current_exception_class = self.resolve_class_reference(current_exception_class.get_class_file().super_name().unwrap())?;
-                                }
-                                ret_flag
+                                self.is_subclass(exception.class(), catch_class_name)?
                             }
                         };
                         if catches_this {
@@ -851,8 +1470,7 @@ impl JVM {
                             let exception_val = frame.op_stack.pop().unwrap();
                             frame.op_stack.clear();
                             frame.op_stack.push(exception_val);
-                            // TODO: Deal with synchronized functions.
-                            return Ok(());
+                            return Ok(None);
                         }
                     }
                 }
@@ -863,21 +1481,70 @@ impl JVM {
                 frame.op_stack.pop().unwrap()
             };
             // If we found no exception handler, pass it down the call chain
+            let current_thread_id = self.current_thread_id();
             let thread = access_macros::current_thread_mut!(self);
-            let _ = thread.m_stack.pop();
-            if !thread.m_stack.is_empty() {
-                // Continue down the call chain
-                let frame: &mut Frame = access_macros::current_frame_mut!(thread);
-                frame.op_stack.push(exception);
-                return self.handle_exception();
+            let frame = thread.pop_frame().unwrap();
+            // Synchronized methods let go of their monitor on the way out too.
+            let released = frame.monitor.is_none_or(|monitor| monitor.try_exit(current_thread_id));
+            if thread.m_stack.len() <= depth {
+                return Ok(Some(exception));
             }
+            if !released {
+                // The method didn't own its monitor any more, which replaces the exception we were throwing.
+                match self.throw_exception::<()>("java/lang/IllegalMonitorStateException", "()V", vec![]) {
+                    Err(Error::Exception) => continue,
+                    Err(e) => return Err(e),
+                    Ok(()) => unreachable!(),
+                }
+            }
+            // Continue down the call chain
+            let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+            frame.op_stack.push(exception);
         }
-        // If we got here, it means that we were at the last frame. 
-        // In that case, we remove this thread.
-        let _ = self.m_threads.remove(self.m_thread_index);
-        Ok(())
-
-    }    
+    }
+    // Checks if class is the class called name, or one of its subclasses.
+    pub fn is_subclass(&mut self, class: Rc<dyn Class>, name: &str) -> Result<bool, Error> {
+        let mut current_class = class;
+        loop {
+            if current_class.get_class_file().name() == name {
+                return Ok(true);
+            }
+            if !current_class.get_class_file().has_super() {
+                return Ok(false);
+            }
+            current_class = self.resolve_class_reference(current_class.get_class_file().super_name().unwrap())?;
+        }
+    }
+    // Creates an exception, calls its constructor with args, and leaves it on the stack to be thrown.
+    // Meant to be returned from instructions, like `return jvm.throw_exception(...)`.
+    pub fn throw_exception<T>(&mut self, class_name: &str, descriptor: &str, args: Vec<Value<dyn Class, dyn Object>>) -> Result<T, Error> {
+        let class = self.resolve_class_reference(class_name)?;
+        self.initialize_class(Rc::clone(&class))?;
+        let exception = object::new_object_with_name(class_name, self)?;
+        let exception_val = Value::Reference(Reference::Object(exception, Rc::new(Monitor::new())));
+        {
+            let thread = access_macros::current_thread_mut!(self);
+            let frame = access_macros::current_frame_mut!(thread);
+            // One copy gets thrown, and the other one is `this` for the constructor.
+            frame.op_stack.push(exception_val.clone());
+            frame.op_stack.push(exception_val);
+            for arg in args {
+                frame.op_stack.push(arg);
+            }
+        }
+        self.setup_method_call_from_name("<init>", descriptor, class, false)?;
+        self.run_until_method_exit();
+        if self.m_thrown_error != Error::None {
+            return Err(mem::replace(&mut self.m_thrown_error, Error::None));
+        }
+        Err(Error::Exception)
+    }
+    // Same as throw_exception, for the exceptions that take a message.
+    pub fn throw_exception_with_message<T>(&mut self, class_name: &str, message: String) -> Result<T, Error> {
+        let message = natives::string::String::new_from_string(message, self)?;
+        let message = Value::Reference(Reference::Object(message as Rc<dyn Object>, Rc::new(Monitor::new())));
+        self.throw_exception(class_name, "(Ljava/lang/String;)V", vec![message])
+    }
     pub fn setup_method_call_from_name(&mut self, name: &str, descriptor: &str, mut current_class: Rc<dyn Class>, is_static: bool)  -> Result<(), Error> {
         let mut method_to_call = None; 
         {
@@ -951,6 +1618,7 @@ impl JVM {
         self.setup_method_call(&method_to_call.unwrap(), current_class, is_static)
     }
     pub fn setup_method_call(&mut self, method: &MethodInfo, c: Rc<dyn Class>, is_static: bool) -> Result<(), Error> {
+        self.reserve_stack(Frame::size_for(method))?;
         let thread = access_macros::current_thread_mut!(self);
         // Fill out the local variables.
         let c_file = c.get_class_file();
@@ -1122,15 +1790,235 @@ impl JVM {
     pub fn execute_native(&mut self, method: &MethodInfo, current_class: Rc<dyn Class>) -> Result<(), Error> {
         let current_class_file = current_class.get_class_file();
         let mname = current_class_file.cp_entry(method.name_index)?.as_utf8()?;
-        let _mdesc = current_class_file.cp_entry(method.descriptor_index)?.as_utf8()?;
+        let mdesc = current_class_file.cp_entry(method.descriptor_index)?.as_utf8()?;
         let cname = current_class_file.name();
-        #[allow(clippy::match_single_binding)]
-        match cname {
+        match (cname, mname.as_str(), mdesc.as_str()) {
+            ("java/lang/Class", "registerNatives", "()V") => Ok(()),
+            // The boxes' static initializers use this for their TYPE fields.
+            ("java/lang/Class", "getPrimitiveClass", "(Ljava/lang/String;)Ljava/lang/Class;") => {
+                let thread = access_macros::current_thread_mut!(self);
+                let frame = access_macros::current_frame_mut!(thread);
+                let name = match frame.op_stack.pop() {
+                    Some(Value::Reference(Reference::Object(s, _))) => match s.as_any().downcast_ref::<natives::string::String>() {
+                        Some(s) => s.backing_string().clone(),
+                        None => return Err(Error::IncorrectReferenceType(Opcode::MethodInvoke)),
+                    },
+                    Some(_) => return Err(Error::UnexpectedTypeOnStack(Opcode::MethodInvoke)),
+                    None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+                };
+                let descriptor = match name.as_str() {
+                    "boolean" => "Z",
+                    "byte" => "B",
+                    "char" => "C",
+                    "short" => "S",
+                    "int" => "I",
+                    "long" => "J",
+                    "float" => "F",
+                    "double" => "D",
+                    "void" => "V",
+                    _ => return self.throw_exception_with_message("java/lang/IllegalArgumentException", name),
+                };
+                let mirror = self.class_mirror(descriptor)?;
+                let thread = access_macros::current_thread_mut!(self);
+                let frame = access_macros::current_frame_mut!(thread);
+                frame.op_stack.push(Value::Reference(mirror));
+                Ok(())
+            },
+            // We don't keep stack traces, so there's nothing to fill in. It gives back the exception, which is already under the argument.
+            ("java/lang/Throwable", "fillInStackTrace", "(I)Ljava/lang/Throwable;") => {
+                let thread = access_macros::current_thread_mut!(self);
+                let frame = access_macros::current_frame_mut!(thread);
+                frame.op_stack.pop();
+                Ok(())
+            },
             _ => Err(Error::UnsatisfiedLinkError(Opcode::MethodInvoke, mname.clone()))
-        }    
+        }
+    }
+    // Calls method with `call`, which sets the call up with one of the functions above. 
+    // Synchronized methods enter their monitor first, and if another thread has it, this thread blocks on it,
+    // and the invoke runs again once it's free. See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-2.html#jvms-2.11.10
+    pub fn invoke_method<F>(&mut self, method: &MethodInfo, class: &Rc<dyn Class>, call: F) -> Result<Flow, Error> 
+    where F: FnOnce(&mut JVM) -> Result<(), Error> {
+        if (method.access_flags.flags & flags::method::ACC_SYNCHRONIZED) == 0 {
+            call(self)?;
+            return Ok(Flow::Invoke);
+        }
+        let monitor = self.method_monitor(method, class)?;
+        let current_thread_id = self.current_thread_id();
+        if !monitor.try_enter(current_thread_id) {
+            current_thread_mut!(self).current_monitor = Some(monitor);
+            return Ok(Flow::Jump);
+        }
+        let depth = self.current_thread().m_stack.len();
+        let result = call(self);
+        let thread = current_thread_mut!(self);
+        if result.is_ok() && thread.m_stack.len() > depth {
+            current_frame_mut!(thread).monitor = Some(monitor);
+        }
+        else {
+            // Native methods are already done, so they give the monitor straight back.
+            monitor.try_exit(current_thread_id);
+        }
+        result.map(|_| Flow::Invoke)
+    }
+    // The monitor a synchronized method uses: the receiver's, which is under the arguments on the stack, or the class's if it's static.
+    fn method_monitor(&mut self, method: &MethodInfo, class: &Rc<dyn Class>) -> Result<Rc<Monitor>, Error> {
+        let class_file = class.get_class_file();
+        if (method.access_flags.flags & flags::method::ACC_STATIC) > 0 {
+            return Ok(Rc::clone(self.class_mirror_from_name(class_file.name())?.monitor()?));
+        }
+        let num_args = method.num_args(&class_file)?;
+        let thread = current_thread_mut!(self); 
+        let frame = current_frame_mut!(thread);
+        if frame.op_stack.len() < num_args + 1 {
+            return Err(Error::StackUnderflow(Opcode::MethodInvoke));
+        }
+        Ok(Rc::clone(frame.op_stack[frame.op_stack.len() - num_args - 1].as_reference()?.monitor()?))
+    }
+    // Exits the monitor a synchronized method entered, for when it returns. The return instructions call this right before popping the frame.
+    pub fn exit_method_monitor(&mut self) -> Result<(), Error> {
+        let current_thread_id = self.current_thread_id();
+        let thread = current_thread_mut!(self);
+        let frame = current_frame_mut!(thread);
+        if let Some(monitor) = frame.monitor.take() {
+            if !monitor.try_exit(current_thread_id) {
+                return self.throw_exception("java/lang/IllegalMonitorStateException", "()V", vec![]);
+            }
+        }
+        Ok(())
+    }
+    // Object's native methods, which work on any reference, so arrays and native objects get them too.
+    // toString isn't native, but all it needs is the class name and the identity hash, so it's simpler to do here too.
+    fn exec_object_method(&mut self, method: &MethodInfo, class: &Rc<dyn Class>) -> Result<bool, Error> {
+        if self.exec_monitor_method(method, class)? {
+            return Ok(true);
+        }
+        let class_file = class.get_class_file();
+        let name = class_file.cp_entry(method.name_index)?.as_utf8()?;
+        let descriptor = class_file.cp_entry(method.descriptor_index)?.as_utf8()?;
+        let thread = current_thread_mut!(self);
+        let receiver = match current_frame_mut!(thread).op_stack.last() {
+            Some(v) => v.as_reference()?,
+            None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+        };
+        let value = match (name.as_str(), descriptor.as_str()) {
+            ("hashCode", "()I") => Value::Int(receiver.monitor()?.identity_hash()),
+            ("getClass", "()Ljava/lang/Class;") => Value::Reference(self.class_of(&receiver)?),
+            // Like HotSpot's, this is the same as getClass().getName() + "@" + Integer.toHexString(hashCode()).
+            ("toString", "()Ljava/lang/String;") => {
+                let class_name = match &receiver {
+                    Reference::Object(o, _) => String::from(o.class().get_class_file().name()),
+                    Reference::Interface(c, _) => String::from(c.get_class_file().name()),
+                    Reference::Array(a, _) => String::from(a.descriptor()),
+                    Reference::Null => return Err(Error::NullPointerException(Opcode::MethodInvoke)),
+                };
+                let hash = receiver.monitor()?.identity_hash();
+                let s = natives::string::String::new_from_string(format!("{}@{hash:x}", class_name.replace('/', ".")), self)?;
+                Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new())))
+            },
+            ("clone", "()Ljava/lang/Object;") => Value::Reference(self.clone_reference(&receiver)?),
+            _ => return Ok(false),
+        };
+        let thread = current_thread_mut!(self);
+        let frame = current_frame_mut!(thread);
+        frame.op_stack.pop();
+        frame.op_stack.push(value);
+        Ok(true)
+    }
+    // A shallow copy of an array or object, for Object.clone. The copy gets its own monitor, so it isn't locked and has its own identity hash.
+    // Every array can be cloned, but objects have to implement Cloneable.
+    fn clone_reference(&mut self, reference: &Reference<dyn Class, dyn Object>) -> Result<Reference<dyn Class, dyn Object>, Error> {
+        match reference {
+            Reference::Array(a, _) => {
+                self.reserve_heap(a.heap_size())?;
+                let copy = Rc::new((**a).clone());
+                self.heap.track_array(&copy);
+                Ok(Reference::Array(copy, Rc::new(Monitor::new())))
+            },
+            Reference::Object(o, _) => {
+                let class = o.class();
+                if !self.implements(Rc::clone(&class), "java/lang/Cloneable")? {
+                    return self.throw_exception_with_message("java/lang/CloneNotSupportedException", class.get_class_file().name().replace('/', "."));
+                }
+                self.reserve_heap(o.heap_size())?;
+                let Some(copy) = o.shallow_clone()? else {
+                    return self.throw_exception_with_message("java/lang/CloneNotSupportedException", class.get_class_file().name().replace('/', "."));
+                };
+                self.heap.track_object(&copy);
+                Ok(Reference::Object(copy, Rc::new(Monitor::new())))
+            },
+            Reference::Interface(c, _) => 
+                self.throw_exception_with_message("java/lang/CloneNotSupportedException", c.get_class_file().name().replace('/', ".")),
+            Reference::Null => Err(Error::NullPointerException(Opcode::MethodInvoke)),
+        }
+    }
+    // The java.lang.Class of whatever a reference points to, which is what getClass gives back.
+    pub fn class_of(&mut self, reference: &Reference<dyn Class, dyn Object>) -> Result<Reference<dyn Class, dyn Object>, Error> {
+        match reference {
+            Reference::Object(o, _) => self.class_mirror_from_name(o.class().get_class_file().name()),
+            Reference::Interface(c, _) => self.class_mirror_from_name(c.get_class_file().name()),
+            Reference::Array(a, _) => self.class_mirror(a.descriptor()),
+            Reference::Null => Err(Error::NullPointerException(Opcode::MethodInvoke)),
+        }
+    }
+    // Object.wait, notify and notifyAll, which work on the receiver's monitor.
+    // The thread has to own the monitor. Waiting lets go of it and returns straight away, and the scheduler leaves the thread
+    // alone until it's notified, times out or is interrupted, and has the monitor back. See JVM::wake_thread.
+    fn exec_monitor_method(&mut self, method: &MethodInfo, class: &Rc<dyn Class>) -> Result<bool, Error> {
+        let class_file = class.get_class_file();
+        let name = class_file.cp_entry(method.name_index)?.as_utf8()?;
+        let descriptor = class_file.cp_entry(method.descriptor_index)?.as_utf8()?;
+        let current_thread_id = self.current_thread_id();
+        let thread = current_thread_mut!(self);
+        let frame = current_frame_mut!(thread);
+        let millis = match (name.as_str(), descriptor.as_str()) {
+            // Newer JDKs have wait(J) call wait0.
+            ("wait" | "wait0", "(J)V") => match frame.op_stack.pop() {
+                Some(v) => Some(*v.as_long()?),
+                None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+            },
+            ("notify" | "notifyAll", "()V") => None,
+            _ => return Ok(false),
+        };
+        let monitor = match frame.op_stack.pop() {
+            Some(v) => Rc::clone(v.as_reference()?.monitor()?),
+            None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+        };
+        if !monitor.is_owned_by(current_thread_id) {
+            self.throw_exception_with_message::<()>("java/lang/IllegalMonitorStateException", String::from("current thread is not owner"))?;
+        }
+        let Some(millis) = millis else {
+            if name == "notify" {
+                monitor.notify();
+            } else {
+                monitor.notify_all();
+            }
+            return Ok(true);
+        };
+        if millis < 0 {
+            self.throw_exception_with_message::<()>("java/lang/IllegalArgumentException", String::from("timeout value is negative"))?;
+        }
+        class::natives::thread::Thread::check_interrupted(self, "wait interrupted")?;
+        // Nothing else can run to notify us from inside an instruction, so there we wake up straight away, which wait is allowed to do.
+        if !self.can_block() {
+            return Ok(true);
+        }
+        let deadline = if millis == 0 {
+            None
+        } else {
+            Some(Instant::now() + std::time::Duration::from_millis(millis as u64))
+        };
+        let count = monitor.wait(current_thread_id);
+        let thread = current_thread_mut!(self);
+        thread.waiting_on = Some((monitor, count));
+        thread.state = ThreadState::Waiting(deadline);
+        Ok(true)
     }
     pub fn execute_on_object(&mut self, method: &MethodInfo, current_class: Rc<dyn Class>) -> Result<(), Error> {
         let num_args = method.num_args(&current_class.get_class_file())?;
+        if current_class.get_class_file().name() == "java/lang/Object" && self.exec_object_method(method, &current_class)? {
+            return Ok(());
+        }
         let thread = current_thread_mut!(self); let frame = current_frame_mut!(thread);
         /* 
         println!("Executing {}.{}{} on object", 
@@ -1155,7 +2043,7 @@ impl JVM {
 }
 
 impl JVM {
-    pub fn parse_descriptor(mut desc: &str) -> Result<(Box<[ValueMarker]>, ValueMarker, usize), Error> {
+    pub fn parse_descriptor(desc: &str) -> Result<(Box<[ValueMarker]>, ValueMarker, usize), Error> {
         let ret_val = match &desc[desc.rfind(')').unwrap() + 1..desc.rfind(')').unwrap() + 2] {
             "B" | "Z"=> ValueMarker::Byte,
             "C" => ValueMarker::Char,
@@ -1171,22 +2059,12 @@ impl JVM {
                 return Err(Error::IllegalDescriptor)},
         };
         let mut real_num_locals = 0;
-        desc = &desc[1..desc.find(')').unwrap()]; // Skip past the return value and first paren
         let mut args = Vec::new();
-        while !desc.is_empty() {
-            let mut index = desc.len() - 1;
-            if &desc[index..] == ";" {
-                index = desc.rfind('L').unwrap();
-            }
-            if (index > 0) && &desc[index - 1..index] == "[" {
-                // There is a better way of doing this, FIXME.
-                desc = &desc[..index];
-                index -= 1;
-            }
-            if matches!(&desc[index..index + 1], "D" | "J") {
+        for param in invoke::parameter_descriptors(desc)? {
+            if matches!(param, "D" | "J") {
                 real_num_locals += 1;
             }
-            args.push(match &desc[index..index + 1] {
+            args.push(match &param[0..1] {
                 "B" | "Z"=> ValueMarker::Byte,
                 "C" => ValueMarker::Char,
                 "D" => ValueMarker::Double,
@@ -1198,56 +2076,7 @@ impl JVM {
                 _ => return Err(Error::IllegalDescriptor),
             }); 
             real_num_locals += 1;
-            desc = &desc[0..index];
         }
-        args.reverse();
         Ok((args.into_boxed_slice(), ret_val, real_num_locals))
     }
-    pub fn box_primitive_name(&mut self, sym: &str) -> Result<&'static str, Error> {
-        match sym {
-            "B" => Ok("java/lang/Byte"),
-            "C" => Ok("java/lang/Char"),
-            "D" => Ok("java/lang/Double"),
-            "F" => Ok("java/lang/Float"),
-            "I" => Ok("java/lang/Int"),
-            "J" => Ok("java/lang/Long"),
-            "S" => Ok("java/lang/Short"),
-            "Z" => Ok("java/lang/Boolean"),
-            _ => Err(Error::IllegalDescriptor),
-        }
-    }
-    pub fn gen_class_obj(&mut self, name: &str) -> Result<(), Error> {
-        // We have to create an instance of the class. 
-        // To do this we call the private constructor with the classloader and the array component type
-        // Since we don't have class loaders, this is always null for now
-        let loader = Value::Reference(Reference::Null);
-        let thread = access_macros::current_thread_mut!(self);
-        let frame = access_macros::current_frame_mut!(thread);
-        frame.op_stack.push(loader);
-        if &name[0..1] == "[" {
-            let mut idx = 1;
-            while &name[idx..idx+1] == "[" {
-                idx += 1;
-            }
-            if &name[idx..idx+1] == "L" {
-                let comp_name = &name[idx + 1..];
-                self.gen_class_obj(comp_name)?; // Puts the comp type on the stack
-            }
-            else {
-                // NOTE: This might be incorrect behavior. 
-                // It's possible that when we encounter a primitive type, we shouldn't box it into a class
-                // For now we do, it makes things easier
-                let boxed_name = self.box_primitive_name(&name[idx..idx+1])?;
-                self.gen_class_obj(boxed_name)?;
-            }
-        }
-        else { 
-            frame.op_stack.push(Value::Reference(Reference::Null));
-        }
-        // This may not work right, idk how exactly it should work. This will probably need explicit support from the vm in the future.
-        let class_class = self.resolve_class_reference("java/lang/Class")?;
-        self.setup_method_call_from_name("<init>", "(Ljava/lang/ClassLoader;Ljava/lang/Class;)V", class_class, true)?;
-        self.run_until_method_exit();
-        Ok(())
-    }
 }
\ No newline at end of file
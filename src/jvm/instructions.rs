@@ -33,6 +33,7 @@ pub mod conversions;
 pub mod comparisons;
 pub mod control;
 pub mod references;
+pub mod quick;
 pub mod extended;
 pub mod reserved;
 
@@ -47,15 +48,33 @@ macro_rules! compress_addr {
     };
 }
 
+// What an instruction did with control, which tells the JVM what to do with the pc afterwards.
+// Throwing is done by returning Error::Exception, and handle_exception moves the pc to the handler.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Flow {
+    // Go on to the next instruction.
+    Next,
+    // The instruction already set the pc to where it's going, so leave it alone.
+    Jump,
+    // The instruction called a method. If that pushed a frame, the pc stays on the invoke until the frame returns,
+    // so that exception handlers still cover it. If the method already ran (like a native one), this is the same as Next.
+    Invoke,
+    // The current frame was popped, so the caller moves past the invoke that called it.
+    Return,
+}
+
 pub trait Instruction : core::fmt::Debug + DynClone {
     fn name(&self) -> &'static str;
     fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized;
-    #[inline] fn execute(&mut self, _ : &mut JVM) -> Result<(), Error> {
+    #[inline] fn execute(&mut self, _ : &mut JVM) -> Result<Flow, Error> {
         panic!("TODO execution not implemented for {}", self.name());
     }
     fn compress_range(&mut self, _this_pc: usize, _translation_map: &HashMap<usize, usize>) {}
     fn as_any(&self) -> &dyn Any;
     fn eq(&self, other: &dyn Instruction) -> bool;
+    // After a successful execute, step1 asks for a quick version of the instruction to replace it with, see quick.rs.
+    // Instructions give one back once they've resolved everything they need to.
+    fn quicken(&self) -> Option<Op> { None }
 
     fn can_jit(&self) -> bool { false }
     // A very basic jit outline, without support for control flow. 
@@ -82,219 +101,302 @@ impl PartialEq for dyn Instruction {
 
 clone_trait_object!(Instruction);
 
-pub fn new_instruction(v: &mut Vec<u8>, c: &Vec<Entry>, jvm: &mut JVM, was_wide: bool, true_pc: usize) -> Result<Box<dyn Instruction>, Error> {
-    let op = v[0];
-    // println!("Creating op {}", op);
-    v.remove(0);
-    match op {
-        0 => Ok(Box::new(constants::Nop::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        1 => Ok(Box::new(constants::AConstNull::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        2 => Ok(Box::new(constants::IConstM1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        3 => Ok(Box::new(constants::IConst0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        4 => Ok(Box::new(constants::IConst1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        5 => Ok(Box::new(constants::IConst2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        6 => Ok(Box::new(constants::IConst3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        7 => Ok(Box::new(constants::IConst4::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        8 => Ok(Box::new(constants::IConst5::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        9 => Ok(Box::new(constants::LConst0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        10 => Ok(Box::new(constants::LConst1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        11 => Ok(Box::new(constants::FConst0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        12 => Ok(Box::new(constants::FConst1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        13 => Ok(Box::new(constants::FConst2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        14 => Ok(Box::new(constants::DConst0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        15 => Ok(Box::new(constants::DConst1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        16 => Ok(Box::new(constants::BiPush::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        17 => Ok(Box::new(constants::SiPush::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        18 => Ok(Box::new(constants::Ldc::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        19 => Ok(Box::new(constants::LdcW::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        20 => Ok(Box::new(constants::Ldc2W::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        21 => Ok(Box::new(loads::ILoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        22 => Ok(Box::new(loads::LLoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        23 => Ok(Box::new(loads::FLoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        24 => Ok(Box::new(loads::DLoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        25 => Ok(Box::new(loads::ALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        26 => Ok(Box::new(loads::ILoad0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        27 => Ok(Box::new(loads::ILoad1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        28 => Ok(Box::new(loads::ILoad2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        29 => Ok(Box::new(loads::ILoad3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        30 => Ok(Box::new(loads::LLoad0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        31 => Ok(Box::new(loads::LLoad1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        32 => Ok(Box::new(loads::LLoad2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        33 => Ok(Box::new(loads::LLoad3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        34 => Ok(Box::new(loads::FLoad0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        35 => Ok(Box::new(loads::FLoad1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        36 => Ok(Box::new(loads::FLoad2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        37 => Ok(Box::new(loads::FLoad3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        38 => Ok(Box::new(loads::DLoad0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        39 => Ok(Box::new(loads::DLoad1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        40 => Ok(Box::new(loads::DLoad2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        41 => Ok(Box::new(loads::DLoad3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        42 => Ok(Box::new(loads::ALoad0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        43 => Ok(Box::new(loads::ALoad1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        44 => Ok(Box::new(loads::ALoad2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        45 => Ok(Box::new(loads::ALoad3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        46 => Ok(Box::new(loads::IALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        47 => Ok(Box::new(loads::LALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        48 => Ok(Box::new(loads::FALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        49 => Ok(Box::new(loads::DALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        50 => Ok(Box::new(loads::AALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        51 => Ok(Box::new(loads::BALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        52 => Ok(Box::new(loads::CALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        53 => Ok(Box::new(loads::SALoad::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        54 => Ok(Box::new(stores::IStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        55 => Ok(Box::new(stores::LStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        56 => Ok(Box::new(stores::FStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        57 => Ok(Box::new(stores::DStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        58 => Ok(Box::new(stores::AStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        59 => Ok(Box::new(stores::IStore0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        60 => Ok(Box::new(stores::IStore1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        61 => Ok(Box::new(stores::IStore2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        62 => Ok(Box::new(stores::IStore3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        63 => Ok(Box::new(stores::LStore0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        64 => Ok(Box::new(stores::LStore1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        65 => Ok(Box::new(stores::LStore2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        66 => Ok(Box::new(stores::LStore3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        67 => Ok(Box::new(stores::FStore0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        68 => Ok(Box::new(stores::FStore1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        69 => Ok(Box::new(stores::FStore2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        70 => Ok(Box::new(stores::FStore3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        71 => Ok(Box::new(stores::DStore0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        72 => Ok(Box::new(stores::DStore1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        73 => Ok(Box::new(stores::DStore2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        74 => Ok(Box::new(stores::DStore3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        75 => Ok(Box::new(stores::AStore0::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        76 => Ok(Box::new(stores::AStore1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        77 => Ok(Box::new(stores::AStore2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        78 => Ok(Box::new(stores::AStore3::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        79 => Ok(Box::new(stores::IAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        80 => Ok(Box::new(stores::LAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        81 => Ok(Box::new(stores::FAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        82 => Ok(Box::new(stores::DAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        83 => Ok(Box::new(stores::AAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        84 => Ok(Box::new(stores::BAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        85 => Ok(Box::new(stores::CAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        86 => Ok(Box::new(stores::SAStore::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        87 => Ok(Box::new(stack::Pop::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        88 => Ok(Box::new(stack::Pop2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        89 => Ok(Box::new(stack::Dup::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        90 => Ok(Box::new(stack::DupX1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        91 => Ok(Box::new(stack::DupX2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        92 => Ok(Box::new(stack::Dup2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        93 => Ok(Box::new(stack::Dup2X1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        94 => Ok(Box::new(stack::Dup2X2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        95 => Ok(Box::new(stack::Swap::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        96 => Ok(Box::new(math::IAdd::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        97 => Ok(Box::new(math::LAdd::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        98 => Ok(Box::new(math::FAdd::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        99 => Ok(Box::new(math::DAdd::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        100 => Ok(Box::new(math::ISub::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        101 => Ok(Box::new(math::LSub::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        102 => Ok(Box::new(math::FSub::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        103 => Ok(Box::new(math::DSub::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        104 => Ok(Box::new(math::IMul::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        105 => Ok(Box::new(math::LMul::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        106 => Ok(Box::new(math::FMul::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        107 => Ok(Box::new(math::DMul::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        108 => Ok(Box::new(math::IDiv::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        109 => Ok(Box::new(math::LDiv::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        110 => Ok(Box::new(math::FDiv::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        111 => Ok(Box::new(math::DDiv::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        112 => Ok(Box::new(math::IRem::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        113 => Ok(Box::new(math::LRem::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        114 => Ok(Box::new(math::FRem::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        115 => Ok(Box::new(math::DRem::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        116 => Ok(Box::new(math::INeg::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        117 => Ok(Box::new(math::LNeg::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        118 => Ok(Box::new(math::FNeg::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        119 => Ok(Box::new(math::DNeg::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        120 => Ok(Box::new(math::IShl::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        121 => Ok(Box::new(math::LShl::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        122 => Ok(Box::new(math::IShr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        123 => Ok(Box::new(math::LShr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        124 => Ok(Box::new(math::IUshr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        125 => Ok(Box::new(math::LUshr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        126 => Ok(Box::new(math::IAnd::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        127 => Ok(Box::new(math::LAnd::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        128 => Ok(Box::new(math::IOr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        129 => Ok(Box::new(math::LOr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        130 => Ok(Box::new(math::IXor::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        131 => Ok(Box::new(math::LXor::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        132 => Ok(Box::new(math::IInc::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        133 => Ok(Box::new(conversions::I2L::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        134 => Ok(Box::new(conversions::I2F::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        135 => Ok(Box::new(conversions::I2D::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        136 => Ok(Box::new(conversions::L2I::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        137 => Ok(Box::new(conversions::L2F::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        138 => Ok(Box::new(conversions::L2D::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        139 => Ok(Box::new(conversions::F2I::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        140 => Ok(Box::new(conversions::F2L::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        141 => Ok(Box::new(conversions::F2D::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        142 => Ok(Box::new(conversions::D2I::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        143 => Ok(Box::new(conversions::D2L::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        144 => Ok(Box::new(conversions::D2F::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        145 => Ok(Box::new(conversions::I2B::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        146 => Ok(Box::new(conversions::I2C::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        147 => Ok(Box::new(conversions::I2S::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        148 => Ok(Box::new(comparisons::LCmp::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        149 => Ok(Box::new(comparisons::FCmpL::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        150 => Ok(Box::new(comparisons::FCmpG::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        151 => Ok(Box::new(comparisons::DCmpL::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        152 => Ok(Box::new(comparisons::DcmpG::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        153 => Ok(Box::new(comparisons::IfEq::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        154 => Ok(Box::new(comparisons::IfNe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        155 => Ok(Box::new(comparisons::IfLt::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        156 => Ok(Box::new(comparisons::IfGe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        157 => Ok(Box::new(comparisons::IfGt::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        158 => Ok(Box::new(comparisons::IfLe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        159 => Ok(Box::new(comparisons::IfICmpEq::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        160 => Ok(Box::new(comparisons::IfICmpNe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        161 => Ok(Box::new(comparisons::IfICmpLt::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        162 => Ok(Box::new(comparisons::IfICmpGe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        163 => Ok(Box::new(comparisons::IfICmpGt::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        164 => Ok(Box::new(comparisons::IfICmpLe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        165 => Ok(Box::new(comparisons::IfACmpEq::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        166 => Ok(Box::new(comparisons::IfACmpNe::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        167 => Ok(Box::new(control::Goto::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        168 => Ok(Box::new(control::Jsr::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        169 => Ok(Box::new(control::Ret::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        170 => Ok(Box::new(control::TableSwitch::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        171 => Ok(Box::new(control::LookupSwitch::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        172 => Ok(Box::new(control::IReturn::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        173 => Ok(Box::new(control::LReturn::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        174 => Ok(Box::new(control::FReturn::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        175 => Ok(Box::new(control::DReturn::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        176 => Ok(Box::new(control::AReturn::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        177 => Ok(Box::new(control::Return::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        178 => Ok(Box::new(references::GetStatic::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        179 => Ok(Box::new(references::PutStatic::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        180 => Ok(Box::new(references::GetField::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        181 => Ok(Box::new(references::PutField::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        182 => Ok(Box::new(references::InvokeVirtual::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        183 => Ok(Box::new(references::InvokeSpecial::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        184 => Ok(Box::new(references::InvokeStatic::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        185 => Ok(Box::new(references::InvokeInterface::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        186 => Ok(Box::new(references::InvokeDynamic::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        187 => Ok(Box::new(references::New::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        188 => Ok(Box::new(references::NewArray::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        189 => Ok(Box::new(references::ANewArray::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        190 => Ok(Box::new(references::ArrayLength::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        191 => Ok(Box::new(references::AThrow::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        192 => Ok(Box::new(references::CheckCast::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        193 => Ok(Box::new(references::InstanceOf::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        194 => Ok(Box::new(references::MonitorEnter::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        195 => Ok(Box::new(references::MonitorExit::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        196 => Ok(Box::new(extended::Wide::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        197 => Ok(Box::new(extended::MultiANewArray::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        198 => Ok(Box::new(extended::IfNull::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        199 => Ok(Box::new(extended::IfNonNull::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        200 => Ok(Box::new(extended::GotoW::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        201 => Ok(Box::new(extended::JsrW::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        202 => Ok(Box::new(reserved::Breakpoint::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        
-        254 => Ok(Box::new(reserved::ImpDep1::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        255 => Ok(Box::new(reserved::ImpDep2::new(v, c, jvm, was_wide, true_pc)?) as Box<dyn Instruction>),
-        _ => {
-            panic!("Error: Opcode {} not supported yet", op);
-        },
+// Every instruction the interpreter can hold, decoded and stored inline. Dispatch is a match over this,
+// which compiles to a jump table, instead of going through a Box<dyn Instruction> vtable for every step.
+// Anything that isn't hot (jit, compress_range, printing) still goes through Instruction, by deref.
+macro_rules! instruction_set {
+    (decoded { $($opcode:literal => $module:ident::$ty:ident,)* } quick { $($qty:ident,)* }) => {
+        #[derive(Clone)]
+        pub enum Op {
+            $($ty($module::$ty),)*
+            $($qty(quick::$qty),)*
+        }
+
+        impl Op {
+            #[inline(always)]
+            pub fn execute(&mut self, jvm: &mut JVM) -> Result<Flow, Error> {
+                match self {
+                    $(Op::$ty(i) => i.execute(jvm),)*
+                    $(Op::$qty(i) => i.execute(jvm),)*
+                }
+            }
+        }
+
+        impl std::ops::Deref for Op {
+            type Target = dyn Instruction;
+            fn deref(&self) -> &Self::Target {
+                match self {
+                    $(Op::$ty(i) => i,)*
+                    $(Op::$qty(i) => i,)*
+                }
+            }
+        }
+        impl std::ops::DerefMut for Op {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                match self {
+                    $(Op::$ty(i) => i,)*
+                    $(Op::$qty(i) => i,)*
+                }
+            }
+        }
+
+        $(impl From<$module::$ty> for Op {
+            fn from(i: $module::$ty) -> Op {
+                Op::$ty(i)
+            }
+        })*
+        $(impl From<quick::$qty> for Op {
+            fn from(i: quick::$qty) -> Op {
+                Op::$qty(i)
+            }
+        })*
+
+        pub fn new_instruction(v: &mut Vec<u8>, c: &Vec<Entry>, jvm: &mut JVM, was_wide: bool, true_pc: usize) -> Result<Op, Error> {
+            let op = v[0];
+            v.remove(0);
+            match op {
+                $($opcode => Ok($module::$ty::new(v, c, jvm, was_wide, true_pc)?.into()),)*
+                _ => Err(Error::IllegalOpcode(op)),
+            }
+        }
+    };
+}
+
+instruction_set! {
+    decoded {
+        0 => constants::Nop,
+        1 => constants::AConstNull,
+        2 => constants::IConstM1,
+        3 => constants::IConst0,
+        4 => constants::IConst1,
+        5 => constants::IConst2,
+        6 => constants::IConst3,
+        7 => constants::IConst4,
+        8 => constants::IConst5,
+        9 => constants::LConst0,
+        10 => constants::LConst1,
+        11 => constants::FConst0,
+        12 => constants::FConst1,
+        13 => constants::FConst2,
+        14 => constants::DConst0,
+        15 => constants::DConst1,
+        16 => constants::BiPush,
+        17 => constants::SiPush,
+        18 => constants::Ldc,
+        19 => constants::LdcW,
+        20 => constants::Ldc2W,
+        21 => loads::ILoad,
+        22 => loads::LLoad,
+        23 => loads::FLoad,
+        24 => loads::DLoad,
+        25 => loads::ALoad,
+        26 => loads::ILoad0,
+        27 => loads::ILoad1,
+        28 => loads::ILoad2,
+        29 => loads::ILoad3,
+        30 => loads::LLoad0,
+        31 => loads::LLoad1,
+        32 => loads::LLoad2,
+        33 => loads::LLoad3,
+        34 => loads::FLoad0,
+        35 => loads::FLoad1,
+        36 => loads::FLoad2,
+        37 => loads::FLoad3,
+        38 => loads::DLoad0,
+        39 => loads::DLoad1,
+        40 => loads::DLoad2,
+        41 => loads::DLoad3,
+        42 => loads::ALoad0,
+        43 => loads::ALoad1,
+        44 => loads::ALoad2,
+        45 => loads::ALoad3,
+        46 => loads::IALoad,
+        47 => loads::LALoad,
+        48 => loads::FALoad,
+        49 => loads::DALoad,
+        50 => loads::AALoad,
+        51 => loads::BALoad,
+        52 => loads::CALoad,
+        53 => loads::SALoad,
+        54 => stores::IStore,
+        55 => stores::LStore,
+        56 => stores::FStore,
+        57 => stores::DStore,
+        58 => stores::AStore,
+        59 => stores::IStore0,
+        60 => stores::IStore1,
+        61 => stores::IStore2,
+        62 => stores::IStore3,
+        63 => stores::LStore0,
+        64 => stores::LStore1,
+        65 => stores::LStore2,
+        66 => stores::LStore3,
+        67 => stores::FStore0,
+        68 => stores::FStore1,
+        69 => stores::FStore2,
+        70 => stores::FStore3,
+        71 => stores::DStore0,
+        72 => stores::DStore1,
+        73 => stores::DStore2,
+        74 => stores::DStore3,
+        75 => stores::AStore0,
+        76 => stores::AStore1,
+        77 => stores::AStore2,
+        78 => stores::AStore3,
+        79 => stores::IAStore,
+        80 => stores::LAStore,
+        81 => stores::FAStore,
+        82 => stores::DAStore,
+        83 => stores::AAStore,
+        84 => stores::BAStore,
+        85 => stores::CAStore,
+        86 => stores::SAStore,
+        87 => stack::Pop,
+        88 => stack::Pop2,
+        89 => stack::Dup,
+        90 => stack::DupX1,
+        91 => stack::DupX2,
+        92 => stack::Dup2,
+        93 => stack::Dup2X1,
+        94 => stack::Dup2X2,
+        95 => stack::Swap,
+        96 => math::IAdd,
+        97 => math::LAdd,
+        98 => math::FAdd,
+        99 => math::DAdd,
+        100 => math::ISub,
+        101 => math::LSub,
+        102 => math::FSub,
+        103 => math::DSub,
+        104 => math::IMul,
+        105 => math::LMul,
+        106 => math::FMul,
+        107 => math::DMul,
+        108 => math::IDiv,
+        109 => math::LDiv,
+        110 => math::FDiv,
+        111 => math::DDiv,
+        112 => math::IRem,
+        113 => math::LRem,
+        114 => math::FRem,
+        115 => math::DRem,
+        116 => math::INeg,
+        117 => math::LNeg,
+        118 => math::FNeg,
+        119 => math::DNeg,
+        120 => math::IShl,
+        121 => math::LShl,
+        122 => math::IShr,
+        123 => math::LShr,
+        124 => math::IUshr,
+        125 => math::LUshr,
+        126 => math::IAnd,
+        127 => math::LAnd,
+        128 => math::IOr,
+        129 => math::LOr,
+        130 => math::IXor,
+        131 => math::LXor,
+        132 => math::IInc,
+        133 => conversions::I2L,
+        134 => conversions::I2F,
+        135 => conversions::I2D,
+        136 => conversions::L2I,
+        137 => conversions::L2F,
+        138 => conversions::L2D,
+        139 => conversions::F2I,
+        140 => conversions::F2L,
+        141 => conversions::F2D,
+        142 => conversions::D2I,
+        143 => conversions::D2L,
+        144 => conversions::D2F,
+        145 => conversions::I2B,
+        146 => conversions::I2C,
+        147 => conversions::I2S,
+        148 => comparisons::LCmp,
+        149 => comparisons::FCmpL,
+        150 => comparisons::FCmpG,
+        151 => comparisons::DCmpL,
+        152 => comparisons::DcmpG,
+        153 => comparisons::IfEq,
+        154 => comparisons::IfNe,
+        155 => comparisons::IfLt,
+        156 => comparisons::IfGe,
+        157 => comparisons::IfGt,
+        158 => comparisons::IfLe,
+        159 => comparisons::IfICmpEq,
+        160 => comparisons::IfICmpNe,
+        161 => comparisons::IfICmpLt,
+        162 => comparisons::IfICmpGe,
+        163 => comparisons::IfICmpGt,
+        164 => comparisons::IfICmpLe,
+        165 => comparisons::IfACmpEq,
+        166 => comparisons::IfACmpNe,
+        167 => control::Goto,
+        168 => control::Jsr,
+        169 => control::Ret,
+        170 => control::TableSwitch,
+        171 => control::LookupSwitch,
+        172 => control::IReturn,
+        173 => control::LReturn,
+        174 => control::FReturn,
+        175 => control::DReturn,
+        176 => control::AReturn,
+        177 => control::Return,
+        178 => references::GetStatic,
+        179 => references::PutStatic,
+        180 => references::GetField,
+        181 => references::PutField,
+        182 => references::InvokeVirtual,
+        183 => references::InvokeSpecial,
+        184 => references::InvokeStatic,
+        185 => references::InvokeInterface,
+        186 => references::InvokeDynamic,
+        187 => references::New,
+        188 => references::NewArray,
+        189 => references::ANewArray,
+        190 => references::ArrayLength,
+        191 => references::AThrow,
+        192 => references::CheckCast,
+        193 => references::InstanceOf,
+        194 => references::MonitorEnter,
+        195 => references::MonitorExit,
+        196 => extended::Wide,
+        197 => extended::MultiANewArray,
+        198 => extended::IfNull,
+        199 => extended::IfNonNull,
+        200 => extended::GotoW,
+        201 => extended::JsrW,
+        202 => reserved::Breakpoint,
+        254 => reserved::ImpDep1,
+        255 => reserved::ImpDep2,
+    }
+    quick {
+        GetStaticQuick,
+        PutStaticQuick,
+        GetFieldQuick,
+        PutFieldQuick,
+        InvokeVirtualQuick,
+        InvokeSpecialQuick,
+        InvokeStaticQuick,
+        InvokeInterfaceQuick,
+        InvokeDynamicQuick,
+        NewQuick,
+        LdcQuick,
     }
-}
\ No newline at end of file
+}
+
+impl std::fmt::Debug for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+impl std::fmt::Display for Op {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        (**self).fmt(f)
+    }
+}
+impl PartialEq for Op {
+    fn eq(&self, other: &Self) -> bool {
+        Instruction::eq(&**self, &**other)
+    }
+}
@@ -14,7 +14,7 @@ impl Instruction for LCmp {
             Ok(LCmp {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -33,7 +33,7 @@ impl Instruction for LCmp {
             }
         };
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -59,7 +59,7 @@ impl Instruction for FCmpL {
             Ok(FCmpL {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -80,7 +80,7 @@ impl Instruction for FCmpL {
             }
         };
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -106,7 +106,7 @@ impl Instruction for FCmpG {
             Ok(FCmpG {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -127,7 +127,7 @@ impl Instruction for FCmpG {
             }
         };
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -153,7 +153,7 @@ impl Instruction for DCmpL {
             Ok(DCmpL {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -174,7 +174,7 @@ impl Instruction for DCmpL {
             }
         };
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -200,7 +200,7 @@ impl Instruction for DcmpG {
             Ok(DcmpG {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -221,7 +221,7 @@ impl Instruction for DcmpG {
             }
         };
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -253,7 +253,7 @@ impl Instruction for IfEq {
             Ok(IfEq {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -263,11 +263,11 @@ impl Instruction for IfEq {
             };
             match *val.as_int()? == 0 {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -300,7 +300,7 @@ impl Instruction for IfNe {
             Ok(IfNe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -310,11 +310,11 @@ impl Instruction for IfNe {
             };
             match *val.as_int()? != 0 {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -347,7 +347,7 @@ impl Instruction for IfLt {
             Ok(IfLt {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -357,11 +357,11 @@ impl Instruction for IfLt {
             };
             match *val.as_int()? < 0 {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -394,7 +394,7 @@ impl Instruction for IfGe {
             Ok(IfGe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -404,11 +404,11 @@ impl Instruction for IfGe {
             };
             match *val.as_int()? >= 0 {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -441,7 +441,7 @@ impl Instruction for IfGt {
             Ok(IfGt {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -451,11 +451,11 @@ impl Instruction for IfGt {
             };
             match *val.as_int()? > 0 {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -488,7 +488,7 @@ impl Instruction for IfLe {
             Ok(IfLe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -498,11 +498,11 @@ impl Instruction for IfLe {
             };
             match *val.as_int()? <= 0 {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -535,7 +535,7 @@ impl Instruction for IfICmpEq {
             Ok(IfICmpEq {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -549,11 +549,11 @@ impl Instruction for IfICmpEq {
             };
             match val1.as_int()? == val2.as_int()? {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -586,7 +586,7 @@ impl Instruction for IfICmpNe {
             Ok(IfICmpNe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -600,11 +600,11 @@ impl Instruction for IfICmpNe {
             };
             match val1.as_int()? != val2.as_int()? {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -637,7 +637,7 @@ impl Instruction for IfICmpLt {
             Ok(IfICmpLt {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -651,11 +651,11 @@ impl Instruction for IfICmpLt {
             };
             match val1.as_int()? < val2.as_int()? {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -688,7 +688,7 @@ impl Instruction for IfICmpGe {
             Ok(IfICmpGe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -702,11 +702,11 @@ impl Instruction for IfICmpGe {
             };
             match val1.as_int()? >= val2.as_int()? {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -739,7 +739,7 @@ impl Instruction for IfICmpGt {
             Ok(IfICmpGt {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -753,11 +753,11 @@ impl Instruction for IfICmpGt {
             };
             match val1.as_int()? > val2.as_int()? {
                 true => self.offset,
-                false => 0,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -790,7 +790,7 @@ impl Instruction for IfICmpLe {
             Ok(IfICmpLe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -804,11 +804,11 @@ impl Instruction for IfICmpLe {
             };
             match val1.as_int()? <= val2.as_int()? {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -841,7 +841,7 @@ impl Instruction for IfACmpEq {
             Ok(IfACmpEq {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -855,11 +855,11 @@ impl Instruction for IfACmpEq {
             };
             match Reference::ptr_eq(&val1.as_reference()?, &val2.as_reference()?) {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -892,7 +892,7 @@ impl Instruction for IfACmpNe {
             Ok(IfACmpNe {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -906,12 +906,11 @@ impl Instruction for IfACmpNe {
             };
             match !Reference::ptr_eq(&val1.as_reference()?, &val2.as_reference()?) {
                 true => self.offset,
-                false => 1,
+                false => return Ok(Flow::Next),
             }
         };
         thread.inc_pc(offset)?;
-        println!("IFACmpNe took us to {}", thread.pc());
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -1,8 +1,8 @@
 use crate::reference::object::natives;
 use crate::reference::object::Object;
 use super::*;
-
-use colored::Colorize;
+use super::quick::LdcQuick;
+use crate::constant_pool::DynamicInfo;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Nop {}
@@ -17,8 +17,8 @@ impl Instruction for Nop {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, _jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
-        Ok(())
+    fn execute(&mut self, _jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -43,11 +43,11 @@ impl Instruction for AConstNull {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);  
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Reference(Reference::Null));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -72,11 +72,11 @@ impl Instruction for IConstM1 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);       
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(-1));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -101,11 +101,11 @@ impl Instruction for IConst0 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -130,11 +130,11 @@ impl Instruction for IConst1 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(1));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -159,11 +159,11 @@ impl Instruction for IConst2 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(2));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -206,11 +206,11 @@ impl Instruction for IConst3 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(3));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -235,11 +235,11 @@ impl Instruction for IConst4 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(4));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -264,11 +264,11 @@ impl Instruction for IConst5 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Int(5));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -293,11 +293,11 @@ impl Instruction for LConst0 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Long(0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -322,11 +322,11 @@ impl Instruction for LConst1 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Long(1));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -351,11 +351,11 @@ impl Instruction for FConst0 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Float(0.0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -380,11 +380,11 @@ impl Instruction for FConst1 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Float(1.0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -409,11 +409,11 @@ impl Instruction for FConst2 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Float(2.0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -438,11 +438,11 @@ impl Instruction for DConst0 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Double(0.0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -467,11 +467,11 @@ impl Instruction for DConst1 {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut super::JVM) -> Result<(), crate::errorcodes::Error> {
+    fn execute(&mut self, jvm : &mut super::JVM) -> Result<Flow, crate::errorcodes::Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Double(1.0));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -497,11 +497,11 @@ impl Instruction for BiPush {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Byte(self.byte));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -531,11 +531,11 @@ impl Instruction for SiPush {
             Err(Error::IllegalWide)
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Short(self.short));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -569,6 +569,10 @@ pub mod ldc {
 
     pub trait LDCFunc : Debug + DynClone {
         fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error>;
+        // Constants that had to be resolved give back what they resolved to, so the ldc can be quickened.
+        fn quicken(&self) -> Option<Value<dyn Class, dyn Object>> {
+            None
+        }
         fn as_any(&self) -> &dyn Any;
         fn eq(&self, other: &dyn LDCFunc) -> bool;
     }
@@ -629,40 +633,65 @@ pub mod ldc {
             write!(f, "{self}")
         }
     }
-    #[derive(Debug, PartialEq, Clone)]
+    #[derive(Debug, Clone)]
     pub struct LDCClass {
         pub c_name: String,
+        pub mirror: Option<Value<dyn Class, dyn Object>>,
     }
     impl LDCFunc for LDCClass {
         fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-            jvm.gen_class_obj(self.c_name.as_str())
+            let mirror = Value::Reference(jvm.class_mirror_from_name(&self.c_name)?);
+            self.mirror = Some(mirror.clone());
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            frame.op_stack.push(mirror);
+            Ok(())
+        }
+        fn quicken(&self) -> Option<Value<dyn Class, dyn Object>> {
+            self.mirror.clone()
         }
         comparable!{}
     }
-    #[derive(Debug, PartialEq, Clone)]
-    pub struct LDCMethodType {
-        pub desc: &'static str, // Same idea as above
+    impl PartialEq for LDCClass {
+        fn eq(&self, other: &Self) -> bool {
+            self.c_name == other.c_name
+        }
+    }
+    // MethodTypes, MethodHandles, and dynamically-computed constants. These get resolved the first time any ldc loads them,
+    // and the runtime constant pool keeps what they resolved to (see JVM::load_constant). After that the ldc gets quickened.
+    #[derive(Clone)]
+    pub struct LDCResolved {
+        pub index: u16,
+        pub opcode: Opcode,
+        pub value: Option<Value<dyn Class, dyn Object>>,
     }
-    impl LDCFunc for LDCMethodType {
+    impl LDCFunc for LDCResolved {
         fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-            let desc = String::from(self.desc);
-            let (params_s, ret_s) = desc.split_at(desc.rfind(')').unwrap());
-            let params_s = String::from(params_s);
-            // In makeimpl, it takes a bool asking whether the params are trusted. I think they are in this case.
-            jvm.gen_class_obj(ret_s)?;
-            let idx = 0;
-            // This logic should be merged with setup_method_call
-            while idx < params_s.len() {
-
-            }
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            let current_class = Rc::clone(&frame.rt_const_pool);
+            let value = jvm.load_constant(current_class, self.index, self.opcode.clone())?;
+            self.value = Some(value.clone());
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            frame.op_stack.push(value);
             Ok(())
         }
+        fn quicken(&self) -> Option<Value<dyn Class, dyn Object>> {
+            self.value.clone()
+        }
         comparable!{}
     }
-    #[derive(Debug, PartialEq, Clone)]
-    pub struct LDCMethodHandle;
-    #[derive(Debug, PartialEq, Clone)]
-    pub struct LDCDynamic;
+    impl PartialEq for LDCResolved {
+        fn eq(&self, other: &Self) -> bool {
+            self.index == other.index
+        }
+    }
+    impl Debug for LDCResolved {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "LDCResolved #{}", self.index)
+        }
+    }
     #[derive(Debug, PartialEq, Clone)]
     pub struct LDCDouble {
         pub d: f64,
@@ -690,23 +719,15 @@ pub mod ldc {
         comparable!{}
     }
 
-    #[derive(Debug, PartialEq, Clone)]
-    pub struct LDCTodo {}
-    impl LDCFunc for LDCTodo {
-        fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-            // Just to try to get farther
-            eprintln!("{}", "WARNING: LDCTodo called, attempting to fudge results".red());
-
-            let thread = access_macros::current_thread_mut!(jvm);
-            let frame = access_macros::current_frame_mut!(thread);
-            frame.op_stack.push(Value::Reference(Reference::Null)); // The only unimplemented ldcs are reference ones.
-            Ok(())
-        }
-        comparable!{}
-    }
     clone_trait_object!(LDCFunc);
 }
 
+// The type of a dynamically-computed constant, which decides which ldc can load it.
+fn dynamic_descriptor<'a>(cpool: &'a [Entry], dynamic: &DynamicInfo) -> Result<&'a str, Error> {
+    let name_and_type = cpool[dynamic.name_and_type_index as usize - 1].as_name_and_type()?;
+    Ok(cpool[name_and_type.descriptor_index as usize - 1].as_utf8()?)
+}
+
 #[derive(Debug, Clone)]
 pub struct Ldc {
     f: Box<dyn ldc::LDCFunc> // Has to be a ptr for size reasons. This still should be performant. 
@@ -721,7 +742,8 @@ impl Instruction for Ldc {
         }
         else {
             // First, get the constant pool entry at that index.
-            let entry = &cpool[v[0] as usize - 1];
+            let index = v[0] as u16;
+            let entry = &cpool[index as usize - 1];
             let f = match entry {
                 Entry::Integer(i) => Box::new(ldc::LDCInt {i: *i}) as Box<dyn ldc::LDCFunc>,
                 Entry::Float(f) => Box::new(ldc::LDCFloat {f: *f}) as Box<dyn ldc::LDCFunc>,
@@ -730,50 +752,15 @@ impl Instruction for Ldc {
                            natives::string::String::new_from_string(cpool[*s as usize - 1].as_utf8()?.clone(), jvm)?, 
                         Rc::new(Monitor::new())) 
                 }),
-                Entry::Class(_c) => {
-                    // The spec says we have to return a reference to the class or interface itself. 
-                    // I think it means that we have to create a java.lang.Class object and return a reference.
-                    // For the same reasons as string above, we are not implementing this right now.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
-                // For these next 2, see https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.5
-                Entry::MethodType(_m) => {
-                    // This one is a reference to java.lang.invoke.MethodType.
-                    // See above.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
-                Entry::MethodHandle(_m) => {
-                    // This one is incredibly complicated, but should result in a java.lang.invoke.MethodHandle.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
-                Entry::MethodRef(_m) => {
-                    // Apparently this is loadable
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                }
-                Entry::Dynamic(_dynamic) => {
-                    // This Dynamic cannot reference a field with discriptor Long or Double.
-    
-                    // For more information about the process see: https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.6
-                    // First, we have to find a 'bootstrap method' to call to produce the value.
-                    // This is done by indexing into the BootstrapMethods Attribute of the current class.
-                    // This gives us a methodhandle info and a list of static arguments (loadable entries in the constant pool).
-                    // The method handle is resolved in the same way as above, except that the first parameter of the method must be java.lang.invoke.MethodHandles.Lookup.
-                    // If it's not, we fail with a BootstrapMethodError.
-                    // We alse get a field descriptor, from which we create a java.lang.Class object from it.
-                    // We then resolve every static argument. This process can be recursive, so we should make it a function.
-                    // Second, we have to call the bootstrap method.
-                    // To do this, we first create an Array of Object with length n + 3, where n is the number of static arguments.
-                    // The 0[] index is a reference an instance of java.lang.invoke.MethodHandles.Lookup for the current class.
-                    // The 1[] index is an reference to an instance of java.lang.String denoting the unqualified name from the name and type info.
-                    // The 2[] index is the reference to and instance of Class obtained earlier.
-                    // The rest of the array is filled with the static arguments.
-                    // The method handle is invoked with BMH.invokeWithArguments(args).
-                    // Finally, we have to validate the reference produced by the invocation.
-                    // the reference o is converted as if by invoking MH.invoke(o), 
-                    // where MH is a method handler produced from invoking the identity(class Object) method of java.lang.invoke.MethodHandles.
-                    // If this gives a NullPtrException or a ClassCastExeption, we fail with a BootstrapMethodError.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
+                Entry::Class(c) => Box::new(ldc::LDCClass { c_name: cpool[*c as usize - 1].as_utf8()?.clone(), mirror: None }) as Box<dyn ldc::LDCFunc>,
+                // For these next 3, see https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.5
+                // and https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.6
+                // They can't be resolved until we know which class we're running in, so that happens in JVM::load_constant.
+                // A Dynamic here can't have a long or double type, which only ldc2_w can load.
+                Entry::Dynamic(d) if matches!(dynamic_descriptor(cpool, d)?, "J" | "D") => return Err(Error::IllegalConstantLoad(Opcode::LDC)),
+                Entry::MethodType(_) | Entry::MethodHandle(_) | Entry::Dynamic(_) => Box::new(ldc::LDCResolved {
+                    index, opcode: Opcode::LDC, value: None,
+                }) as Box<dyn ldc::LDCFunc>,
                 Entry::Long(_) | Entry::Double(_) => {
                     // Even though these are loadable, they shouldn't appear here
                     return Err(Error::IllegalConstantLoad(Opcode::LDC));
@@ -786,8 +773,12 @@ impl Instruction for Ldc {
             Ok(Ldc { f })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-        self.f.execute(jvm)
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        self.f.execute(jvm)?;
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.f.quicken().map(|value| LdcQuick { name: "ldc_quick", value }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -818,9 +809,10 @@ impl Instruction for LdcW {
         }
         else {
             // First, get the constant pool entry at that index.
-            let entry = &cpool[unsafe {
+            let index = unsafe {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap())
-            } as usize - 1];
+            };
+            let entry = &cpool[index as usize - 1];
             
             let f = match entry {
                 Entry::Integer(i) => Box::new(ldc::LDCInt {i: *i}) as Box<dyn ldc::LDCFunc>,
@@ -830,52 +822,15 @@ impl Instruction for LdcW {
                            natives::string::String::new_from_string(cpool[*s as usize - 1].as_utf8()?.clone(), jvm)?, 
                         Rc::new(Monitor::new())) 
                 }),
-                Entry::Class(_c) => {
-                    // The spec says we have to return a reference to the class or interface itself. 
-                    // I think it means that we have to create a java.lang.Class object and return a reference.
-                    // For the same reasons as string above, we are not implementing this right now.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
-                // For these next 2, see https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.5
-                Entry::MethodType(_m) => {
-                    // This one is a reference to java.lang.invoke.MethodType.
-                    // See above.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
-                Entry::MethodHandle(_m) => {
-                    // This one is incredibly complicated, but should result in a java.lang.invoke.MethodHandle.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
-                /* 
-                Entry::MethodRef(_r) => {
-                    // Apparently this is allowed?
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                }
-                */
-                Entry::Dynamic(_dynamic) => {
-                    // This Dynamic cannot reference a field with discriptor Long or Double.
-    
-                    // For more information about the process see: https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.6
-                    // First, we have to find a 'bootstrap method' to call to produce the value.
-                    // This is done by indexing into the BootstrapMethods Attribute of the current class.
-                    // This gives us a methodhandle info and a list of static arguments (loadable entries in the constant pool).
-                    // The method handle is resolved in the same way as above, except that the first parameter of the method must be java.lang.invoke.MethodHandles.Lookup.
-                    // If it's not, we fail with a BootstrapMethodError.
-                    // We alse get a field descriptor, from which we create a java.lang.Class object from it.
-                    // We then resolve every static argument. This process can be recursive, so we should make it a function.
-                    // Second, we have to call the bootstrap method.
-                    // To do this, we first create an Array of Object with length n + 3, where n is the number of static arguments.
-                    // The 0[] index is a reference an instance of java.lang.invoke.MethodHandles.Lookup for the current class.
-                    // The 1[] index is an reference to an instance of java.lang.String denoting the unqualified name from the name and type info.
-                    // The 2[] index is the reference to and instance of Class obtained earlier.
-                    // The rest of the array is filled with the static arguments.
-                    // The method handle is invoked with BMH.invokeWithArguments(args).
-                    // Finally, we have to validate the reference produced by the invocation.
-                    // the reference o is converted as if by invoking MH.invoke(o), 
-                    // where MH is a method handler produced from invoking the identity(class Object) method of java.lang.invoke.MethodHandles.
-                    // If this gives a NullPtrException or a ClassCastExeption, we fail with a BootstrapMethodError.
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
+                Entry::Class(c) => Box::new(ldc::LDCClass { c_name: cpool[*c as usize - 1].as_utf8()?.clone(), mirror: None }) as Box<dyn ldc::LDCFunc>,
+                // For these next 3, see https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.5
+                // and https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.6
+                // They can't be resolved until we know which class we're running in, so that happens in JVM::load_constant.
+                // A Dynamic here can't have a long or double type, which only ldc2_w can load.
+                Entry::Dynamic(d) if matches!(dynamic_descriptor(cpool, d)?, "J" | "D") => return Err(Error::IllegalConstantLoad(Opcode::LDCW)),
+                Entry::MethodType(_) | Entry::MethodHandle(_) | Entry::Dynamic(_) => Box::new(ldc::LDCResolved {
+                    index, opcode: Opcode::LDCW, value: None,
+                }) as Box<dyn ldc::LDCFunc>,
                 Entry::Long(_) | Entry::Double(_) => {
                     // Even though these are loadable, they shouldn't appear here
                     return Err(Error::IllegalConstantLoad(Opcode::LDCW));
@@ -889,8 +844,12 @@ impl Instruction for LdcW {
             Ok(LdcW { f })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-        self.f.execute(jvm)
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        self.f.execute(jvm)?;
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.f.quicken().map(|value| LdcQuick { name: "ldc_w_quick", value }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -921,16 +880,18 @@ impl Instruction for Ldc2W {
         }
         else {
             // First, get the constant pool entry at that index.
-            let entry = &cpool[unsafe {
-                u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) as usize
-            } - 1];
+            let index = unsafe {
+                u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap())
+            };
+            let entry = &cpool[index as usize - 1];
             let f = match entry {
                 Entry::Double(d) => Box::new(ldc::LDCDouble {d: *d}) as Box<dyn ldc::LDCFunc>,
                 Entry::Long(l) => Box::new(ldc::LDCLong {l: *l}) as Box<dyn ldc::LDCFunc>,
-                Entry::Dynamic(_dynamic) => {
-                    // like LdcW dynamic, except it can only load references to longs or doubles
-                    Box::new(ldc::LDCTodo {}) as Box<dyn ldc::LDCFunc>
-                },
+                // Like LdcW dynamic, except it can only load longs or doubles.
+                Entry::Dynamic(d) if !matches!(dynamic_descriptor(cpool, d)?, "J" | "D") => return Err(Error::IllegalConstantLoad(Opcode::LDC2W)),
+                Entry::Dynamic(_) => Box::new(ldc::LDCResolved {
+                    index, opcode: Opcode::LDC2W, value: None,
+                }) as Box<dyn ldc::LDCFunc>,
                 Entry::Integer(_) | Entry::Float(_) | Entry::String(_) | Entry::Class(_) | 
                 Entry::MethodHandle(_) | Entry::MethodType(_) => return Err(Error::IllegalConstantLoad(Opcode::LDC2W)),
                 _ => return Err(Error::IllegalConstantLoad(Opcode::LDC2W)),
@@ -940,8 +901,12 @@ impl Instruction for Ldc2W {
             Ok(Ldc2W { f })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-        self.f.execute(jvm)
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        self.f.execute(jvm)?;
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.f.quicken().map(|value| LdcQuick { name: "ldc2_w_quick", value }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -20,10 +20,10 @@ impl Instruction for Goto {
             Ok(Goto {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         thread.inc_pc(self.offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -56,13 +56,14 @@ impl Instruction for Jsr {
             Ok(Jsr {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
-        let pc = thread.pc() as u16;
+        // ret comes back to the instruction after this one.
+        let pc = thread.pc() as u16 + 1;
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::ReturnAddress(pc));
         thread.inc_pc(self.offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -93,14 +94,14 @@ impl Instruction for Ret {
             Ok(Ret {index})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let addr = {
             let frame = access_macros::current_frame_mut!(thread);
             *frame.local_variables[self.index].as_retaddr()?
         };
         thread.set_pc(addr as usize)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -137,10 +138,10 @@ impl Instruction for TableSwitch {
                 i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap())
             } as isize;
             let low = unsafe {
-                i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap())
+                i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr().offset(4), 4).try_into().unwrap())
             } as isize;
             let high = unsafe {
-                i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap())
+                i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr().offset(8), 4).try_into().unwrap())
             } as isize;
             v.remove(0); v.remove(0); v.remove(0); v.remove(0); 
             v.remove(0); v.remove(0); v.remove(0); v.remove(0);
@@ -157,7 +158,7 @@ impl Instruction for TableSwitch {
             Ok(TableSwitch { default, low, high, j_offsets })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -171,7 +172,14 @@ impl Instruction for TableSwitch {
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
+    }
+    fn compress_range(&mut self, this_pc: usize, translation_map: &HashMap<usize, usize>) {
+        let compress = |offset: isize| *translation_map.get(&((this_pc as isize + offset) as usize)).unwrap() as isize - *translation_map.get(&this_pc).unwrap() as isize;
+        self.default = compress(self.default);
+        for offset in self.j_offsets.iter_mut() {
+            *offset = compress(*offset);
+        }
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -228,7 +236,7 @@ impl Instruction for LookupSwitch {
             Ok(LookupSwitch { default, pairs})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let offset = {
             // Maybe cache offsets?
@@ -243,7 +251,14 @@ impl Instruction for LookupSwitch {
             }
         };
         thread.inc_pc(offset)?;
-        Ok(())
+        Ok(Flow::Jump)
+    }
+    fn compress_range(&mut self, this_pc: usize, translation_map: &HashMap<usize, usize>) {
+        let compress = |offset: isize| *translation_map.get(&((this_pc as isize + offset) as usize)).unwrap() as isize - *translation_map.get(&this_pc).unwrap() as isize;
+        self.default = compress(self.default);
+        for (_, offset) in self.pairs.iter_mut() {
+            *offset = compress(*offset);
+        }
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -269,7 +284,7 @@ impl Instruction for IReturn {
             Ok(IReturn {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let return_value = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -290,13 +305,15 @@ impl Instruction for IReturn {
                 _ => unreachable!(),
             }
         };
-        match thread.m_stack.pop() {
+        jvm.exit_method_monitor()?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        match thread.pop_frame() {
             Some(_) => (),
             None => return Err(Error::FrameStackUnderflow(Opcode::IRETURN)),
         }
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(return_value);
-        Ok(())
+        Ok(Flow::Return)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -322,7 +339,7 @@ impl Instruction for LReturn {
             Ok(LReturn {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let return_value = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -334,13 +351,15 @@ impl Instruction for LReturn {
                 None => return Err(Error::StackUnderflow(Opcode::LRETURN)),
             }          
         };
-        match thread.m_stack.pop() {
+        jvm.exit_method_monitor()?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        match thread.pop_frame() {
             Some(_) => (),
             None => return Err(Error::FrameStackUnderflow(Opcode::LRETURN)),
         }
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(return_value);
-        Ok(())
+        Ok(Flow::Return)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -366,7 +385,7 @@ impl Instruction for FReturn {
             Ok(FReturn {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let return_value = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -378,13 +397,15 @@ impl Instruction for FReturn {
                 None => return Err(Error::StackUnderflow(Opcode::FRETURN)),
             }          
         };
-        match thread.m_stack.pop() {
+        jvm.exit_method_monitor()?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        match thread.pop_frame() {
             Some(_) => (),
             None => return Err(Error::FrameStackUnderflow(Opcode::FRETURN)),
         }
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(return_value);
-        Ok(())
+        Ok(Flow::Return)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -410,7 +431,7 @@ impl Instruction for DReturn {
             Ok(DReturn {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let return_value = {
             let frame = access_macros::current_frame_mut!(thread);
@@ -422,13 +443,15 @@ impl Instruction for DReturn {
                 None => return Err(Error::StackUnderflow(Opcode::DRETURN)),
             }          
         };
-        match thread.m_stack.pop() {
+        jvm.exit_method_monitor()?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        match thread.pop_frame() {
             Some(_) => (),
             None => return Err(Error::FrameStackUnderflow(Opcode::DRETURN)),
         }
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(return_value);
-        Ok(())
+        Ok(Flow::Return)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -454,7 +477,7 @@ impl Instruction for AReturn {
             Ok(AReturn {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         // This needs a lot of love
         let return_value = {
@@ -492,35 +515,8 @@ impl Instruction for AReturn {
                         }
                     },
                     Reference::Object(o, _) => {
-                        let mut current_class = o.class().clone();
-                        let mut current_class_file = current_class.get_class_file();
-                        let mut found = false;
-                        // These nested loops are rough, they should be tested and probably refactored.
-                        while current_class_file.has_super() && !found {
-                            if current_class_file.name() == ret_class_name {
-                                found = true;
-                                break;
-                            }
-                            for interface_index in current_class_file.interfaces() {
-                                let interface = current_class_file.cp_entry(*interface_index)?.as_class()?;
-                                let mut current_interface = jvm.resolve_class_reference(current_class_file.cp_entry(*interface)?.as_utf8()?)?;
-                                let mut found_interface = false;
-                                while current_interface.get_class_file().has_super() {
-                                    if current_interface.get_class_file().name() == ret_class_name {
-                                        found_interface = true;
-                                        break;
-                                    }
-                                    current_interface = jvm.resolve_class_reference(current_interface.get_class_file().super_name().unwrap())?;
-                                }
-                                if found_interface {
-                                    found = true;
-                                    break;
-                                }
-                            }
-                            current_class = jvm.resolve_class_reference(current_class_file.super_name().unwrap())?;
-                            current_class_file = current_class.get_class_file();
-                        }
-                        if !found {
+                        let object_desc = format!("L{}", o.class().get_class_file().name());
+                        if ret_descriptor.as_bytes()[0] as char == '[' || !jvm.check_class(&object_desc, ret_class_name)? {
                             return Err(Error::IncompatibleReturnType(Opcode::ARETURN));
                         }
                     },
@@ -543,14 +539,15 @@ impl Instruction for AReturn {
             } 
             ret_val   
         };
+        jvm.exit_method_monitor()?;
         let thread = access_macros::current_thread_mut!(jvm);
-        match thread.m_stack.pop() {
+        match thread.pop_frame() {
             Some(_) => (),
             None => return Err(Error::FrameStackUnderflow(Opcode::ARETURN)),
         }
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(return_value);
-        Ok(())
+        Ok(Flow::Return)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -576,7 +573,7 @@ impl Instruction for Return {
             Ok(Return {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         {
             let frame = access_macros::current_frame_mut!(thread);
@@ -584,8 +581,10 @@ impl Instruction for Return {
                 return Err(Error::IncompatibleReturnType(Opcode::RETURN));
             }
         }
-        match thread.m_stack.pop() {
-            Some(_) => Ok(()),
+        jvm.exit_method_monitor()?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        match thread.pop_frame() {
+            Some(_) => Ok(Flow::Return),
             None => Err(Error::FrameStackUnderflow(Opcode::RETURN)),
         }   
     }
@@ -13,7 +13,7 @@ impl Instruction for I2L {
             Ok(I2L {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -22,7 +22,7 @@ impl Instruction for I2L {
         };
         let result = i64::from(*val.as_int()?);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -48,7 +48,7 @@ impl Instruction for I2F {
             Ok(I2F {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -57,7 +57,7 @@ impl Instruction for I2F {
         };
         let result = *val.as_int()? as f32;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -83,7 +83,7 @@ impl Instruction for I2D {
             Ok(I2D {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -92,7 +92,7 @@ impl Instruction for I2D {
         };
         let result = *val.as_int()? as f64;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -118,7 +118,7 @@ impl Instruction for L2I {
             Ok(L2I {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -127,7 +127,7 @@ impl Instruction for L2I {
         };
         let result = (val.as_long()? & 0xffffffff) as i32;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -153,7 +153,7 @@ impl Instruction for L2F {
             Ok(L2F {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -162,7 +162,7 @@ impl Instruction for L2F {
         };
         let result = *val.as_long()? as f32;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -188,7 +188,7 @@ impl Instruction for L2D {
             Ok(L2D {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -197,7 +197,7 @@ impl Instruction for L2D {
         };
         let result = *val.as_long()? as f64;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -225,7 +225,7 @@ impl Instruction for F2I {
             Ok(F2I {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -234,7 +234,7 @@ impl Instruction for F2I {
         };
         let result = *val.as_float()? as i32;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -260,7 +260,7 @@ impl Instruction for F2L {
             Ok(F2L {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -269,7 +269,7 @@ impl Instruction for F2L {
         };
         let result = *val.as_float()? as i64;
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -295,7 +295,7 @@ impl Instruction for F2D {
             Ok(F2D {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -304,7 +304,7 @@ impl Instruction for F2D {
         };
         let result = *val.as_float()? as f64;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -330,7 +330,7 @@ impl Instruction for D2I {
             Ok(D2I {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -339,7 +339,7 @@ impl Instruction for D2I {
         };
         let result = *val.as_double()? as i32;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -365,7 +365,7 @@ impl Instruction for D2L {
             Ok(D2L {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -374,7 +374,7 @@ impl Instruction for D2L {
         };
         let result = *val.as_double()? as i64;
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -400,7 +400,7 @@ impl Instruction for D2F {
             Ok(D2F {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -409,7 +409,7 @@ impl Instruction for D2F {
         };
         let result = *val.as_double()? as f32;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -435,7 +435,7 @@ impl Instruction for I2B {
             Ok(I2B {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -444,7 +444,7 @@ impl Instruction for I2B {
         };
         let result = ((val.as_int()? & 0xff) as i8) as i32;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -470,7 +470,7 @@ impl Instruction for I2C {
             Ok(I2C {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -479,7 +479,7 @@ impl Instruction for I2C {
         };
         let result = ((val.as_int()? & 0xffff) as u16) as i32;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -505,7 +505,7 @@ impl Instruction for I2S {
             Ok(I2S {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -514,7 +514,7 @@ impl Instruction for I2S {
         };
         let result = ((val.as_int()? & 0xffff) as i16) as i32;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1,4 +1,5 @@
 use crate::reference::array::Array;
+use crate::reference::object::Object;
 
 use super::*;
 use crate::compress_addr;
@@ -16,7 +17,7 @@ impl Instruction for Wide {
             Err(Error::Wide)
         }
     }
-    fn execute(&mut self, _jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, _jvm : &mut JVM) -> Result<Flow, Error> {
         Err(Error::Wide)
     }
     fn as_any(&self) -> &dyn Any {
@@ -51,24 +52,37 @@ impl Instruction for MultiANewArray {
             Ok(MultiANewArray { index, dimensions })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         if self.dimensions == 0 {
             return Err(Error::IllegalDimensionCount);
         }
         let len = frame.op_stack.len();
-        if len >= self.dimensions {
+        if len < self.dimensions {
             return Err(Error::NotEnoughDimensionValues);
         }
-        let counts = &frame.op_stack[(len - self.dimensions)..0];
+        let counts = frame.op_stack.split_off(len - self.dimensions);
+        let mut sizes = Vec::with_capacity(counts.len());
+        for count in &counts {
+            let count = *count.as_int()?;
+            if count < 0 {
+                return Err(Error::NegativeArraySizeException(Opcode::MULTIANEWARRAY));
+            }
+            sizes.push(count as usize);
+        }
         let array_class_desc_index = *frame.rt_const_pool.get_class_file().cp_entry(self.index)?.as_class()?;
         let array_class_desc = frame.rt_const_pool.get_class_file().cp_entry(array_class_desc_index)?.as_utf8()?.clone();
-        let array = Array::new_multi(self.dimensions as u8, counts, array_class_desc)?;
-        let array_ref = Reference::Array(Rc::new(array), Rc::new(Monitor::new()));
+        jvm.reserve_heap(Array::<dyn Class, dyn Object>::multi_heap_size(&sizes, &array_class_desc))?;
+        let array = Array::new_multi(self.dimensions as u8, &counts, array_class_desc)?;
+        let array = Rc::new(array);
+        jvm.heap.track_multi_array(&array);
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        let array_ref = Reference::Array(array, Rc::new(Monitor::new()));
         let array_ref_val = Value::Reference(array_ref);
         frame.op_stack.push(array_ref_val);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -100,7 +114,7 @@ impl Instruction for IfNull {
             Ok(IfNull {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let value = match frame.op_stack.pop() {
@@ -109,8 +123,9 @@ impl Instruction for IfNull {
         };
         if let Reference::Null = value {
             thread.inc_pc(self.offset)?;
+            return Ok(Flow::Jump);
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     compress_addr!(offset);
     fn as_any(&self) -> &dyn Any {
@@ -143,7 +158,7 @@ impl Instruction for IfNonNull {
             Ok(IfNonNull {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let value = match frame.op_stack.pop() {
@@ -152,8 +167,9 @@ impl Instruction for IfNonNull {
         };
         if !matches!(value, Reference::Null) {
             thread.inc_pc(self.offset)?;
+            return Ok(Flow::Jump);
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     compress_addr!(offset);
     fn as_any(&self) -> &dyn Any {
@@ -180,16 +196,16 @@ impl Instruction for GotoW {
             Err(Error::IllegalWide)
         } else {
             let offset = unsafe {
-                isize::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap())
+                i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap()) as isize
             };
             v.remove(0); v.remove(0); v.remove(0); v.remove(0);
             Ok(GotoW {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         thread.inc_pc(self.offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
@@ -216,19 +232,20 @@ impl Instruction for JsrW {
             Err(Error::IllegalWide)
         } else {
             let offset = unsafe {
-                isize::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap())
+                i32::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 4).try_into().unwrap()) as isize
             };
             v.remove(0); v.remove(0); v.remove(0); v.remove(0);
             Ok(JsrW {offset})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
-        let pc = thread.pc() as u16;
+        // ret comes back to the instruction after this one.
+        let pc = thread.pc() as u16 + 1;
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::ReturnAddress(pc));
         thread.inc_pc(self.offset)?;
-        Ok(())
+        Ok(Flow::Jump)
     }
     compress_addr!{offset}
     fn as_any(&self) -> &dyn Any {
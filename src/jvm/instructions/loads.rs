@@ -22,12 +22,12 @@ impl Instruction for ILoad {
         }
         Ok(ILoad { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[self.idx];
         frame.op_stack.push(Value::Int(*var.as_int()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -62,12 +62,12 @@ impl Instruction for LLoad {
         }
         Ok(LLoad { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[self.idx];
         frame.op_stack.push(Value::Long(*var.as_long()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -102,12 +102,12 @@ impl Instruction for FLoad {
         }
         Ok(FLoad { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[self.idx];
         frame.op_stack.push(Value::Float(*var.as_float()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -142,12 +142,12 @@ impl Instruction for DLoad {
         }
         Ok(DLoad { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[self.idx];
         frame.op_stack.push(Value::Double(*var.as_double()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -182,12 +182,12 @@ impl Instruction for ALoad {
         }
         Ok(ALoad { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[self.idx];
         frame.op_stack.push(Value::Reference(var.as_reference()?.clone()));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -213,12 +213,12 @@ impl Instruction for ILoad0 {
             Ok(ILoad0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[0];
         frame.op_stack.push(Value::Int(*var.as_int()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -244,12 +244,12 @@ impl Instruction for ILoad1 {
             Ok(ILoad1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[1];
         frame.op_stack.push(Value::Int(*var.as_int()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -296,12 +296,12 @@ impl Instruction for ILoad2 {
             Ok(ILoad2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[2];
         frame.op_stack.push(Value::Int(*var.as_int()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -327,12 +327,12 @@ impl Instruction for ILoad3 {
             Ok(ILoad3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[3];
         frame.op_stack.push(Value::Int(*var.as_int()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -358,12 +358,12 @@ impl Instruction for LLoad0 {
             Ok(LLoad0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[0];
         frame.op_stack.push(Value::Long(*var.as_long()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -389,12 +389,12 @@ impl Instruction for LLoad1 {
             Ok(LLoad1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[1];
         frame.op_stack.push(Value::Long(*var.as_long()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -420,12 +420,12 @@ impl Instruction for LLoad2 {
             Ok(LLoad2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[2];
         frame.op_stack.push(Value::Long(*var.as_long()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -451,12 +451,12 @@ impl Instruction for LLoad3 {
             Ok(LLoad3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[3];
         frame.op_stack.push(Value::Long(*var.as_long()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -482,12 +482,12 @@ impl Instruction for FLoad0 {
             Ok(FLoad0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[0];
         frame.op_stack.push(Value::Float(*var.as_float()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -513,12 +513,12 @@ impl Instruction for FLoad1 {
             Ok(FLoad1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[1];
         frame.op_stack.push(Value::Float(*var.as_float()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -544,12 +544,12 @@ impl Instruction for FLoad2 {
             Ok(FLoad2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[2];
         frame.op_stack.push(Value::Float(*var.as_float()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -575,12 +575,12 @@ impl Instruction for FLoad3 {
             Ok(FLoad3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[3];
         frame.op_stack.push(Value::Float(*var.as_float()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -606,12 +606,12 @@ impl Instruction for DLoad0 {
             Ok(DLoad0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[0];
         frame.op_stack.push(Value::Double(*var.as_double()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -637,12 +637,12 @@ impl Instruction for DLoad1 {
             Ok(DLoad1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[1];
         frame.op_stack.push(Value::Double(*var.as_double()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -668,12 +668,12 @@ impl Instruction for DLoad2 {
             Ok(DLoad2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[2];
         frame.op_stack.push(Value::Double(*var.as_double()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -699,12 +699,12 @@ impl Instruction for DLoad3 {
             Ok(DLoad3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[3];
         frame.op_stack.push(Value::Double(*var.as_double()?));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -730,12 +730,12 @@ impl Instruction for ALoad0 {
             Ok(ALoad0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[0];
         frame.op_stack.push(Value::Reference(var.as_reference()?.clone()));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -761,12 +761,12 @@ impl Instruction for ALoad1 {
             Ok(ALoad1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[1];
         frame.op_stack.push(Value::Reference(var.as_reference()?.clone()));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -792,12 +792,12 @@ impl Instruction for ALoad2 {
             Ok(ALoad2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[2];
         frame.op_stack.push(Value::Reference(var.as_reference()?.clone()));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -823,12 +823,12 @@ impl Instruction for ALoad3 {
             Ok(ALoad3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let var = &frame.local_variables[3];
         frame.op_stack.push(Value::Reference(var.as_reference()?.clone()));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -854,7 +854,7 @@ impl Instruction for IALoad {
             Ok(IALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -875,7 +875,7 @@ impl Instruction for IALoad {
             return Err(Error::IncorrectReferenceType(Opcode::IALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -901,7 +901,7 @@ impl Instruction for LALoad {
             Ok(LALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -922,7 +922,7 @@ impl Instruction for LALoad {
             return Err(Error::IncorrectReferenceType(Opcode::LALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -948,7 +948,7 @@ impl Instruction for FALoad {
             Ok(FALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -969,7 +969,7 @@ impl Instruction for FALoad {
             return Err(Error::IncorrectReferenceType(Opcode::FALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -995,7 +995,7 @@ impl Instruction for DALoad {
             Ok(DALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -1016,7 +1016,7 @@ impl Instruction for DALoad {
             return Err(Error::IncorrectReferenceType(Opcode::DALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1042,7 +1042,7 @@ impl Instruction for AALoad {
             Ok(AALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -1063,7 +1063,7 @@ impl Instruction for AALoad {
             return Err(Error::IncorrectReferenceType(Opcode::AALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1089,7 +1089,7 @@ impl Instruction for BALoad {
             Ok(BALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -1106,11 +1106,11 @@ impl Instruction for BALoad {
             Reference::Array(arr, _) => arr,
             _ => return Err(Error::UnexpectedTypeOnStack(Opcode::BALOAD)),
         };
-        if !arr.is_barray() | !arr.is_boolarray() {
+        if !arr.is_barray() && !arr.is_boolarray() {
             return Err(Error::IncorrectReferenceType(Opcode::BALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1136,7 +1136,7 @@ impl Instruction for CALoad {
             Ok(CALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -1157,7 +1157,7 @@ impl Instruction for CALoad {
             return Err(Error::IncorrectReferenceType(Opcode::CALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1183,7 +1183,7 @@ impl Instruction for SALoad {
             Ok(SALoad {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let index_val = match frame.op_stack.pop() {
@@ -1204,7 +1204,7 @@ impl Instruction for SALoad {
             return Err(Error::IncorrectReferenceType(Opcode::SALOAD));
         }
         frame.op_stack.push(arr.get(*index as usize));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -13,7 +13,7 @@ impl Instruction for IAdd {
             Ok(IAdd {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -26,7 +26,7 @@ impl Instruction for IAdd {
         };
         let (result, _) = val1.as_int()?.overflowing_add(*val2.as_int()?);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -52,7 +52,7 @@ impl Instruction for LAdd {
             Ok(LAdd {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -65,7 +65,7 @@ impl Instruction for LAdd {
         };
         let (result, _) = val1.as_long()?.overflowing_add(*val2.as_long()?);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -91,7 +91,7 @@ impl Instruction for FAdd {
             Ok(FAdd {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -104,7 +104,7 @@ impl Instruction for FAdd {
         };
         let result = val1.as_float()? + val2.as_float()?;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -130,7 +130,7 @@ impl Instruction for DAdd {
             Ok(DAdd {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -143,7 +143,7 @@ impl Instruction for DAdd {
         };
         let result = val1.as_double()? + val2.as_double()?;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -169,7 +169,7 @@ impl Instruction for ISub {
             Ok(ISub {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -182,7 +182,7 @@ impl Instruction for ISub {
         };
         let (result, _) = val1.as_int()?.overflowing_sub(*val2.as_int()?);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -208,7 +208,7 @@ impl Instruction for LSub {
             Ok(LSub {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -221,7 +221,7 @@ impl Instruction for LSub {
         };
         let (result, _) = val1.as_long()?.overflowing_sub(*val2.as_long()?);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -247,7 +247,7 @@ impl Instruction for FSub {
             Ok(FSub {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -260,7 +260,7 @@ impl Instruction for FSub {
         };
         let result = val1.as_float()? - val2.as_float()?;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -286,7 +286,7 @@ impl Instruction for DSub {
             Ok(DSub {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -299,7 +299,7 @@ impl Instruction for DSub {
         };
         let result = val1.as_double()? - val2.as_double()?;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -325,7 +325,7 @@ impl Instruction for IMul {
             Ok(IMul {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -338,7 +338,7 @@ impl Instruction for IMul {
         };
         let (result, _) = val1.as_int()?.overflowing_mul(*val2.as_int()?);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -364,7 +364,7 @@ impl Instruction for LMul {
             Ok(LMul {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -377,7 +377,7 @@ impl Instruction for LMul {
         };
         let (result, _) = val1.as_long()?.overflowing_mul(*val2.as_long()?);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -403,7 +403,7 @@ impl Instruction for FMul {
             Ok(FMul {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -416,7 +416,7 @@ impl Instruction for FMul {
         };
         let result = val1.as_float()? * val2.as_float()?;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -442,7 +442,7 @@ impl Instruction for DMul {
             Ok(DMul {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -455,7 +455,7 @@ impl Instruction for DMul {
         };
         let result = val1.as_double()? * val2.as_double()?;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -481,7 +481,7 @@ impl Instruction for IDiv {
             Ok(IDiv {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -493,11 +493,11 @@ impl Instruction for IDiv {
             None => return Err(Error::StackUnderflow(Opcode::IDIV)),
         };
         if *val2.as_int()? == 0 {
-            return Err(Error::ArithmeticException(Opcode::IDIV));
+            return jvm.throw_exception_with_message("java/lang/ArithmeticException", String::from("/ by zero"));
         }
         let (result, _) = val1.as_int()?.overflowing_div(*val2.as_int()?);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -523,7 +523,7 @@ impl Instruction for LDiv {
             Ok(LDiv {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -535,11 +535,11 @@ impl Instruction for LDiv {
             None => return Err(Error::StackUnderflow(Opcode::LDIV)),
         };
         if *val2.as_long()? == 0 {
-            return Err(Error::ArithmeticException(Opcode::IDIV));
+            return jvm.throw_exception_with_message("java/lang/ArithmeticException", String::from("/ by zero"));
         }
         let (result, _) = val1.as_long()?.overflowing_div(*val2.as_long()?);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -565,7 +565,7 @@ impl Instruction for FDiv {
             Ok(FDiv {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -578,7 +578,7 @@ impl Instruction for FDiv {
         };
         let result = val1.as_float()? / val2.as_float()?;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -604,7 +604,7 @@ impl Instruction for DDiv {
             Ok(DDiv {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -617,7 +617,7 @@ impl Instruction for DDiv {
         };
         let result = val1.as_double()? / val2.as_double()?;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -643,7 +643,7 @@ impl Instruction for IRem {
             Ok(IRem {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -655,11 +655,11 @@ impl Instruction for IRem {
             None => return Err(Error::StackUnderflow(Opcode::IREM)),
         };
         if *val2.as_int()? == 0 {
-            return Err(Error::ArithmeticException(Opcode::IREM));
+            return jvm.throw_exception_with_message("java/lang/ArithmeticException", String::from("/ by zero"));
         }
         let (result, _) = val1.as_int()?.overflowing_rem(*val2.as_int()?);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -685,7 +685,7 @@ impl Instruction for LRem {
             Ok(LRem {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -697,11 +697,11 @@ impl Instruction for LRem {
             None => return Err(Error::StackUnderflow(Opcode::LREM)),
         };
         if *val2.as_long()? == 0 {
-            return Err(Error::ArithmeticException(Opcode::IREM));
+            return jvm.throw_exception_with_message("java/lang/ArithmeticException", String::from("/ by zero"));
         }
         let (result, _) = val1.as_long()?.overflowing_rem(*val2.as_long()?);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -727,7 +727,7 @@ impl Instruction for FRem {
             Ok(FRem {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -740,7 +740,7 @@ impl Instruction for FRem {
         };
         let result = val1.as_float()? % val2.as_float()?;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -766,7 +766,7 @@ impl Instruction for DRem {
             Ok(DRem {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -779,7 +779,7 @@ impl Instruction for DRem {
         };
         let result = val1.as_double()? % val2.as_double()?;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -805,7 +805,7 @@ impl Instruction for INeg {
             Ok(INeg {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -814,7 +814,7 @@ impl Instruction for INeg {
         };
         let (result, _) = val.as_int()?.overflowing_neg();
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -840,7 +840,7 @@ impl Instruction for LNeg {
             Ok(LNeg {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -849,7 +849,7 @@ impl Instruction for LNeg {
         };
         let (result, _) = val.as_long()?.overflowing_neg();
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -875,7 +875,7 @@ impl Instruction for FNeg {
             Ok(FNeg {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -884,7 +884,7 @@ impl Instruction for FNeg {
         };
         let result = val.as_float()? * -1.0;
         frame.op_stack.push(Value::Float(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -910,7 +910,7 @@ impl Instruction for DNeg {
             Ok(DNeg {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -919,7 +919,7 @@ impl Instruction for DNeg {
         };
         let result = val.as_double()? * -1.0;
         frame.op_stack.push(Value::Double(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -945,7 +945,7 @@ impl Instruction for IShl {
             Ok(IShl {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -958,7 +958,7 @@ impl Instruction for IShl {
         };
         let result = val1.as_int()? << (val2.as_int()? & 0x1f);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -984,7 +984,7 @@ impl Instruction for LShl {
             Ok(LShl {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -997,7 +997,7 @@ impl Instruction for LShl {
         };
         let result = val1.as_long()? << (val2.as_int()? & 0x3f);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1023,7 +1023,7 @@ impl Instruction for IShr {
             Ok(IShr {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1036,7 +1036,7 @@ impl Instruction for IShr {
         };
         let result = val1.as_int()? >> (val2.as_int()? & 0x1f);
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1062,7 +1062,7 @@ impl Instruction for LShr {
             Ok(LShr {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1075,7 +1075,7 @@ impl Instruction for LShr {
         };
         let result = val1.as_long()? >> (val2.as_int()? & 0x3f);
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1101,7 +1101,7 @@ impl Instruction for IUshr {
             Ok(IUshr {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1119,7 +1119,7 @@ impl Instruction for IUshr {
             result += 2 << !(val2.as_int()? & 0x1f);
         }
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1145,7 +1145,7 @@ impl Instruction for LUshr {
             Ok(LUshr {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1163,7 +1163,7 @@ impl Instruction for LUshr {
             result += 2 << !(val2.as_int()? & 0x3f);
         }
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1189,7 +1189,7 @@ impl Instruction for IAnd {
             Ok(IAnd {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1200,9 +1200,9 @@ impl Instruction for IAnd {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::IAND)),
         };
-        let result = val1.as_int()? & (val2.as_int()? & 0x1f);
+        let result = val1.as_int()? & val2.as_int()?;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1228,7 +1228,7 @@ impl Instruction for LAnd {
             Ok(LAnd {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1239,9 +1239,9 @@ impl Instruction for LAnd {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::LAND)),
         };
-        let result = val1.as_long()? & (val2.as_long()? & 0x3f);
+        let result = val1.as_long()? & val2.as_long()?;
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1267,7 +1267,7 @@ impl Instruction for IOr {
             Ok(IOr {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1278,9 +1278,9 @@ impl Instruction for IOr {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::IOR)),
         };
-        let result = val1.as_int()? | (val2.as_int()? & 0x1f);
+        let result = val1.as_int()? | val2.as_int()?;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1306,7 +1306,7 @@ impl Instruction for LOr {
             Ok(LOr {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1317,9 +1317,9 @@ impl Instruction for LOr {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::LOR)),
         };
-        let result = val1.as_long()? | (val2.as_long()? & 0x3f);
+        let result = val1.as_long()? | val2.as_long()?;
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1345,7 +1345,7 @@ impl Instruction for IXor {
             Ok(IXor {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1356,9 +1356,9 @@ impl Instruction for IXor {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::IXOR)),
         };
-        let result = val1.as_int()? ^ (val2.as_int()? & 0x1f);
+        let result = val1.as_int()? ^ val2.as_int()?;
         frame.op_stack.push(Value::Int(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1384,7 +1384,7 @@ impl Instruction for LXor {
             Ok(LXor {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val2 = match frame.op_stack.pop() {
@@ -1395,9 +1395,9 @@ impl Instruction for LXor {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::LXOR)),
         };
-        let result = val1.as_long()? ^ (val2.as_long()? & 0x3f);
+        let result = val1.as_long()? ^ val2.as_long()?;
         frame.op_stack.push(Value::Long(result));
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1420,23 +1420,26 @@ impl Instruction for IInc {
         "iinc"
     }
     fn new(v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        // The constant is signed, and wide makes both it and the index 2 bytes.
         if was_wide {
-            Err(Error::IllegalWide)
+            let index = u16::from_be_bytes([v[0], v[1]]) as usize;
+            let const_incr = i16::from_be_bytes([v[2], v[3]]) as i32;
+            v.drain(..4);
+            Ok(IInc {index, const_incr})
         } else {
             let index = v[0] as usize;
-            let const_incr = v[1] as i32;
+            let const_incr = v[1] as i8 as i32;
             v.remove(0);
             v.remove(0);
             Ok(IInc {index, const_incr})
         } 
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        let var = &mut frame.local_variables[self.index];
-        // Confirm that this updates the value.
-        *var.as_int_mut()? += self.const_incr;
-        Ok(())
+        let var = frame.local_variables[self.index].as_int_mut()?;
+        (*var, _) = var.overflowing_add(self.const_incr);
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
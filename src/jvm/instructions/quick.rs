@@ -0,0 +1,356 @@
+// The "quick" instructions. Nothing in a class file uses these, instead the instruction that resolves something
+// gives back one of these from quicken() once it's done, and step1 puts it in its place. After that, every run skips
+// straight to using what was resolved. This is the same idea as the _quick opcodes from the first edition of the JVM spec.
+use crate::{class::rt_const_pool::{FieldRef, MethodRef}, jvm::{VirtualCall, invoke::CallSite}, reference::object::{self, Object}};
+
+use super::*;
+use super::references::{invoke_interface, invoke_virtual};
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GetStaticQuick {
+    pub field: Rc<FieldRef>,
+}
+impl Instruction for GetStaticQuick {
+    fn name(&self) -> &'static str {
+        "getstatic_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let value = self.field.class.get_static(&self.field.name_and_type.name, &self.field.name_and_type.descriptor, jvm)?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.push(value);
+        Ok(Flow::Next)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PutStaticQuick {
+    pub field: Rc<FieldRef>,
+}
+impl Instruction for PutStaticQuick {
+    fn name(&self) -> &'static str {
+        "putstatic_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        let value = match frame.op_stack.pop() {
+            Some(v) => v,
+            None => return Err(Error::StackUnderflow(Opcode::PUTSTATIC)),
+        };
+        let mut class = Rc::clone(&self.field.class);
+        unsafe { Rc::get_mut_unchecked(&mut class).put_static(&self.field.name_and_type.name, &self.field.name_and_type.descriptor, value, jvm)?; }
+        Ok(Flow::Next)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct GetFieldQuick {
+    pub field: Rc<FieldRef>,
+}
+impl Instruction for GetFieldQuick {
+    fn name(&self) -> &'static str {
+        "getfield_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        let object = match frame.op_stack.pop() {
+            Some(v) => match v.as_reference()? {
+                Reference::Object(o, _) => o,
+                Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
+                _ => return Err(Error::IncorrectReferenceType(Opcode::GETFIELD)),
+            },
+            None => return Err(Error::StackUnderflow(Opcode::GETFIELD)),
+        };
+        let value = object.get_field(&self.field, jvm)?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.push(value);
+        Ok(Flow::Next)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct PutFieldQuick {
+    pub field: Rc<FieldRef>,
+}
+impl Instruction for PutFieldQuick {
+    fn name(&self) -> &'static str {
+        "putfield_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        let value = match frame.op_stack.pop() {
+            Some(v) => v,
+            None => return Err(Error::StackUnderflow(Opcode::PUTFIELD)),
+        };
+        let mut object = match frame.op_stack.pop() {
+            Some(v) => match v.as_reference()? {
+                Reference::Object(o, _) => o,
+                Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
+                _ => return Err(Error::IncorrectReferenceType(Opcode::PUTFIELD)),
+            },
+            None => return Err(Error::StackUnderflow(Opcode::PUTFIELD)),
+        };
+        unsafe { Rc::get_mut_unchecked(&mut object) }.put_field(&self.field, jvm, value)?;
+        Ok(Flow::Next)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvokeVirtualQuick {
+    pub call: Rc<VirtualCall>,
+}
+impl Instruction for InvokeVirtualQuick {
+    fn name(&self) -> &'static str {
+        "invokevirtual_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        invoke_virtual(jvm, &self.call)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvokeSpecialQuick {
+    pub method: Rc<MethodRef>,
+}
+impl Instruction for InvokeSpecialQuick {
+    fn name(&self) -> &'static str {
+        "invokespecial_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let class_file = self.method.class.get_class_file();
+        let method = &class_file.methods[self.method.index];
+        jvm.invoke_method(method, &self.method.class, |jvm| jvm.execute_on_object(method, Rc::clone(&self.method.class)))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvokeStaticQuick {
+    pub method: Rc<MethodRef>,
+}
+impl Instruction for InvokeStaticQuick {
+    fn name(&self) -> &'static str {
+        "invokestatic_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let class_file = self.method.class.get_class_file();
+        let method = &class_file.methods[self.method.index];
+        jvm.invoke_method(method, &self.method.class, |jvm| Rc::clone(&self.method.class).exec_method(jvm, method).map(|_was_native| ()))
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct InvokeInterfaceQuick {
+    // The method the InterfaceMethodref resolved to, which we need for the interface it named.
+    pub method: Rc<MethodRef>,
+    pub call: Rc<VirtualCall>,
+}
+impl Instruction for InvokeInterfaceQuick {
+    fn name(&self) -> &'static str {
+        "invokeinterface_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        invoke_interface(jvm, &self.method.symbolic_class, &self.call)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct InvokeDynamicQuick {
+    pub call_site: Rc<CallSite>,
+}
+impl PartialEq for InvokeDynamicQuick {
+    fn eq(&self, other: &Self) -> bool {
+        Rc::ptr_eq(&self.call_site, &other.call_site)
+    }
+}
+impl Instruction for InvokeDynamicQuick {
+    fn name(&self) -> &'static str {
+        "invokedynamic_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        self.call_site.invoke(jvm)?;
+        Ok(Flow::Invoke)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct NewQuick {
+    pub class: Rc<dyn Class>,
+}
+impl PartialEq for NewQuick {
+    fn eq(&self, other: &Self) -> bool {
+        crate::class::customclass::same_class(&self.class, &other.class)
+    }
+}
+impl std::fmt::Debug for NewQuick {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "NewQuick {{ class: {} }}", self.class.get_class_file().name())
+    }
+}
+impl Instruction for NewQuick {
+    fn name(&self) -> &'static str {
+        "new_quick"
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let size = object::heap_size_of(&self.class, jvm)?;
+        jvm.reserve_heap(size)?;
+        let object = object::new_object_of_class(&self.class, jvm)?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.push(Value::Reference(Reference::Object(object, Rc::new(Monitor::new()))));
+        Ok(Flow::Next)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
+
+// ldc, ldc_w, and ldc2_w of anything that had to be resolved, like a Class or a MethodType.
+#[derive(Debug, PartialEq, Clone)]
+pub struct LdcQuick {
+    pub name: &'static str,
+    pub value: Value<dyn Class, dyn Object>,
+}
+impl Instruction for LdcQuick {
+    fn name(&self) -> &'static str {
+        self.name
+    }
+    fn new(_v: &mut Vec<u8>, _cpool: &Vec<Entry>, _jvm: &mut JVM, _was_wide: bool, _true_pc: usize) -> Result<Self, Error> where Self : Sized {
+        panic!("Quick instructions only come from quickening")
+    }
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.push(self.value.clone());
+        Ok(Flow::Next)
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn eq(&self, other: &dyn Instruction) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self == other,
+        }
+    }
+}
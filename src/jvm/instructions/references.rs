@@ -1,10 +1,12 @@
-use crate::{flags, reference::{array::Array, object}};
+use crate::{flags, class::{customclass::CustomClass, rt_const_pool::{FieldRef, MethodRef}}, jvm::{VirtualCall, invoke::CallSite}, reference::{array::{Array, atype}, object}};
 
 use super::*;
+use super::quick::*;
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct GetStatic {
     index: u16,
+    field: Option<Rc<FieldRef>>,
 }
 impl Instruction for GetStatic {
     fn name(&self) -> &'static str {
@@ -18,27 +20,30 @@ impl Instruction for GetStatic {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(GetStatic { index })
+            Ok(GetStatic { index, field: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let current_class = Rc::clone(&frame.rt_const_pool);
-        let current_class_file = current_class.get_class_file();
-        let field = current_class_file.cp_entry(self.index)?.as_field_ref()?;
-        let class_index = current_class_file.cp_entry(field.class_index)?.as_class()?;
-        let class_name = current_class_file.cp_entry(*class_index)?.as_utf8()?;
-        let class = jvm.resolve_class_reference(class_name)?;
-        let name_and_type = current_class_file.cp_entry(field.name_and_type_index)?.as_name_and_type()?;
-        let name = current_class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
-        let descriptor = current_class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
-        let field = class.get_static(name, descriptor, jvm)?;
-        let new_value = field.clone();
+        let field = jvm.resolve_field_ref(&current_class, self.index, Opcode::GETSTATIC)?;
+        if !field.is_static {
+            return Err(Error::IncompatibleClassChangeError(Opcode::GETSTATIC));
+        }
+        jvm.initialize_class(Rc::clone(&field.class))?;
+        // Only once the class is done initializing, since the quick version doesn't check.
+        if jvm.is_initialized(&field.class) {
+            self.field = Some(Rc::clone(&field));
+        }
+        let new_value = field.class.get_static(&field.name_and_type.name, &field.name_and_type.descriptor, jvm)?;
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(new_value);
-        Ok(())
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.field.as_ref().map(|field| GetStaticQuick { field: Rc::clone(field) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -54,6 +59,7 @@ impl Instruction for GetStatic {
 #[derive(Debug, PartialEq, Clone)]
 pub struct PutStatic {
     index: u16,
+    field: Option<Rc<FieldRef>>,
 }
 impl Instruction for PutStatic {
     fn name(&self) -> &'static str {
@@ -67,31 +73,33 @@ impl Instruction for PutStatic {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(PutStatic { index })
+            Ok(PutStatic { index, field: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let current_class = Rc::clone(&frame.rt_const_pool);
-        let current_class_file = current_class.get_class_file();
-        let field = current_class_file.cp_entry(self.index)?.as_field_ref()?;
-        let class_index = current_class_file.cp_entry(field.class_index)?.as_class()?;
-        let class_name = current_class_file.cp_entry(*class_index)?.as_utf8()?;
-        let mut class = jvm.resolve_class_reference(class_name)?;
-        let name_and_type = current_class_file.cp_entry(field.name_and_type_index)?.as_name_and_type()?;
-        let name = current_class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
-        let descriptor = current_class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
+        let field = jvm.resolve_field_ref(&current_class, self.index, Opcode::PUTSTATIC)?;
+        if !field.is_static {
+            return Err(Error::IncompatibleClassChangeError(Opcode::PUTSTATIC));
+        }
+        let mut class = Rc::clone(&field.class);
+        jvm.initialize_class(Rc::clone(&class))?;
+        if jvm.is_initialized(&class) {
+            self.field = Some(Rc::clone(&field));
+        }
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        let field = match frame.op_stack.pop() {
+        let value = match frame.op_stack.pop() {
             Some(v) => v,
             None => return Err(Error::StackUnderflow(Opcode::PUTSTATIC)),
         };
-        //println!("{class_name}");
-        //todo!();
-        unsafe {Rc::get_mut_unchecked(&mut class).put_static(name, descriptor, field, jvm)?; }
-        Ok(())
+        unsafe {Rc::get_mut_unchecked(&mut class).put_static(&field.name_and_type.name, &field.name_and_type.descriptor, value, jvm)?; }
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.field.as_ref().map(|field| PutStaticQuick { field: Rc::clone(field) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -107,6 +115,7 @@ impl Instruction for PutStatic {
 #[derive(Debug, PartialEq, Clone)]
 pub struct GetField {
     index: u16,
+    field: Option<Rc<FieldRef>>,
 }
 impl Instruction for GetField {
     fn name(&self) -> &'static str {
@@ -120,10 +129,10 @@ impl Instruction for GetField {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(GetField { index })
+            Ok(GetField { index, field: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let object_ref = match frame.op_stack.pop() {
@@ -131,17 +140,26 @@ impl Instruction for GetField {
             None => return Err(Error::StackUnderflow(Opcode::GETFIELD)),
         };
         let current_class = frame.rt_const_pool.clone();
+        let field = jvm.resolve_field_ref(&current_class, self.index, Opcode::GETFIELD)?;
+        if field.is_static {
+            return Err(Error::IncompatibleClassChangeError(Opcode::GETFIELD));
+        }
+        self.field = Some(Rc::clone(&field));
         let val = {
             let object = match object_ref {
                 Reference::Object(o, _) => o,
+                Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
                 _ => return Err(Error::IncorrectReferenceType(Opcode::GETFIELD)),
             };
-            object.get_field(current_class, self.index, jvm)?
+            object.get_field(&field, jvm)?
         };
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(val);
-        Ok(())
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.field.as_ref().map(|field| GetFieldQuick { field: Rc::clone(field) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -157,6 +175,7 @@ impl Instruction for GetField {
 #[derive(Debug, PartialEq, Clone)]
 pub struct PutField {
     index: u16,
+    field: Option<Rc<FieldRef>>,
 }
 impl Instruction for PutField {
     fn name(&self) -> &'static str {
@@ -170,10 +189,10 @@ impl Instruction for PutField {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(PutField { index })
+            Ok(PutField { index, field: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -186,11 +205,21 @@ impl Instruction for PutField {
         };
         let mut object = match object_ref {
             Reference::Object(o, _) => o,
+            Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
             _ => return Err(Error::IncorrectReferenceType(Opcode::PUTFIELD)),
         };
+        let current_class = frame.rt_const_pool.clone();
+        let field = jvm.resolve_field_ref(&current_class, self.index, Opcode::PUTFIELD)?;
+        if field.is_static {
+            return Err(Error::IncompatibleClassChangeError(Opcode::PUTFIELD));
+        }
+        self.field = Some(Rc::clone(&field));
         unsafe {Rc::get_mut_unchecked(&mut object)}
-        .put_field(frame.rt_const_pool.clone(), self.index, jvm, val)?;
-        Ok(())
+        .put_field(&field, jvm, val)?;
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.field.as_ref().map(|field| PutFieldQuick { field: Rc::clone(field) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -203,9 +232,66 @@ impl Instruction for PutField {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+// The class of the object a method is being invoked on, which is below the arguments on the stack.
+fn receiver_class(jvm: &mut JVM, num_args: usize, opcode: Opcode) -> Result<Rc<dyn Class>, Error> {
+    let thread = access_macros::current_thread_mut!(jvm);
+    let frame = access_macros::current_frame_mut!(thread);
+    if frame.op_stack.len() < num_args + 1 {
+        return Err(Error::StackUnderflow(opcode));
+    }
+    match frame.op_stack[frame.op_stack.len() - num_args - 1].as_reference()? {
+        Reference::Object(o, _) => Ok(o.class()),
+        Reference::Null => Err(Error::NullPointerException(opcode)),
+        // Arrays only have the methods of Object.
+        Reference::Array(..) => jvm.resolve_class_reference("java/lang/Object"),
+        _ => Err(Error::IncorrectReferenceType(opcode)),
+    }
+}
+
+// Everything invokevirtual does once the method is resolved, which invokevirtual_quick shares.
+pub fn invoke_virtual(jvm: &mut JVM, call: &VirtualCall) -> Result<Flow, Error> {
+    let receiver_class = receiver_class(jvm, call.num_args, Opcode::INVOKEVIRTUAL)?;
+    let (class, index) = jvm.select_virtual(call, receiver_class, Opcode::INVOKEVIRTUAL)?;
+    let class_file = class.get_class_file();
+    let method = &class_file.methods[index];
+    if (method.access_flags.flags & flags::method::ACC_ABSTRACT) > 0 {
+        return Err(Error::AbstractMethodError(Opcode::INVOKEVIRTUAL));
+    } 
+    jvm.invoke_method(method, &class, |jvm| jvm.execute_on_object(method, Rc::clone(&class)))
+}
+
+// Same as invoke_virtual, but the receiver also has to implement the interface the InterfaceMethodref named.
+pub fn invoke_interface(jvm: &mut JVM, interface: &Rc<dyn Class>, call: &VirtualCall) -> Result<Flow, Error> {
+    let receiver_class = receiver_class(jvm, call.num_args, Opcode::INVOKEINTERFACE)?;
+    // Subclasses of native classes don't know about the interfaces they inherit, so they have to be searched too.
+    let implements = match receiver_class.as_any().downcast_ref::<CustomClass>().filter(|c| c.has_tables()) {
+        Some(c) => c.implements(interface),
+        None => jvm.implements(Rc::clone(&receiver_class), interface.get_class_file().name())?,
+    };
+    if !implements {
+        return Err(Error::IncompatibleClassChangeError(Opcode::INVOKEINTERFACE));
+    }
+    let (class, index) = jvm.select_virtual(call, receiver_class, Opcode::INVOKEINTERFACE)?;
+    let class_file = class.get_class_file();
+    let method = &class_file.methods[index];
+    if (method.access_flags.flags & flags::method::ACC_ABSTRACT) > 0 {
+        return Err(Error::AbstractMethodError(Opcode::INVOKEINTERFACE));
+    }
+    if (method.access_flags.flags & (flags::method::ACC_PUBLIC | flags::method::ACC_PRIVATE)) == 0 {
+        return Err(Error::IllegalAccessError(Opcode::INVOKEINTERFACE));
+    }
+    jvm.invoke_method(method, &class, |jvm| jvm.execute_on_object(method, Rc::clone(&class)))
+}
+
+#[derive(Debug, Clone)]
 pub struct InvokeVirtual {
     index: u16,
+    call: Option<Rc<VirtualCall>>,
+}
+impl PartialEq for InvokeVirtual {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
 }
 impl Instruction for InvokeVirtual {
     fn name(&self) -> &'static str {
@@ -219,104 +305,25 @@ impl Instruction for InvokeVirtual {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(InvokeVirtual { index })
+            Ok(InvokeVirtual { index, call: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-        // https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-6.html#jvms-6.5.invokevirtual
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        // https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.invokevirtual
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let current_class = Rc::clone(&frame.rt_const_pool);
-        let current_class_file = current_class.get_class_file();
-        let (mut c, name, descriptor) = {  
-            let method_ref = current_class_file.cp_entry(self.index)?.as_method_ref()?;
-            let name_and_type = current_class_file.cp_entry(method_ref.name_and_type_index)?.as_name_and_type()?;
-            let name = current_class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
-            let descriptor = current_class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
-            let c_info = current_class_file.cp_entry(method_ref.class_index)?.as_class()?;
-            let c_name = current_class_file.cp_entry(*c_info)?.as_utf8()?;
-            (jvm.resolve_class_reference(c_name.clone().as_str())?, name, descriptor)
-        };
-        let c_file = c.get_class_file();
-        //println!("Got {}.{}{}", c_file.name(), name, descriptor);
-        let mut method_to_call = None; 
-        // Resolve method
-        {
-            let mut found = false;
-            'super_loop: while !found {
-                for method in c_file.methods() {
-                    // https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.3
-                    // We still need to check for signature polymorphic functions.
-                    let method_descriptor = c_file.cp_entry(method.descriptor_index)?.as_utf8()?;
-                    if method_descriptor != descriptor {
-                        continue;
-                    }
-                    let method_name = c_file.cp_entry(method.name_index)?.as_utf8()?;
-                    if method_name == name {
-                        method_to_call = Some(method.clone());
-                        found = true;
-                        break;
-                    }
-                }   
-                // Recurse up the inheritance tree.
-                if !found {
-                    if !c.get_class_file().has_super() { break 'super_loop; }
-                    c = jvm.resolve_class_reference(c.get_class_file().super_name().unwrap())?;
-                }
-                
-            }
-            // TODO: Search Superinterfaces of c.
-            if !found {
-                return Err(Error::NoSuchMethodError(Opcode::INVOKEVIRTUAL));
-            }
-        }
-        
-        let resolved_method = method_to_call.unwrap();
-        /*
-        {
-            // search the methods of the object to see if we can override. 
-            if (resolved_method.access_flags.flags & flags::method::ACC_PRIVATE) == 0 {
-                let mut obj_c = object.class().clone();
-                let mut obj_c_file = obj_c.get_class_file();
-                let mut found = false;
-                while obj_c.get_class_file().has_super() && !found {
-                    for method in obj_c.get_class_file().methods() {
-                        // https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.3
-                        // We still need to check for signature polymorphic functions.
-                        let method_descriptor = obj_c_file.cp_entry(method.descriptor_index)?.as_utf8()?;
-                        if method_descriptor != descriptor {
-                            continue;
-                        }
-                        let method_name = obj_c_file.cp_entry(method.name_index)?.as_utf8()?;
-                        if method_name != name {
-                            continue;
-                        }
-                        // we *should* check access control, but we don't
-                        resolved_method = method.clone();
-                        found = true;
-                        break;
-                    }   
-                    // Recurse up the inheritance tree.
-                    if !found {
-                        obj_c = self.resolve_class_reference(obj_c.get_class_file().super_name().unwrap())?;
-                        obj_c_file = obj_c.get_class_file();
-                    }
-                    
-                }
-                // TODO: Search Superinterfaces of c.
-            }
-        };
-        */
-        if (resolved_method.access_flags.flags & flags::method::ACC_STATIC) > 0 {
+        // TODO: Signature polymorphic methods.
+        let method_ref = jvm.resolve_method_ref(&current_class, self.index, Opcode::INVOKEVIRTUAL)?;
+        if (method_ref.method().access_flags.flags & flags::method::ACC_STATIC) > 0 {
             return Err(Error::IncompatibleClassChangeError(Opcode::INVOKEVIRTUAL));
-        } 
-        if (resolved_method.access_flags.flags & flags::method::ACC_ABSTRACT) > 0 {
-            return Err(Error::AbstractMethodError(Opcode::INVOKEVIRTUAL));
-        } 
-        if (resolved_method.access_flags.flags & flags::method::ACC_SYNCHRONIZED) > 0 {
-            // TODO: Enter monitors on Classes.
-        } 
-        jvm.execute_on_object(&resolved_method, c)
+        }
+        let call = Rc::new(jvm.link_virtual_call(Rc::clone(&method_ref.class), &method_ref.name, &method_ref.descriptor)?);
+        self.call = Some(Rc::clone(&call));
+        invoke_virtual(jvm, &call)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.call.as_ref().map(|call| InvokeVirtualQuick { call: Rc::clone(call) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -332,6 +339,7 @@ impl Instruction for InvokeVirtual {
 #[derive(Debug, PartialEq, Clone)]
 pub struct InvokeSpecial {
     index: u16,
+    method: Option<Rc<MethodRef>>,
 }
 impl Instruction for InvokeSpecial {
     fn name(&self) -> &'static str {
@@ -345,141 +353,34 @@ impl Instruction for InvokeSpecial {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(InvokeSpecial { index })
+            Ok(InvokeSpecial { index, method: None })
         }
     }
     //https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-6.html#jvms-6.5.invokespecial
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        let rt_file = frame.rt_const_pool.get_class_file();
-        let entry = rt_file.cp_entry(self.index)?;
-        let (method_ref, _) = match entry {
-            Entry::MethodRef(refinfo) => (refinfo, false),
-            Entry::InterfaceMethodRef(refinfo) => (refinfo, true),
-            _ => return Err(Error::IllegalConstantLoad(Opcode::INVOKESPECIAL)),
-        };
         let current_class = Rc::clone(&frame.rt_const_pool);
-        let current_class_file = current_class.get_class_file();
-        let name_and_type = current_class_file.cp_entry(method_ref.name_and_type_index)?.as_name_and_type()?;
-        let name = current_class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
-        let descriptor = current_class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
-        let c_info = current_class_file.cp_entry(method_ref.class_index)?.as_class()?;
-        let c_name = current_class_file.cp_entry(*c_info)?.as_utf8()?;
-        let mut c = jvm.resolve_class_reference(c_name)?;
-        let mut c_file = c.get_class_file();
-        if (c.get_class_file().access_flags().flags & flags::class::ACC_INTERFACE) > 0 {
-            return Err(Error::IncompatibleClassChangeError(Opcode::INVOKESPECIAL));
-        } 
-
-
-        // Resolve method
-        let mut resolved_method_wrapped = None; 
-        {
-            let mut found = false;
-            while !found {
-                for method in c_file.methods() {
-                    // https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.3
-                    // We still need to check for signature polymorphic functions.
-                    let method_descriptor = c_file.cp_entry(method.descriptor_index)?.as_utf8()?;
-                    if method_descriptor != descriptor {
-                        continue;
-                    }
-                    let method_name = c_file.cp_entry(method.name_index)?.as_utf8()?;
-                    if method_name == name {
-                        resolved_method_wrapped = Some(method.clone());
-                        found = true;
-                        break;
-                    }
-                }   
-                // Recurse up the inheritance tree.
-                if !found {
-                    if !c_file.has_super() { break; }
-
-                    c = jvm.resolve_class_reference(c_file.super_name().unwrap())?;
-                    c_file = c.get_class_file();
+        let method_ref = jvm.resolve_method_ref(&current_class, self.index, Opcode::INVOKESPECIAL)?;
+        let symbolic_class = &method_ref.symbolic_class;
+        // Calls like super.foo() name a superclass of the current class, and get looked up again from the direct superclass,
+        // in case something in between overrides the method.
+        let is_super_call = method_ref.name != "<init>" && !symbolic_class.get_class_file().is_interface()
+            && !Rc::ptr_eq(symbolic_class, &current_class) 
+            && jvm.is_subclass(Rc::clone(&current_class), symbolic_class.get_class_file().name())?;
+        let (c, index) = match current_class.get_class_file().super_name() {
+            Some(super_name) if is_super_call => {
+                let super_class = jvm.resolve_class_reference(super_name)?;
+                let (c, _) = jvm.resolve_class_method(super_class, &method_ref.name, &method_ref.descriptor, Opcode::INVOKESPECIAL)?;
+                match JVM::find_declared_method_index(&c, &method_ref.name, &method_ref.descriptor)? {
+                    Some(index) => (c, index),
+                    None => return Err(Error::NoSuchMethodError(Opcode::INVOKESPECIAL)),
                 }
-                
-            }
-            // TODO: Search Superinterfaces of c.
-            if !found {
-                return Err(Error::NoSuchMethodError(Opcode::MethodInvoke));
-            }
-        }
-        let resolved_method = resolved_method_wrapped.unwrap();
-        let resolved_method_class = Rc::clone(&c); // Save the originating class of resolved_method so we can index it.
-        let res_method_file = resolved_method_class.get_class_file();
-        let resolved_name = res_method_file.cp_entry(resolved_method.name_index)?.as_utf8()?;
-        let resolved_desc = res_method_file.cp_entry(resolved_method.descriptor_index)?.as_utf8()?;
-        // Next, possibly change C
-        
-        if (c.get_class_file().cp_entry(resolved_method.name_index)?.as_utf8()? != "<init>") && current_class.get_class_file().has_super() && ({
-            // Figure out if c is a superclass of current_class
-            let mut temp = Rc::clone(&current_class);
-            let mut temp_file = temp.get_class_file();
-            let mut res = false;
-            while temp_file.has_super() {
-                let super_name = temp_file.super_name().unwrap();
-                if super_name == c.get_class_file().name() {
-                    res = true;
-                    break;
-                }
-                temp = jvm.resolve_class_reference(super_name)?;
-                temp_file = temp.get_class_file();
-            }
-            res
-        })
-        && ((c.get_class_file().access_flags().flags & flags::class::ACC_SUPER) > 0 ) {
-            c = jvm.resolve_class_reference(current_class.get_class_file().super_name().unwrap())?;
-        }
-
-        let mut actual_method_wrapped = None;
-        // Now, select *actual* method
-        // All methods have to be instance methods. 
-        // First, check methods of c for a method with the same name and desc.
-        // and Check superclasses, if c is a class
-        if actual_method_wrapped.is_none() && ((c.get_class_file().access_flags().flags & flags::class::ACC_INTERFACE) == 0) {
-            let mut c_super = Rc::clone(&c);
-            let mut found = false;
-            while !found {
-                for method in c.get_class_file().methods() {
-                    if (method.access_flags.flags & flags::method::ACC_STATIC) > 0 {
-                        continue;
-                    }
-                    if (c_super.get_class_file().cp_entry(method.name_index)?.as_utf8()? == resolved_name) && 
-                        (c_super.get_class_file().cp_entry(method.descriptor_index)?.as_utf8()? == resolved_desc) {
-                        actual_method_wrapped = Some(method.clone());
-                        c = Rc::clone(&c_super);
-                        found = true;
-                        break;
-                    }
-                }
-                if !found {
-                    if !c_super.get_class_file().has_super() { break; }
-
-                    c_super = jvm.resolve_class_reference(c_super.get_class_file().super_name().unwrap())?;
-                }
-            }
-        }
-        // Check object, if c in an interface.
-        if actual_method_wrapped.is_none() && ((c.get_class_file().access_flags().flags & flags::class::ACC_INTERFACE) > 0) {
-            let obj_class = jvm.resolve_class_reference("java/lang/Object")?;
-            for method in obj_class.get_class_file().methods() {
-                if (method.access_flags.flags & flags::method::ACC_STATIC) > 0 {
-                    continue;
-                }
-                if (obj_class.get_class_file().cp_entry(method.name_index)?.as_utf8()? == resolved_name) && 
-                   (obj_class.get_class_file().cp_entry(method.descriptor_index)?.as_utf8()? == resolved_desc) {
-                    actual_method_wrapped = Some(method.clone());
-                    c = Rc::clone(&obj_class);
-                    break;
-                }
-            }
-        }
-        // TODO: Check maximally specific methods in superinterfaces.
-
-        // I *think* the way this works is that if we don't find a method, we use the one we resolved.
-        let actual_method = actual_method_wrapped.unwrap();
+            },
+            _ => (Rc::clone(&method_ref.class), method_ref.index),
+        };
+        let c_file = c.get_class_file();
+        let actual_method = &c_file.methods[index];
 
 
         if (actual_method.access_flags.flags & flags::method::ACC_STATIC) > 0 {
@@ -488,29 +389,16 @@ impl Instruction for InvokeSpecial {
         if (actual_method.access_flags.flags & flags::method::ACC_ABSTRACT) > 0 {
             return Err(Error::AbstractMethodError(Opcode::INVOKESPECIAL));
         } 
-        if (actual_method.access_flags.flags & flags::method::ACC_SYNCHRONIZED) > 0 {
-            let current_thread_number = jvm.m_thread_index;
-            let thread = access_macros::current_thread_mut!(jvm);
-            let frame = access_macros::current_frame_mut!(thread);
-            // FIXME: This code is completely wrong, and should be addressed in either execute_on_object or the object's method call function.
-            let object = match frame.op_stack.pop() {
-                Some(o) => o.as_reference()?,
-                None => return Err(Error::StackUnderflow(Opcode::INVOKESPECIAL)),
-            };
-            match object {
-                Reference::Null => return Err(Error::NullPointerException(Opcode::MONITORENTER)),
-                Reference::Array(_, mut mrc) | Reference::Interface(_, mut mrc) | Reference::Object(_, mut mrc) => {
-                    let m = unsafe { Rc::get_mut_unchecked(&mut mrc)};
-                    let success = m.try_enter(current_thread_number);
-                    if !success {
-                        thread.current_monitor = Some(mrc.clone());
-                        return Ok(()); // We have to block
-                    }
-                },
-            }
-        } 
-        // TODO: Refactor jvm.setup_method_call() to handle native methods and synchronized ones (and rename it).
-        jvm.execute_on_object(&actual_method, c)
+        // invokespecial always calls the same method, so there's nothing left to look up next time.
+        self.method = Some(Rc::new(MethodRef {
+            symbolic_class: Rc::clone(symbolic_class), class: Rc::clone(&c), index,
+            name: method_ref.name.clone(), descriptor: method_ref.descriptor.clone(),
+        }));
+        // TODO: Refactor jvm.setup_method_call() to handle native methods (and rename it).
+        jvm.invoke_method(actual_method, &c, |jvm| jvm.execute_on_object(actual_method, Rc::clone(&c)))
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.method.as_ref().map(|method| InvokeSpecialQuick { method: Rc::clone(method) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -526,6 +414,7 @@ impl Instruction for InvokeSpecial {
 #[derive(Debug, PartialEq, Clone)]
 pub struct InvokeStatic {
     index: u16,
+    method: Option<Rc<MethodRef>>,
 }
 impl Instruction for InvokeStatic {
     fn name(&self) -> &'static str {
@@ -539,79 +428,31 @@ impl Instruction for InvokeStatic {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(InvokeStatic { index })
+            Ok(InvokeStatic { index, method: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        let rt_file = frame.rt_const_pool.get_class_file();   
-        let entry = rt_file.cp_entry(self.index)?;
-        let (method_ref, is_interface) = match entry {
-            Entry::MethodRef(refinfo) => (refinfo, false),
-            Entry::InterfaceMethodRef(refinfo) => (refinfo, true),
-            _ => return Err(Error::IllegalConstantLoad(Opcode::INVOKESTATIC)),
-        };
         let current_class = Rc::clone(&frame.rt_const_pool);
-        let current_class_file = current_class.get_class_file();
-        let name_and_type = current_class_file.cp_entry(method_ref.name_and_type_index)?.as_name_and_type()?;
-        let name = current_class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
-        let descriptor = current_class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
-        let c_info = current_class_file.cp_entry(method_ref.class_index)?.as_class()?;
-        let c_name = current_class_file.cp_entry(*c_info)?.as_utf8()?;
-        let mut c = jvm.resolve_class_reference(c_name)?;
-        let mut c_file = c.get_class_file();
-        if is_interface {
-            if (c.get_class_file().access_flags().flags & flags::class::ACC_INTERFACE) == 0 {
-                return Err(Error::IncompatibleMethodRefAndClass(Opcode::INVOKESTATIC));
-            }
-        }
-        else if (c.get_class_file().access_flags().flags & flags::class::ACC_INTERFACE) > 0 {
-                return Err(Error::IncompatibleMethodRefAndClass(Opcode::INVOKESTATIC));
-        }
-        let mut method_to_call = None; 
-        // Resolve method
-        {
-            let mut found = false;
-            while c_file.has_super() && !found {
-                for method in c_file.methods() {
-                    // https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.3
-                    // We still need to check for signature polymorphic functions.
-                    let method_descriptor = c_file.cp_entry(method.descriptor_index)?.as_utf8()?;
-                    if method_descriptor != descriptor {
-                        continue;
-                    }
-                    let method_name = c_file.cp_entry(method.name_index)?.as_utf8()?;
-                    if method_name == name {
-                        method_to_call = Some(method.clone());
-                        found = true;
-                        break;
-                    }
-                }   
-                // Recurse up the inheritance tree.
-                if !found {
-                    c = jvm.resolve_class_reference(c.get_class_file().super_name().unwrap())?;
-                    c_file = c.get_class_file();
-                }
-                
-            }
-            // TODO: Search Superinterfaces of c.
-            if !found {
-                return Err(Error::NoSuchMethodError(Opcode::MethodInvoke));
-            }
-        }
-        let method = method_to_call.unwrap();
+        let method_ref = jvm.resolve_method_ref(&current_class, self.index, Opcode::INVOKESTATIC)?;
+        let c = Rc::clone(&method_ref.class);
+        let c_file = c.get_class_file();
+        let method = &c_file.methods[method_ref.index];
         if (method.access_flags.flags & flags::method::ACC_STATIC) == 0 {
             return Err(Error::IllegalMethodType(Opcode::INVOKESTATIC));
         } 
         if (method.access_flags.flags & flags::method::ACC_ABSTRACT) > 0 {
             return Err(Error::IllegalMethodType(Opcode::INVOKESTATIC));
         } 
-        if (method.access_flags.flags & flags::method::ACC_SYNCHRONIZED) > 0 {
-            // TODO: Enter monitors on Classes.
-        } 
-        let _was_native = c.exec_method(jvm, &method)?;
-        Ok(())
+        jvm.initialize_class(Rc::clone(&c))?;
+        if jvm.is_initialized(&c) {
+            self.method = Some(Rc::clone(&method_ref));
+        }
+        jvm.invoke_method(method, &c, |jvm| Rc::clone(&c).exec_method(jvm, method).map(|_was_native| ()))
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.method.as_ref().map(|method| InvokeStaticQuick { method: Rc::clone(method) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -624,9 +465,16 @@ impl Instruction for InvokeStatic {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct InvokeInterface {
-    _index: u16,
+    index: u16,
+    method: Option<Rc<MethodRef>>,
+    call: Option<Rc<VirtualCall>>,
+}
+impl PartialEq for InvokeInterface {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
 }
 impl Instruction for InvokeInterface {
     fn name(&self) -> &'static str {
@@ -640,11 +488,29 @@ impl Instruction for InvokeInterface {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(InvokeInterface { _index: index })
+            v.remove(0); v.remove(0); // Skip count and the required zero
+            Ok(InvokeInterface { index, method: None, call: None })
         }
     }
-    fn execute(&mut self, _jvm : &mut JVM) -> Result<(), Error> {
-        Err(Error::Todo(Opcode::INVOKEINTERFACE))
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        // https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.invokeinterface
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        let current_class = Rc::clone(&frame.rt_const_pool);
+        let method_ref = jvm.resolve_method_ref(&current_class, self.index, Opcode::INVOKEINTERFACE)?;
+        if (method_ref.method().access_flags.flags & flags::method::ACC_STATIC) > 0 {
+            return Err(Error::IncompatibleClassChangeError(Opcode::INVOKEINTERFACE));
+        }
+        let call = Rc::new(jvm.link_virtual_call(Rc::clone(&method_ref.class), &method_ref.name, &method_ref.descriptor)?);
+        self.method = Some(Rc::clone(&method_ref));
+        self.call = Some(Rc::clone(&call));
+        invoke_interface(jvm, &method_ref.symbolic_class, &call)
+    }
+    fn quicken(&self) -> Option<Op> {
+        match (&self.method, &self.call) {
+            (Some(method), Some(call)) => Some(InvokeInterfaceQuick { method: Rc::clone(method), call: Rc::clone(call) }.into()),
+            _ => None,
+        }
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -657,9 +523,15 @@ impl Instruction for InvokeInterface {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct InvokeDynamic {
     index: u16,
+    call_site: Option<Rc<CallSite>>,
+}
+impl PartialEq for InvokeDynamic {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
 }
 impl Instruction for InvokeDynamic {
     fn name(&self) -> &'static str {
@@ -674,46 +546,21 @@ impl Instruction for InvokeDynamic {
             };
             v.remove(0); v.remove(0);
             v.remove(0); v.remove(0); // Skip required zeros
-            Ok(InvokeDynamic { index })
+            Ok(InvokeDynamic { index, call_site: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        let rt_file = frame.rt_const_pool.get_class_file();
-        // Resolve the index into an instance of java.lang.invoke.CallSite.
-        // This procedure also occurs in ldc, so this should be a subroutine.
-        let invoke_dynamic_info = rt_file.cp_entry(self.index)?.as_invoke_dynamic()?;
-        let bootstrap_index = invoke_dynamic_info.bootstrap_method_attr_index;
-        let bootstrap_methods = match &rt_file.bootstrap_methods {
-            Some(m) => m,
-            None => return Err(Error::MissingBootstrapTable(Opcode::INVOKEDYNAMIC)),
-        };
-        let bootstrap_method = &bootstrap_methods[bootstrap_index as usize];
-        let _method_handle = frame.rt_const_pool.get_class_file().cp_entry(bootstrap_method.bootstrap_method_ref)?.as_method_handle()?;
-        // Resolve though https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.6 and 
-        // https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-5.html#jvms-5.4.3.5, a reference to an instance of 
-        // java.lang.invoke.CallSite.
-        /* 
-        match method_handle.ref_kind {
-            ReferenceKind::RefGetField => ,
-            ReferenceKind::RefGetStatic => ,
-            ReferenceKind::RefPutField => ,
-            ReferenceKind::RefPutStatic => ,
-            ReferenceKind::RefInvokeVirtual => ,
-            ReferenceKind::RefInvokeStatic => ,
-            ReferenceKind::RefInvokeSpecial => ,
-            ReferenceKind::RefNewInvokeSpecial => ,
-            ReferenceKind::RefInvokeInterface => ,
-        }
-        */
-        // Then, use the process in invokevirtual to invoke a method where
-        // The class in which the method is to be found is java.lang.invoke.MethodHandle,
-        // The name of the method is invokeExact,
-        // The descriptor is the descriptor in the reference,
-        // and the stack looks like: reference to the method handle in the invoke.CallSite [nargs].
-        Err(Error::Todo(Opcode::INVOKEDYNAMIC))
-
+        let current_class = Rc::clone(&frame.rt_const_pool);
+        // Linking runs the bootstrap method, see https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.invokedynamic
+        let call_site = Rc::new(CallSite::link(jvm, current_class, self.index)?);
+        self.call_site = Some(Rc::clone(&call_site));
+        call_site.invoke(jvm)?;
+        Ok(Flow::Invoke)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.call_site.as_ref().map(|call_site| InvokeDynamicQuick { call_site: Rc::clone(call_site) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -726,9 +573,20 @@ impl Instruction for InvokeDynamic {
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Clone)]
 pub struct New {
     index: u16,
+    class: Option<Rc<dyn Class>>,
+}
+impl PartialEq for New {
+    fn eq(&self, other: &Self) -> bool {
+        self.index == other.index
+    }
+}
+impl std::fmt::Debug for New {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "New {{ index: {} }}", self.index)
+    }
 }
 impl Instruction for New {
     fn name(&self) -> &'static str {
@@ -742,18 +600,31 @@ impl Instruction for New {
                 u16::from_be_bytes(std::slice::from_raw_parts(v.as_ptr(), 2).try_into().unwrap()) 
             };
             v.remove(0); v.remove(0);
-            Ok(New { index })
+            Ok(New { index, class: None })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let current_class = frame.rt_const_pool.clone();      
+        let current_class_file = current_class.get_class_file();
+        let class_name_index = current_class_file.cp_entry(self.index)?.as_class()?;
+        let class_name = current_class_file.cp_entry(*class_name_index)?.as_utf8()?;
+        let class = jvm.resolve_class_reference(class_name)?;
+        jvm.initialize_class(Rc::clone(&class))?;
+        let size = object::heap_size_of(&class, jvm)?;
+        if jvm.is_initialized(&class) {
+            self.class = Some(class);
+        }
+        jvm.reserve_heap(size)?;
         let objectref = Reference::new_object(current_class, self.index, jvm)?;
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         frame.op_stack.push(Value::Reference(objectref));
-        Ok(())
+        Ok(Flow::Next)
+    }
+    fn quicken(&self) -> Option<Op> {
+        self.class.as_ref().map(|class| NewQuick { class: Rc::clone(class) }.into())
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -783,7 +654,7 @@ impl Instruction for NewArray {
             Ok(NewArray { atype })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let count = match frame.op_stack.pop() {
@@ -793,10 +664,13 @@ impl Instruction for NewArray {
         if count < 0 {
             return Err(Error::NegativeArraySizeException(Opcode::NEWARRAY));
         }
-        let arrayref = Reference::new_array(count as usize, self.atype);
-        let arrayval = Value::Reference(arrayref);
-        frame.op_stack.push(arrayval);
-        Ok(())
+        jvm.reserve_heap(Array::<dyn Class, dyn object::Object>::heap_size_of(count as usize, self.atype))?;
+        let array = Rc::new(Array::new(count as usize, self.atype));
+        jvm.heap.track_array(&array);
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.push(Value::Reference(Reference::Array(array, Rc::new(Monitor::new()))));
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -828,7 +702,7 @@ impl Instruction for ANewArray {
             Ok(ANewArray { index })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let rt_file = frame.rt_const_pool.get_class_file();
@@ -839,17 +713,26 @@ impl Instruction for ANewArray {
         let descriptor = {
             let class_ref = rt_file.cp_entry(self.index)?.as_class()?;
             let class_name = rt_file.cp_entry(*class_ref)?.as_utf8()?;
-            // We actually just need the descriptor.
             // The Array descriptor represents the descriptor for the array, not for the component type, and so we need to add an extra '['.
-            format!("[{}", class_name.clone())          
+            // The component is a class name, unless it's an array itself, in which case it's already a descriptor.
+            if class_name.starts_with('[') {
+                format!("[{class_name}")
+            }
+            else {
+                format!("[L{class_name};")
+            }
         };
         if *count.as_int()? < 0 {
             return Err(Error::NegativeArraySizeException(Opcode::ANEWARRAY));
         }
-        let array = Reference::Array(Rc::new(Array::new_ref(*count.as_int()? as usize, descriptor)), 
-        Rc::new(Monitor::new()));
-        frame.op_stack.push(Value::Reference(array));
-        Ok(())
+        let count = *count.as_int()? as usize;
+        jvm.reserve_heap(Array::<dyn Class, dyn object::Object>::heap_size_of(count, atype::T_REF))?;
+        let array = Rc::new(Array::new_ref(count, descriptor));
+        jvm.heap.track_array(&array);
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.push(Value::Reference(Reference::Array(array, Rc::new(Monitor::new()))));
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -875,7 +758,7 @@ impl Instruction for ArrayLength {
             Ok(ArrayLength {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let array_val = match frame.op_stack.pop() {
@@ -891,7 +774,7 @@ impl Instruction for ArrayLength {
         let size = array.len();
         let size_val = Value::Int(size as i32);
         frame.op_stack.push(size_val);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -917,7 +800,7 @@ impl Instruction for AThrow {
             Ok(AThrow {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let (should_init, null_class) = {
             let thread = access_macros::current_thread_mut!(jvm);
             let frame = access_macros::current_frame_mut!(thread);
@@ -983,7 +866,7 @@ impl Instruction for CheckCast {
             Ok(CheckCast { index })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let op_stack_len = frame.op_stack.len();
@@ -1000,7 +883,7 @@ impl Instruction for CheckCast {
                 name = format!("L{}", c.get_class_file().name());
                 name.as_str()
             }
-            Reference::Null => return Ok(()),
+            Reference::Null => return Ok(Flow::Next),
         };
         let class = Rc::clone(&frame.rt_const_pool);
         let class_file = class.get_class_file();
@@ -1009,9 +892,12 @@ impl Instruction for CheckCast {
         
         
         if jvm.check_class(object_desc, class_desc)? {
-            return Ok(());
+            return Ok(Flow::Next);
         }
-        Err(Error::ClassCastException(Opcode::CHECKCAST))
+        // Same message as Java's, minus which module and class loader each class came from.
+        let message = format!("class {} cannot be cast to class {}",
+            object_desc.strip_prefix('L').unwrap_or(object_desc).replace('/', "."), class_desc.replace('/', "."));
+        jvm.throw_exception_with_message("java/lang/ClassCastException", message)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1043,7 +929,7 @@ impl Instruction for InstanceOf {
             Ok(InstanceOf { index })
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let objectref = match frame.op_stack.pop() {
@@ -1068,23 +954,18 @@ impl Instruction for InstanceOf {
             }
             Reference::Null => {
                 frame.op_stack.push(Value::Int(0));
-                return Ok(());
+                return Ok(Flow::Next);
             },
         };
         let class = Rc::clone(&frame.rt_const_pool);
         let class_file = class.get_class_file();
         let class_reference = *class_file.cp_entry(self.index)?.as_class()?;
         let class_desc = class_file.cp_entry(class_reference)?.as_utf8()?.as_str();
-        if jvm.check_class(object_desc, class_desc)? {
-            let thread = access_macros::current_thread_mut!(jvm);
-            let frame = access_macros::current_frame_mut!(thread);
-            frame.op_stack.push(Value::Int(0));
-            return Ok(());
-        }
+        let is_instance = jvm.check_class(object_desc, class_desc)?;
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        frame.op_stack.push(Value::Int(1));
-        Ok(())
+        frame.op_stack.push(Value::Int(is_instance as i32));
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1110,28 +991,21 @@ impl Instruction for MonitorEnter {
             Ok(MonitorEnter {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-        let current_thread_number = jvm.m_thread_index;
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let current_thread_number = jvm.current_thread_id();
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
-        let object = match frame.op_stack.pop() {
-            Some(o) => o.as_reference()?,
+        let monitor = match frame.op_stack.last() {
+            Some(o) => Rc::clone(o.as_reference()?.monitor().map_err(|_| Error::NullPointerException(Opcode::MONITORENTER))?),
             None => return Err(Error::StackUnderflow(Opcode::MONITORENTER)),
         };
-        match object {
-            Reference::Null => return Err(Error::NullPointerException(Opcode::MONITORENTER)),
-            Reference::Array(_, mut mrc) | Reference::Interface(_, mut mrc) | Reference::Object(_, mut mrc) => {
-                let m = match Rc::get_mut(&mut mrc) {
-                    Some(m) => m,
-                    None => return Err(Error::DoubleMutableReferenceToMonitor(Opcode::MONITORENTER)),
-                };
-                let success = m.try_enter(current_thread_number);
-                if !success {
-                    thread.current_monitor = Some(mrc.clone());
-                }
-            },
+        if !monitor.try_enter(current_thread_number) {
+            // The object stays on the stack, so we can try again once the owner lets go of it.
+            thread.current_monitor = Some(monitor);
+            return Ok(Flow::Jump);
         }
-        Ok(())
+        frame.op_stack.pop();
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1157,28 +1031,19 @@ impl Instruction for MonitorExit {
             Ok(MonitorExit {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
-        let current_thread_number = jvm.m_thread_index;
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
+        let current_thread_number = jvm.current_thread_id();
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let object = match frame.op_stack.pop() {
             Some(o) => o.as_reference()?,
             None => return Err(Error::StackUnderflow(Opcode::MONITOREXIT)),
         };
-        match object {
-            Reference::Null => return Err(Error::NullPointerException(Opcode::MONITOREXIT)),
-            Reference::Array(_, mut mrc) | Reference::Interface(_, mut mrc) | Reference::Object(_, mut mrc) => {
-                let m = match Rc::get_mut(&mut mrc) {
-                    Some(m) => m,
-                    None => return Err(Error::DoubleMutableReferenceToMonitor(Opcode::MONITORENTER)),
-                };
-                let success = m.try_exit(current_thread_number);
-                if !success {
-                    return Err(Error::IllegalMonitorStateException(Opcode::MONITOREXIT));
-                }
-            },
+        let monitor = object.monitor().map_err(|_| Error::NullPointerException(Opcode::MONITOREXIT))?;
+        if !monitor.try_exit(current_thread_number) {
+            return jvm.throw_exception("java/lang/IllegalMonitorStateException", "()V", vec![]);
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -13,7 +13,7 @@ impl Instruction for Breakpoint {
             Err(Error::Breakpoint)
         }
     }
-    fn execute(&mut self, _jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, _jvm : &mut JVM) -> Result<Flow, Error> {
         Err(Error::Breakpoint)
     }
     fn as_any(&self) -> &dyn Any {
@@ -40,7 +40,7 @@ impl Instruction for ImpDep1 {
             Err(Error::ImpDep1)
         }
     }
-    fn execute(&mut self, _jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, _jvm : &mut JVM) -> Result<Flow, Error> {
         Err(Error::ImpDep1)
     }
     fn as_any(&self) -> &dyn Any {
@@ -67,7 +67,7 @@ impl Instruction for ImpDep2 {
             Err(Error::ImpDep2)
         }
     }
-    fn execute(&mut self, _jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, _jvm : &mut JVM) -> Result<Flow, Error> {
         Err(Error::ImpDep2)
     }
     fn as_any(&self) -> &dyn Any {
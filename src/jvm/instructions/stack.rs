@@ -13,7 +13,7 @@ impl Instruction for Pop {
             Ok(Pop {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         match frame.op_stack.pop() {
@@ -24,7 +24,7 @@ impl Instruction for Pop {
             },
             None => return Err(Error::StackUnderflow(Opcode::POP)),
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -50,13 +50,13 @@ impl Instruction for Pop2 {
             Ok(Pop2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         match frame.op_stack.pop() {
             Some(val) => {
                 if val.is_comptype2() {
-                    return Ok(());
+                    return Ok(Flow::Next);
                 }
                 else {
                     match frame.op_stack.pop() {
@@ -71,7 +71,7 @@ impl Instruction for Pop2 {
             },
             None => return Err(Error::StackUnderflow(Opcode::POP2)),
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -97,7 +97,7 @@ impl Instruction for Dup {
             Ok(Dup {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let top_index = frame.op_stack.len();
@@ -109,7 +109,7 @@ impl Instruction for Dup {
             return Err(Error::IncorrectComputationalType(Opcode::DUP));
         }
         frame.op_stack.push(val);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -135,7 +135,7 @@ impl Instruction for DupX1 {
             Ok(DupX1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -155,7 +155,7 @@ impl Instruction for DupX1 {
         frame.op_stack.push(val.clone());
         frame.op_stack.push(val2);
         frame.op_stack.push(val);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -181,7 +181,7 @@ impl Instruction for DupX2 {
             Ok(DupX2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -213,7 +213,7 @@ impl Instruction for DupX2 {
             frame.op_stack.push(val2);
             frame.op_stack.push(val);
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -239,7 +239,7 @@ impl Instruction for Dup2 {
             Ok(Dup2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let top_index = frame.op_stack.len();
@@ -258,7 +258,7 @@ impl Instruction for Dup2 {
         else {
             frame.op_stack.push(val);
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -284,7 +284,7 @@ impl Instruction for Dup2X1 {
             Ok(Dup2X1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -317,7 +317,7 @@ impl Instruction for Dup2X1 {
             frame.op_stack.push(val2);
             frame.op_stack.push(val);
         }
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -343,7 +343,7 @@ impl Instruction for Dup2X2 {
             Ok(Dup2X2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -407,7 +407,7 @@ impl Instruction for Dup2X2 {
             frame.op_stack.push(val); 
         }
         
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -433,7 +433,7 @@ impl Instruction for Swap {
             Ok(Swap {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -446,7 +446,7 @@ impl Instruction for Swap {
         };
         frame.op_stack.push(val);
         frame.op_stack.push(val2);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -22,7 +22,7 @@ impl Instruction for IStore {
         }
         Ok(IStore { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -30,7 +30,7 @@ impl Instruction for IStore {
             None => return Err(Error::StackUnderflow(Opcode::ISTORE)),
         };
         frame.insert_local(VarValue::Int(*val.as_int()?), self.idx);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -65,7 +65,7 @@ impl Instruction for LStore {
         }
         Ok(LStore { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -73,7 +73,7 @@ impl Instruction for LStore {
             None => return Err(Error::StackUnderflow(Opcode::LSTORE)),
         };
         frame.insert_local(VarValue::Long(*val.as_long()?), self.idx);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -108,7 +108,7 @@ impl Instruction for FStore {
         }
         Ok(FStore { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -116,7 +116,7 @@ impl Instruction for FStore {
             None => return Err(Error::StackUnderflow(Opcode::FSTORE)),
         };
         frame.insert_local(VarValue::Float(*val.as_float()?), self.idx);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -151,7 +151,7 @@ impl Instruction for DStore {
         }
         Ok(DStore { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -159,7 +159,7 @@ impl Instruction for DStore {
             None => return Err(Error::StackUnderflow(Opcode::DSTORE)),
         };
         frame.insert_local(VarValue::Double(*val.as_double()?), self.idx);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -194,7 +194,7 @@ impl Instruction for AStore {
         }
         Ok(AStore { idx })
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -202,7 +202,7 @@ impl Instruction for AStore {
             None => return Err(Error::StackUnderflow(Opcode::ASTORE)),
         };
         frame.insert_local(VarValue::Reference(val.as_reference()?.clone()), self.idx);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -228,7 +228,7 @@ impl Instruction for IStore0 {
             Ok(IStore0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -236,7 +236,7 @@ impl Instruction for IStore0 {
             None => return Err(Error::StackUnderflow(Opcode::ISTORE0)),
         };
         frame.insert_local(VarValue::Int(*val.as_int()?), 0);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -262,7 +262,7 @@ impl Instruction for IStore1 {
             Ok(IStore1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -270,7 +270,7 @@ impl Instruction for IStore1 {
             None => return Err(Error::StackUnderflow(Opcode::ISTORE1)),
         };
         frame.insert_local(VarValue::Int(*val.as_int()?), 1);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -319,7 +319,7 @@ impl Instruction for IStore2 {
             Ok(IStore2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -327,7 +327,7 @@ impl Instruction for IStore2 {
             None => return Err(Error::StackUnderflow(Opcode::ISTORE2)),
         };
         frame.insert_local(VarValue::Int(*val.as_int()?), 2);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -353,7 +353,7 @@ impl Instruction for IStore3 {
             Ok(IStore3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -361,7 +361,7 @@ impl Instruction for IStore3 {
             None => return Err(Error::StackUnderflow(Opcode::ISTORE3)),
         };
         frame.insert_local(VarValue::Int(*val.as_int()?), 3);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -387,7 +387,7 @@ impl Instruction for LStore0 {
             Ok(LStore0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -395,7 +395,7 @@ impl Instruction for LStore0 {
             None => return Err(Error::StackUnderflow(Opcode::LSTORE0)),
         };
         frame.insert_local(VarValue::Long(*val.as_long()?), 0);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -421,7 +421,7 @@ impl Instruction for LStore1 {
             Ok(LStore1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -429,7 +429,7 @@ impl Instruction for LStore1 {
             None => return Err(Error::StackUnderflow(Opcode::LSTORE1)),
         };
         frame.insert_local(VarValue::Long(*val.as_long()?), 1);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -455,7 +455,7 @@ impl Instruction for LStore2 {
             Ok(LStore2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -463,7 +463,7 @@ impl Instruction for LStore2 {
             None => return Err(Error::StackUnderflow(Opcode::LSTORE2)),
         };
         frame.insert_local(VarValue::Long(*val.as_long()?), 2);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -489,7 +489,7 @@ impl Instruction for LStore3 {
             Ok(LStore3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -497,7 +497,7 @@ impl Instruction for LStore3 {
             None => return Err(Error::StackUnderflow(Opcode::LSTORE3)),
         };
         frame.insert_local(VarValue::Long(*val.as_long()?), 3);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -523,7 +523,7 @@ impl Instruction for FStore0 {
             Ok(FStore0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -531,7 +531,7 @@ impl Instruction for FStore0 {
             None => return Err(Error::StackUnderflow(Opcode::FSTORE0)),
         };
         frame.insert_local(VarValue::Float(*val.as_float()?), 0);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -557,7 +557,7 @@ impl Instruction for FStore1 {
             Ok(FStore1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -565,7 +565,7 @@ impl Instruction for FStore1 {
             None => return Err(Error::StackUnderflow(Opcode::FSTORE1)),
         };
         frame.insert_local(VarValue::Float(*val.as_float()?), 1);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -591,7 +591,7 @@ impl Instruction for FStore2 {
             Ok(FStore2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -599,7 +599,7 @@ impl Instruction for FStore2 {
             None => return Err(Error::StackUnderflow(Opcode::FSTORE2)),
         };
         frame.insert_local(VarValue::Float(*val.as_float()?), 2);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -625,7 +625,7 @@ impl Instruction for FStore3 {
             Ok(FStore3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -633,7 +633,7 @@ impl Instruction for FStore3 {
             None => return Err(Error::StackUnderflow(Opcode::FSTORE3)),
         };
         frame.insert_local(VarValue::Float(*val.as_float()?), 3);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -659,7 +659,7 @@ impl Instruction for DStore0 {
             Ok(DStore0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -667,7 +667,7 @@ impl Instruction for DStore0 {
             None => return Err(Error::StackUnderflow(Opcode::DSTORE0)),
         };
         frame.insert_local(VarValue::Double(*val.as_double()?), 0);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -693,7 +693,7 @@ impl Instruction for DStore1 {
             Ok(DStore1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -701,7 +701,7 @@ impl Instruction for DStore1 {
             None => return Err(Error::StackUnderflow(Opcode::DSTORE1)),
         };
         frame.insert_local(VarValue::Double(*val.as_double()?), 1);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -727,7 +727,7 @@ impl Instruction for DStore2 {
             Ok(DStore2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -735,7 +735,7 @@ impl Instruction for DStore2 {
             None => return Err(Error::StackUnderflow(Opcode::DSTORE2)),
         };
         frame.insert_local(VarValue::Double(*val.as_double()?), 2);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -761,7 +761,7 @@ impl Instruction for DStore3 {
             Ok(DStore3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -769,7 +769,7 @@ impl Instruction for DStore3 {
             None => return Err(Error::StackUnderflow(Opcode::DSTORE3)),
         };
         frame.insert_local(VarValue::Double(*val.as_double()?), 3);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -795,7 +795,7 @@ impl Instruction for AStore0 {
             Ok(AStore0 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -803,7 +803,7 @@ impl Instruction for AStore0 {
             None => return Err(Error::StackUnderflow(Opcode::ASTORE0)),
         };
         frame.insert_local(VarValue::Reference(val.as_reference()?.clone()), 0);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -829,7 +829,7 @@ impl Instruction for AStore1 {
             Ok(AStore1 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -837,7 +837,7 @@ impl Instruction for AStore1 {
             None => return Err(Error::StackUnderflow(Opcode::ASTORE1)),
         };
         frame.insert_local(VarValue::Reference(val.as_reference()?.clone()), 1);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -863,7 +863,7 @@ impl Instruction for AStore2 {
             Ok(AStore2 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -871,7 +871,7 @@ impl Instruction for AStore2 {
             None => return Err(Error::StackUnderflow(Opcode::ASTORE2)),
         };
         frame.insert_local(VarValue::Reference(val.as_reference()?.clone()), 2);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -897,7 +897,7 @@ impl Instruction for AStore3 {
             Ok(AStore3 {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -905,7 +905,7 @@ impl Instruction for AStore3 {
             None => return Err(Error::StackUnderflow(Opcode::ASTORE3)),
         };
         frame.insert_local(VarValue::Reference(val.as_reference()?.clone()), 3);
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -931,7 +931,7 @@ impl Instruction for IAStore {
             Ok(IAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -958,7 +958,7 @@ impl Instruction for IAStore {
             return Err(Error::IncorrectReferenceType(Opcode::IASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -984,7 +984,7 @@ impl Instruction for LAStore {
             Ok(LAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1010,8 +1010,8 @@ impl Instruction for LAStore {
         if !array.is_larray() {
             return Err(Error::IncorrectReferenceType(Opcode::LASTORE));
         }
-        unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_long()? as usize, val)?;
-        Ok(())
+        unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1037,7 +1037,7 @@ impl Instruction for FAStore {
             Ok(FAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1064,7 +1064,7 @@ impl Instruction for FAStore {
             return Err(Error::IncorrectReferenceType(Opcode::FASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1090,7 +1090,7 @@ impl Instruction for DAStore {
             Ok(DAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1117,7 +1117,7 @@ impl Instruction for DAStore {
             return Err(Error::IncorrectReferenceType(Opcode::DASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1143,7 +1143,7 @@ impl Instruction for AAStore {
             Ok(AAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1170,7 +1170,7 @@ impl Instruction for AAStore {
             return Err(Error::IncorrectReferenceType(Opcode::AASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1196,7 +1196,7 @@ impl Instruction for BAStore {
             Ok(BAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1219,11 +1219,11 @@ impl Instruction for BAStore {
             Reference::Array(arr, _) => arr,
             _ => return Err(Error::UnexpectedTypeOnStack(Opcode::BASTORE)),
         };
-        if !array.is_barray() | !array.is_boolarray() {
+        if !array.is_barray() && !array.is_boolarray() {
             return Err(Error::IncorrectReferenceType(Opcode::BASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1249,7 +1249,7 @@ impl Instruction for CAStore {
             Ok(CAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1276,7 +1276,7 @@ impl Instruction for CAStore {
             return Err(Error::IncorrectReferenceType(Opcode::CASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -1302,7 +1302,7 @@ impl Instruction for SAStore {
             Ok(SAStore {})
         }
     }
-    fn execute(&mut self, jvm : &mut JVM) -> Result<(), Error> {
+    fn execute(&mut self, jvm : &mut JVM) -> Result<Flow, Error> {
         let thread = access_macros::current_thread_mut!(jvm);
         let frame = access_macros::current_frame_mut!(thread);
         let val = match frame.op_stack.pop() {
@@ -1329,7 +1329,7 @@ impl Instruction for SAStore {
             return Err(Error::IncorrectReferenceType(Opcode::SASTORE));
         }
         unsafe {Rc::get_mut_unchecked(&mut array)}.set(*index.as_int()? as usize, val)?;
-        Ok(())
+        Ok(Flow::Next)
     }
     fn as_any(&self) -> &dyn Any {
         self
@@ -0,0 +1,1098 @@
+// Support for invokedynamic, dynamic constants, and the method handles they use. See CallSite::link for how a call site
+// gets linked. The bootstraps javac uses for string concatenation, records, lambdas and type switches need java.lang.invoke
+// machinery we can't run, so we recognize those and link their call sites natively. Any other bootstrap method gets called
+// for real (see call_bootstrap), and we keep the target of the CallSite it gives back.
+
+use crate::attributes::BootstrapMethod;
+use crate::class::Class;
+use crate::class::classfile::ClassFile;
+use crate::class::customclass::same_class;
+use crate::class::rt_const_pool::FieldRef;
+use crate::class::classfile::MethodInfo;
+use crate::class::natives::lambda::LambdaClass;
+use crate::constant_pool::{Entry, NameAndType, ReferenceKind};
+use crate::errorcodes::{Error, Opcode};
+use crate::flags;
+use crate::reference::{Reference, Monitor};
+use crate::reference::object::{self, Object, natives};
+use crate::value::Value;
+
+use std::fmt;
+use std::rc::Rc;
+
+use super::JVM;
+
+// These come from java.lang.invoke.LambdaMetafactory, and are used by altMetafactory.
+const FLAG_SERIALIZABLE: i32 = 1 << 0;
+const FLAG_MARKERS: i32 = 1 << 1;
+const FLAG_BRIDGES: i32 = 1 << 2;
+
+// A direct method handle, resolved from a CONSTANT_MethodHandle_info.
+// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.5
+#[derive(Clone)]
+pub struct MethodHandle {
+    pub kind: ReferenceKind,
+    pub class: Rc<dyn Class>,
+    pub method: MethodInfo,
+    pub name: String,
+    pub descriptor: String,
+}
+
+impl MethodHandle {
+    pub fn resolve(jvm: &mut JVM, current_class: &Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<MethodHandle, Error> {
+        let class_file = current_class.get_class_file();
+        let handle_info = class_file.cp_entry(index)?.as_method_handle()?;
+        let (ref_info, is_interface) = match class_file.cp_entry(handle_info.ref_index)? {
+            Entry::MethodRef(refinfo) => (refinfo, false),
+            Entry::InterfaceMethodRef(refinfo) => (refinfo, true),
+            // TODO: Handles to fields. Nothing javac emits for invokedynamic needs them.
+            _ => return Err(Error::Todo(opcode)),
+        };
+        let c_info = class_file.cp_entry(ref_info.class_index)?.as_class()?;
+        let c_name = class_file.cp_entry(*c_info)?.as_utf8()?;
+        let name_and_type = class_file.cp_entry(ref_info.name_and_type_index)?.as_name_and_type()?;
+        let name = class_file.cp_entry(name_and_type.name_index)?.as_utf8()?;
+        let descriptor = class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?;
+        let c = jvm.resolve_class_reference(c_name)?;
+        MethodHandle::find(jvm, handle_info.ref_kind, c, is_interface, name, descriptor, opcode)
+    }
+    // Resolves a method in c, and checks that it's the kind of method that kind calls. This is what both the constant pool 
+    // and Lookup's find methods come down to.
+    pub fn find(jvm: &mut JVM, kind: ReferenceKind, c: Rc<dyn Class>, is_interface: bool, name: &str, descriptor: &str, opcode: Opcode) 
+    -> Result<MethodHandle, Error> {
+        let (class, method) = if is_interface {
+            jvm.resolve_interface_method(c, name, descriptor, opcode.clone())?
+        } else {
+            jvm.resolve_class_method(c, name, descriptor, opcode.clone())?
+        };
+        let is_static = (method.access_flags.flags & flags::method::ACC_STATIC) > 0;
+        match kind {
+            ReferenceKind::RefInvokeStatic => if !is_static {
+                return Err(Error::IncompatibleClassChangeError(opcode));
+            },
+            ReferenceKind::RefInvokeVirtual | ReferenceKind::RefInvokeSpecial | ReferenceKind::RefInvokeInterface => if is_static {
+                return Err(Error::IncompatibleClassChangeError(opcode));
+            },
+            ReferenceKind::RefNewInvokeSpecial => if name != "<init>" {
+                return Err(Error::IllegalReferenceKind);
+            },
+            _ => return Err(Error::IllegalReferenceKind),
+        }
+        Ok(MethodHandle { kind, class, method, name: String::from(name), descriptor: String::from(descriptor) })
+    }
+    // Calls the method this handle refers to, using the arguments that are already on the stack.
+    // They have to be exactly what the method takes, see invoke_as for when they might not be.
+    pub fn invoke(&self, jvm: &mut JVM) -> Result<(), Error> {
+        match self.kind {
+            ReferenceKind::RefInvokeStatic => {
+                jvm.initialize_class(Rc::clone(&self.class))?;
+                Rc::clone(&self.class).exec_method(jvm, &self.method)?;
+            },
+            ReferenceKind::RefInvokeVirtual | ReferenceKind::RefInvokeInterface => {
+                let num_args = self.method.num_args(&self.class.get_class_file())?;
+                let thread = current_thread_mut!(jvm);
+                let frame = current_frame_mut!(thread);
+                let receiver = match frame.op_stack.len().checked_sub(num_args + 1) {
+                    Some(index) => frame.op_stack[index].as_reference()?,
+                    None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+                };
+                let receiver = match receiver {
+                    Reference::Null => return Err(Error::NullPointerException(Opcode::MethodInvoke)),
+                    Reference::Object(o, _) => o.class(),
+                    _ => return Err(Error::IncorrectReferenceType(Opcode::MethodInvoke)),
+                };
+                let (class, method) = jvm.select_method(receiver, Rc::clone(&self.class), self.method.clone(), &self.name, &self.descriptor, Opcode::MethodInvoke)?;
+                jvm.execute_on_object(&method, class)?;
+            },
+            ReferenceKind::RefInvokeSpecial => jvm.execute_on_object(&self.method, Rc::clone(&self.class))?,
+            ReferenceKind::RefNewInvokeSpecial => {
+                // Like new, dup, and then invokespecial.
+                jvm.initialize_class(Rc::clone(&self.class))?;
+                let obj = object::new_object_with_name(self.class.get_class_file().name(), jvm)?;
+                let obj = Value::Reference(Reference::Object(obj, Rc::new(Monitor::new())));
+                let num_args = self.method.num_args(&self.class.get_class_file())?;
+                let thread = current_thread_mut!(jvm);
+                let frame = current_frame_mut!(thread);
+                let index = match frame.op_stack.len().checked_sub(num_args) {
+                    Some(index) => index,
+                    None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+                };
+                frame.op_stack.insert(index, obj.clone());
+                frame.op_stack.insert(index, obj);
+                jvm.execute_on_object(&self.method, Rc::clone(&self.class))?;
+            },
+            _ => return Err(Error::IllegalReferenceKind),
+        }
+        Ok(())
+    }
+    // Like invoke, but the arguments on top of the stack and the return value have the types in call_type, so they get
+    // boxed, unboxed or widened to what the method wants, like MethodHandle.asType would. Anything under those arguments,
+    // like what a lambda captured, gets passed as it is.
+    pub fn invoke_as(&self, jvm: &mut JVM, call_type: &str) -> Result<(), Error> {
+        let own_type = self.type_descriptor();
+        if own_type == call_type {
+            return self.invoke(jvm);
+        }
+        let call_params = parameter_descriptors(call_type)?;
+        let own_params = parameter_descriptors(&own_type)?;
+        let own_params = match own_params.len().checked_sub(call_params.len()) {
+            Some(skipped) => &own_params[skipped..],
+            None => return Err(Error::BootstrapMethodError(Opcode::MethodInvoke, format!("can't call {own_type} as {call_type}"))),
+        };
+        if call_params != own_params {
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            let args = match frame.op_stack.len().checked_sub(call_params.len()) {
+                Some(index) => frame.op_stack.split_off(index),
+                None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+            };
+            let mut adapted = Vec::with_capacity(args.len());
+            for ((arg, from), to) in args.into_iter().zip(&call_params).zip(own_params) {
+                adapted.push(convert_value(jvm, arg, from, to)?);
+            }
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            frame.op_stack.extend(adapted);
+        }
+        let call_return = return_descriptor(call_type);
+        let own_return = return_descriptor(&own_type);
+        if call_return == own_return || (!is_primitive(call_return) && !is_primitive(own_return)) {
+            return self.invoke(jvm);
+        }
+        // The return value has to be changed once the method is done, so we have to run it until then.
+        let depth = jvm.current_thread().m_stack.len();
+        self.invoke(jvm)?;
+        finish_call(jvm, depth)?;
+        let thread = current_thread_mut!(jvm);
+        let frame = current_frame_mut!(thread);
+        if own_return == "V" {
+            return Err(Error::BootstrapMethodError(Opcode::MethodInvoke, format!("can't call {own_type} as {call_type}")));
+        }
+        let value = match frame.op_stack.pop() {
+            Some(value) => value,
+            None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+        };
+        if call_return != "V" {
+            let value = convert_value(jvm, value, own_return, call_return)?;
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            frame.op_stack.push(value);
+        }
+        Ok(())
+    }
+    // The descriptor of what calling this handle looks like, which is what MethodHandle.type() gives.
+    pub fn type_descriptor(&self) -> String {
+        let class_file = self.class.get_class_file();
+        let owner = class_file.name();
+        match self.kind {
+            ReferenceKind::RefInvokeStatic => self.descriptor.clone(),
+            ReferenceKind::RefNewInvokeSpecial => {
+                let params = &self.descriptor[..self.descriptor.rfind(')').unwrap() + 1];
+                format!("{params}L{owner};")
+            },
+            _ => format!("(L{owner};{}", &self.descriptor[1..]),
+        }
+    }
+}
+
+impl fmt::Debug for MethodHandle {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "MethodHandle {:?} {}.{}{}", self.kind, self.class.get_class_file().name(), self.name, self.descriptor)
+    }
+}
+
+// A static argument to a bootstrap method. When we link call sites ourselves these never need to become Java objects,
+// but they get turned into them (see into_value) when we call a bootstrap method for real.
+#[derive(Debug, Clone)]
+pub enum BootstrapArgument {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+    Class(String),
+    MethodType(String),
+    // Boxed, since handles are a lot bigger than everything else.
+    MethodHandle(Box<MethodHandle>),
+    Dynamic(Value<dyn Class, dyn Object>),
+}
+
+impl BootstrapArgument {
+    pub fn resolve(jvm: &mut JVM, current_class: &Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<BootstrapArgument, Error> {
+        let class_file = current_class.get_class_file();
+        Ok(match class_file.cp_entry(index)? {
+            Entry::Integer(i) => BootstrapArgument::Int(*i),
+            Entry::Long(l) => BootstrapArgument::Long(*l),
+            Entry::Float(f) => BootstrapArgument::Float(*f),
+            Entry::Double(d) => BootstrapArgument::Double(*d),
+            Entry::String(s) => BootstrapArgument::String(class_file.cp_entry(*s)?.as_utf8()?.clone()),
+            Entry::Class(c) => BootstrapArgument::Class(class_file.cp_entry(*c)?.as_utf8()?.clone()),
+            Entry::MethodType(d) => BootstrapArgument::MethodType(class_file.cp_entry(*d)?.as_utf8()?.clone()),
+            Entry::MethodHandle(_) => BootstrapArgument::MethodHandle(Box::new(MethodHandle::resolve(jvm, current_class, index, opcode)?)),
+            Entry::Dynamic(_) => BootstrapArgument::Dynamic(resolve_dynamic_constant(jvm, current_class, index, opcode)?),
+            _ => return Err(Error::IllegalConstantLoad(opcode)),
+        })
+    }
+    // Makes the value that gets passed for a parameter with the type in descriptor, so primitives get boxed when it's an Object.
+    pub fn into_value(self, jvm: &mut JVM, descriptor: &str) -> Result<Value<dyn Class, dyn Object>, Error> {
+        let value = match self {
+            BootstrapArgument::Int(i) => Value::Int(i),
+            BootstrapArgument::Long(l) => Value::Long(l),
+            BootstrapArgument::Float(f) => Value::Float(f),
+            BootstrapArgument::Double(d) => Value::Double(d),
+            BootstrapArgument::String(s) => {
+                let s = natives::string::String::new_from_string(s, jvm)?;
+                Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new())))
+            },
+            BootstrapArgument::Class(name) => Value::Reference(jvm.class_mirror_from_name(&name)?),
+            BootstrapArgument::MethodType(descriptor) => Value::Reference(method_type_object(jvm, &descriptor)?),
+            BootstrapArgument::MethodHandle(handle) => Value::Reference(method_handle_object(jvm, *handle)?),
+            BootstrapArgument::Dynamic(value) => value,
+        };
+        let value_type = match &value {
+            Value::Long(_) => "J",
+            Value::Float(_) => "F",
+            Value::Double(_) => "D",
+            Value::Reference(_) => "Ljava/lang/Object;",
+            _ => "I",
+        };
+        convert_value(jvm, value, value_type, descriptor)
+    }
+}
+
+pub enum CallSiteTarget {
+    // From LambdaMetafactory. Every call makes a new instance of class, which holds onto the arguments and passes them to target.
+    Lambda { class: Rc<dyn Class>, target: MethodHandle },
+    // From StringConcatFactory. In the recipe, \u{1} stands for the next argument and \u{2} stands for the next constant.
+    StringConcat { recipe: String, constants: Vec<String> },
+    // From ObjectMethods, which records use for toString, hashCode and equals. The fields are the record's components, in order.
+    ObjectMethod { method: String, class: Rc<dyn Class>, fields: Vec<NameAndType> },
+    // From SwitchBootstraps.typeSwitch, for switches on patterns. Gives back the index of the first label the value matches.
+    TypeSwitch { labels: Vec<BootstrapArgument> },
+    // From any other bootstrap method. It's whatever the CallSite it gave back was targeting at the time.
+    Handle(MethodHandle),
+}
+
+// A linked invokedynamic call site. Like a ConstantCallSite, the target never changes once it's linked.
+pub struct CallSite {
+    pub descriptor: String,
+    pub target: CallSiteTarget,
+}
+
+impl CallSite {
+    // Resolves the dynamically-computed call site at index in current_class's constant pool.
+    // See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.6
+    pub fn link(jvm: &mut JVM, current_class: Rc<dyn Class>, index: u16) -> Result<CallSite, Error> {
+        let class_file = current_class.get_class_file();
+        let invoke_dynamic_info = class_file.cp_entry(index)?.as_invoke_dynamic()?;
+        let name_and_type = class_file.cp_entry(invoke_dynamic_info.name_and_type_index)?.as_name_and_type()?;
+        let name = class_file.cp_entry(name_and_type.name_index)?.as_utf8()?.clone();
+        let descriptor = class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?.clone();
+        let bootstrap_method = bootstrap_method(&class_file, invoke_dynamic_info.bootstrap_method_attr_index, Opcode::INVOKEDYNAMIC)?;
+        // String concatenation is everywhere, so it gets linked before we resolve anything, and never needs java.lang.invoke.
+        if let Some(target) = CallSite::link_string_concat(&class_file, bootstrap_method, &descriptor)? {
+            return Ok(CallSite { descriptor, target });
+        }
+        if let Some(target) = CallSite::link_object_methods(jvm, &class_file, bootstrap_method, &name)? {
+            return Ok(CallSite { descriptor, target });
+        }
+        let bootstrap_handle = MethodHandle::resolve(jvm, &current_class, bootstrap_method.bootstrap_method_ref, Opcode::INVOKEDYNAMIC)?;
+        let mut args = Vec::new();
+        for arg in &bootstrap_method.bootstrap_arguments {
+            args.push(BootstrapArgument::resolve(jvm, &current_class, *arg, Opcode::INVOKEDYNAMIC)?);
+        }
+        let bootstrap_class_file = bootstrap_handle.class.get_class_file();
+        let target = match (bootstrap_class_file.name(), bootstrap_handle.name.as_str()) {
+            ("java/lang/invoke/LambdaMetafactory", "metafactory" | "altMetafactory") => {
+                CallSite::link_lambda(jvm, &current_class, &name, &descriptor, &args)?
+            },
+            ("java/lang/runtime/SwitchBootstraps", "typeSwitch") => {
+                if parameter_descriptors(&descriptor)?.len() != 2 || return_descriptor(&descriptor) != "I" {
+                    return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, format!("typeSwitch can't make {descriptor}")));
+                }
+                if !args.iter().all(|l| matches!(l, BootstrapArgument::Class(_) | BootstrapArgument::String(_) | BootstrapArgument::Int(_))) {
+                    return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("typeSwitch labels have to be classes, strings or ints")));
+                }
+                CallSiteTarget::TypeSwitch { labels: args }
+            },
+            _ => CallSite::link_with_bootstrap(jvm, &current_class, &bootstrap_handle, name, &descriptor, args)?,
+        };
+        Ok(CallSite { descriptor, target })
+    }
+    // Links call sites bootstrapped by ObjectMethods, straight from the constant pool, since its arguments are handles to
+    // the record's fields. Gives back None for any other bootstrap method.
+    fn link_object_methods(jvm: &mut JVM, class_file: &ClassFile, bootstrap_method: &BootstrapMethod, name: &str) -> Result<Option<CallSiteTarget>, Error> {
+        let handle_info = class_file.cp_entry(bootstrap_method.bootstrap_method_ref)?.as_method_handle()?;
+        let method_ref = match class_file.cp_entry(handle_info.ref_index)? {
+            Entry::MethodRef(refinfo) => refinfo,
+            _ => return Ok(None),
+        };
+        let c_info = class_file.cp_entry(method_ref.class_index)?.as_class()?;
+        if class_file.cp_entry(*c_info)?.as_utf8()? != "java/lang/runtime/ObjectMethods" {
+            return Ok(None);
+        }
+        let bad_arguments = || Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("bad arguments to ObjectMethods"));
+        let (record, names) = match bootstrap_method.bootstrap_arguments[..] {
+            [record, names, ..] => match (class_file.cp_entry(record)?, class_file.cp_entry(names)?) {
+                (Entry::Class(record), Entry::String(names)) => (class_file.cp_entry(*record)?.as_utf8()?, class_file.cp_entry(*names)?.as_utf8()?),
+                _ => return Err(bad_arguments()),
+            },
+            _ => return Err(bad_arguments()),
+        };
+        let class = jvm.resolve_class_reference(record)?;
+        let record_file = class.get_class_file();
+        let mut fields = Vec::new();
+        // Each component has a field with the same name.
+        for component in names.split(';').filter(|n| !n.is_empty()) {
+            let mut descriptor = None;
+            for field in &record_file.fields {
+                if record_file.cp_entry(field.name_index)?.as_utf8()? == component {
+                    descriptor = Some(record_file.cp_entry(field.descriptor_index)?.as_utf8()?.clone());
+                }
+            }
+            match descriptor {
+                Some(descriptor) => fields.push(NameAndType { name: String::from(component), descriptor }),
+                None => return Err(Error::NoSuchFieldError(Opcode::INVOKEDYNAMIC)),
+            }
+        }
+        match name {
+            "toString" | "hashCode" | "equals" => Ok(Some(CallSiteTarget::ObjectMethod { method: String::from(name), class, fields })),
+            _ => Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, format!("ObjectMethods can't make {name}"))),
+        }
+    }
+    // Any other bootstrap method gets called for real, and has to give back a CallSite. We only keep what it's targeting,
+    // so the call site acts like a ConstantCallSite even if the bootstrap method made some other kind.
+    fn link_with_bootstrap(jvm: &mut JVM, current_class: &Rc<dyn Class>, bootstrap_handle: &MethodHandle, name: String, descriptor: &str, 
+        args: Vec<BootstrapArgument>) -> Result<CallSiteTarget, Error> {
+        let call_site_type = method_type_object(jvm, descriptor)?;
+        let call_site = call_bootstrap(jvm, current_class, bootstrap_handle, name, call_site_type, args, Opcode::INVOKEDYNAMIC)?;
+        let target = match call_site.to_reference()? {
+            Reference::Object(o, _) => match o.as_any().downcast_ref::<natives::call_site::CallSite>() {
+                Some(call_site) => call_site.target().clone(),
+                None => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("bootstrap method didn't give back a CallSite"))),
+            },
+            _ => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("bootstrap method didn't give back a CallSite"))),
+        };
+        let target = match target {
+            Reference::Object(o, _) => match o.as_any().downcast_ref::<natives::method_handle::MethodHandle>() {
+                Some(h) => h.handle().clone(),
+                None => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("call site target isn't a direct method handle"))),
+            },
+            _ => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("call site has no target"))),
+        };
+        // Same as the JVM, the target has to take exactly what the call site passes.
+        if target.type_descriptor() != descriptor {
+            return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC,
+                format!("call site target has type {} instead of {descriptor}", target.type_descriptor())));
+        }
+        Ok(CallSiteTarget::Handle(target))
+    }
+    // Links call sites bootstrapped by StringConcatFactory, straight from the constant pool. Gives back None for any other bootstrap method.
+    fn link_string_concat(class_file: &ClassFile, bootstrap_method: &BootstrapMethod, descriptor: &str) -> Result<Option<CallSiteTarget>, Error> {
+        let handle_info = class_file.cp_entry(bootstrap_method.bootstrap_method_ref)?.as_method_handle()?;
+        let method_ref = match class_file.cp_entry(handle_info.ref_index)? {
+            Entry::MethodRef(refinfo) => refinfo,
+            _ => return Ok(None),
+        };
+        let c_info = class_file.cp_entry(method_ref.class_index)?.as_class()?;
+        if class_file.cp_entry(*c_info)?.as_utf8()? != "java/lang/invoke/StringConcatFactory" {
+            return Ok(None);
+        }
+        let name_and_type = class_file.cp_entry(method_ref.name_and_type_index)?.as_name_and_type()?;
+        match class_file.cp_entry(name_and_type.name_index)?.as_utf8()?.as_str() {
+            "makeConcatWithConstants" => {
+                let mut args = bootstrap_method.bootstrap_arguments.iter();
+                let recipe = match args.next() {
+                    Some(index) => match class_file.cp_entry(*index)? {
+                        Entry::String(s) => class_file.cp_entry(*s)?.as_utf8()?.clone(),
+                        _ => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("recipe must be a String"))),
+                    },
+                    None => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("makeConcatWithConstants needs a recipe"))),
+                };
+                let mut constants = Vec::new();
+                for index in args {
+                    constants.push(match class_file.cp_entry(*index)? {
+                        Entry::String(s) => class_file.cp_entry(*s)?.as_utf8()?.clone(),
+                        Entry::Integer(i) => i.to_string(),
+                        Entry::Long(l) => l.to_string(),
+                        Entry::Float(f) => format_float(*f),
+                        Entry::Double(d) => format_double(*d),
+                        _ => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("unsupported constant in string concatenation"))),
+                    });
+                }
+                Ok(Some(CallSiteTarget::StringConcat { recipe, constants }))
+            },
+            "makeConcat" => {
+                let recipe = "\u{1}".repeat(parameter_descriptors(descriptor)?.len());
+                Ok(Some(CallSiteTarget::StringConcat { recipe, constants: Vec::new() }))
+            },
+            _ => Ok(None),
+        }
+    }
+    // This is what LambdaMetafactory.metafactory and altMetafactory do, except that we make a LambdaClass instead of spinning bytecode.
+    fn link_lambda(jvm: &mut JVM, current_class: &Rc<dyn Class>, name: &str, descriptor: &str, args: &[BootstrapArgument]) -> Result<CallSiteTarget, Error> {
+        let (interface_method_type, target) = match (args.first(), args.get(1)) {
+            (Some(BootstrapArgument::MethodType(t)), Some(BootstrapArgument::MethodHandle(h))) => (t, &**h),
+            _ => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("bad arguments to LambdaMetafactory"))),
+        };
+        // The call site returns an instance of the functional interface.
+        let return_type = match descriptor.rfind(')') {
+            Some(end) => &descriptor[end + 1..],
+            None => return Err(Error::IllegalDescriptor),
+        };
+        if return_type.len() < 3 || !return_type.starts_with('L') || !return_type.ends_with(';') {
+            return Err(Error::IllegalDescriptor);
+        }
+        let mut interfaces = vec![String::from(&return_type[1..return_type.len() - 1])];
+        let mut methods = vec![(String::from(name), interface_method_type.clone())];
+        if let Some(BootstrapArgument::Int(flags)) = args.get(3) {
+            let mut rest = args[4..].iter();
+            if (flags & FLAG_MARKERS) > 0 {
+                let count = match rest.next() {
+                    Some(BootstrapArgument::Int(count)) => *count,
+                    _ => 0,
+                };
+                for _ in 0..count {
+                    if let Some(BootstrapArgument::Class(marker)) = rest.next() {
+                        interfaces.push(marker.clone());
+                    }
+                }
+            }
+            if (flags & FLAG_BRIDGES) > 0 {
+                let count = match rest.next() {
+                    Some(BootstrapArgument::Int(count)) => *count,
+                    _ => 0,
+                };
+                for _ in 0..count {
+                    if let Some(BootstrapArgument::MethodType(bridge)) = rest.next() {
+                        methods.push((String::from(name), bridge.clone()));
+                    }
+                }
+            }
+            if (flags & FLAG_SERIALIZABLE) > 0 {
+                interfaces.push(String::from("java/io/Serializable"));
+            }
+        }
+        for interface in &interfaces {
+            jvm.resolve_class_reference(interface)?;
+        }
+        // Real lambda classes are hidden, so they don't need unique names, but ours get put in the loaded classes.
+        let class_name = {
+            let mut count = 0;
+            loop {
+                let class_name = format!("{}$$Lambda${count}", current_class.get_class_file().name());
+                if !jvm.m_loaded_classes.contains_key(&class_name) {
+                    break class_name;
+                }
+                count += 1;
+            }
+        };
+        let class_file = LambdaClass::generate_class_file(&class_name, &interfaces, &methods);
+        let class: Rc<dyn Class> = Rc::new(<LambdaClass as Class>::new(class_file, jvm)?);
+        jvm.m_loaded_classes.insert(class_name, Rc::clone(&class));
+        Ok(CallSiteTarget::Lambda { class, target: target.clone() })
+    }
+    // Runs the target of this call site, with the arguments from the call site's descriptor on the stack.
+    pub fn invoke(&self, jvm: &mut JVM) -> Result<(), Error> {
+        if let CallSiteTarget::Handle(target) = &self.target {
+            // The arguments are already where the target wants them.
+            return target.invoke(jvm);
+        }
+        let arg_types = parameter_descriptors(&self.descriptor)?;
+        let thread = current_thread_mut!(jvm);
+        let frame = current_frame_mut!(thread);
+        let args = match frame.op_stack.len().checked_sub(arg_types.len()) {
+            Some(index) => frame.op_stack.split_off(index),
+            None => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+        };
+        let result = match &self.target {
+            CallSiteTarget::Lambda { class, target } => {
+                jvm.reserve_heap(natives::lambda::Lambda::heap_size_of(args.len()))?;
+                let lambda = natives::lambda::Lambda::new_with_target(Rc::clone(class), target.clone(), args) as Rc<dyn Object>;
+                jvm.heap.track_object(&lambda);
+                Value::Reference(Reference::Object(lambda, Rc::new(Monitor::new())))
+            },
+            CallSiteTarget::StringConcat { recipe, constants } => {
+                let mut s = String::new();
+                let mut args = args.into_iter().zip(arg_types.iter());
+                let mut constants = constants.iter();
+                for c in recipe.chars() {
+                    match c {
+                        '\u{1}' => match args.next() {
+                            Some((arg, arg_type)) => s.push_str(&concat_value(jvm, arg, arg_type)?),
+                            None => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("not enough arguments for recipe"))),
+                        },
+                        '\u{2}' => match constants.next() {
+                            Some(constant) => s.push_str(constant),
+                            None => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, String::from("not enough constants for recipe"))),
+                        },
+                        c => s.push(c),
+                    }
+                }
+                let s = natives::string::String::new_from_string(s, jvm)? as Rc<dyn Object>;
+                Value::Reference(Reference::Object(s, Rc::new(Monitor::new())))
+            },
+            CallSiteTarget::ObjectMethod { method, class, fields } => object_method(jvm, method, class, fields, args)?,
+            CallSiteTarget::TypeSwitch { labels } => type_switch(jvm, labels, args)?,
+            CallSiteTarget::Handle(_) => unreachable!("call sites with a handle are invoked before the arguments are taken off"),
+        };
+        let thread = current_thread_mut!(jvm);
+        let frame = current_frame_mut!(thread);
+        frame.op_stack.push(result);
+        Ok(())
+    }
+}
+
+impl fmt::Debug for CallSite {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.target {
+            CallSiteTarget::Lambda { class, target } => write!(f, "CallSite {} -> {} ({:?})", self.descriptor, class.get_class_file().name(), target),
+            CallSiteTarget::StringConcat { recipe, .. } => write!(f, "CallSite {} -> concat {:?}", self.descriptor, recipe),
+            CallSiteTarget::ObjectMethod { method, class, .. } => write!(f, "CallSite {} -> {}.{}", self.descriptor, class.get_class_file().name(), method),
+            CallSiteTarget::TypeSwitch { labels } => write!(f, "CallSite {} -> typeSwitch {:?}", self.descriptor, labels),
+            CallSiteTarget::Handle(target) => write!(f, "CallSite {} -> {:?}", self.descriptor, target),
+        }
+    }
+}
+
+// Splits up the parameters of a method descriptor, so "(I[JLjava/lang/String;)V" gives ["I", "[J", "Ljava/lang/String;"].
+pub fn parameter_descriptors(descriptor: &str) -> Result<Vec<&str>, Error> {
+    let end = match descriptor.find(')') {
+        Some(end) if descriptor.starts_with('(') => end,
+        _ => return Err(Error::IllegalDescriptor),
+    };
+    let params = &descriptor[1..end];
+    let mut descriptors = Vec::new();
+    let mut idx = 0;
+    while idx < params.len() {
+        let start = idx;
+        while params[idx..].starts_with('[') {
+            idx += 1;
+        }
+        if params[idx..].starts_with('L') {
+            idx += match params[idx..].find(';') {
+                Some(semicolon) => semicolon,
+                None => return Err(Error::IllegalDescriptor),
+            };
+        }
+        idx += 1;
+        if idx > params.len() {
+            return Err(Error::IllegalDescriptor);
+        }
+        descriptors.push(&params[start..idx]);
+    }
+    Ok(descriptors)
+}
+
+// The return type of a method descriptor, so "(I)Ljava/lang/String;" gives "Ljava/lang/String;".
+fn return_descriptor(descriptor: &str) -> &str {
+    match descriptor.rfind(')') {
+        Some(end) => &descriptor[end + 1..],
+        None => descriptor,
+    }
+}
+
+fn is_primitive(descriptor: &str) -> bool {
+    descriptor.len() == 1 && descriptor != "V"
+}
+
+// The class that boxes a primitive type, and the method that unboxes it again.
+fn wrapper(descriptor: &str) -> Option<(&'static str, &'static str)> {
+    Some(match descriptor {
+        "Z" => ("java/lang/Boolean", "booleanValue"),
+        "B" => ("java/lang/Byte", "byteValue"),
+        "C" => ("java/lang/Character", "charValue"),
+        "S" => ("java/lang/Short", "shortValue"),
+        "I" => ("java/lang/Integer", "intValue"),
+        "J" => ("java/lang/Long", "longValue"),
+        "F" => ("java/lang/Float", "floatValue"),
+        "D" => ("java/lang/Double", "doubleValue"),
+        _ => return None,
+    })
+}
+
+// Turns value, which has the type from, into the type to. Primitives get boxed or widened, boxes get unboxed,
+// and references are passed along as they are.
+fn convert_value(jvm: &mut JVM, value: Value<dyn Class, dyn Object>, from: &str, to: &str) -> Result<Value<dyn Class, dyn Object>, Error> {
+    match (is_primitive(from), is_primitive(to)) {
+        _ if from == to => Ok(value),
+        (true, true) => widen(value, from, to),
+        (true, false) => box_value(jvm, value, from),
+        (false, true) => unbox_value(jvm, value, to),
+        (false, false) => Ok(value),
+    }
+}
+
+// The widening primitive conversions, see https://docs.oracle.com/javase/specs/jls/se17/html/jls-5.html#jls-5.1.2
+fn widen(value: Value<dyn Class, dyn Object>, from: &str, to: &str) -> Result<Value<dyn Class, dyn Object>, Error> {
+    Ok(match (from, to) {
+        ("B" | "S" | "C", "I") | ("B", "S") => Value::Int(value.to_int()?),
+        ("B" | "S" | "C" | "I", "J") => Value::Long(value.to_int()? as i64),
+        ("B" | "S" | "C" | "I", "F") => Value::Float(value.to_int()? as f32),
+        ("B" | "S" | "C" | "I", "D") => Value::Double(value.to_int()? as f64),
+        ("J", "F") => Value::Float(value.to_long()? as f32),
+        ("J", "D") => Value::Double(value.to_long()? as f64),
+        ("F", "D") => Value::Double(value.to_float()? as f64),
+        _ => return Err(Error::BootstrapMethodError(Opcode::MethodInvoke, format!("can't convert {from} to {to}"))),
+    })
+}
+
+// Puts a primitive in its box. This uses the constructor instead of valueOf, so we don't depend on the caches being set up.
+fn box_value(jvm: &mut JVM, value: Value<dyn Class, dyn Object>, descriptor: &str) -> Result<Value<dyn Class, dyn Object>, Error> {
+    let (class_name, _) = match wrapper(descriptor) {
+        Some(w) => w,
+        None => return Err(Error::IllegalDescriptor),
+    };
+    let class = jvm.resolve_class_reference(class_name)?;
+    let constructor = format!("({descriptor})V");
+    let method = match JVM::find_declared_method(&class, "<init>", &constructor)? {
+        Some(m) => m,
+        None => return Err(Error::NoSuchMethodError(Opcode::MethodInvoke)),
+    };
+    let handle = MethodHandle { kind: ReferenceKind::RefNewInvokeSpecial, class, method, name: String::from("<init>"), descriptor: constructor };
+    match call_for_value(jvm, &handle, vec![value])? {
+        Some(boxed) => Ok(boxed),
+        None => Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+    }
+}
+
+// Takes a primitive out of its box, and widens it to the type in descriptor if it has to, like Integer to long.
+fn unbox_value(jvm: &mut JVM, value: Value<dyn Class, dyn Object>, descriptor: &str) -> Result<Value<dyn Class, dyn Object>, Error> {
+    let (class, class_name) = match value.as_reference()? {
+        Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
+        Reference::Object(o, _) => {
+            let class = o.class();
+            let class_name = String::from(class.get_class_file().name());
+            (class, class_name)
+        },
+        _ => return jvm.throw_exception_with_message("java/lang/ClassCastException", format!("can't unbox an array to {descriptor}")),
+    };
+    let boxed = ["Z", "B", "C", "S", "I", "J", "F", "D"].into_iter()
+        .find_map(|d| wrapper(d).filter(|(name, _)| *name == class_name).map(|(_, unbox)| (d, unbox)));
+    let (from, unbox) = match boxed {
+        Some(b) => b,
+        None => return jvm.throw_exception_with_message("java/lang/ClassCastException",
+            format!("{} can't be unboxed to {descriptor}", class_name.replace('/', "."))),
+    };
+    let unbox_descriptor = format!("(){from}");
+    let method = match JVM::find_declared_method(&class, unbox, &unbox_descriptor)? {
+        Some(m) => m,
+        None => return Err(Error::NoSuchMethodError(Opcode::MethodInvoke)),
+    };
+    let handle = MethodHandle { kind: ReferenceKind::RefInvokeVirtual, class, method, name: String::from(unbox), descriptor: unbox_descriptor };
+    let primitive = match call_for_value(jvm, &handle, vec![value])? {
+        Some(primitive) => primitive,
+        None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+    };
+    if from == descriptor {
+        Ok(primitive)
+    }
+    else {
+        widen(primitive, from, descriptor)
+    }
+}
+
+// What Double.toString gives. Like Rust, it uses the fewest digits that read back as the same double,
+// but it switches to E notation outside of 10^-3 to 10^7, and whole numbers still get a ".0".
+pub fn format_double(d: f64) -> String {
+    if d.is_nan() {
+        String::from("NaN")
+    }
+    else if d.is_infinite() {
+        String::from(if d > 0.0 { "Infinity" } else { "-Infinity" })
+    }
+    else if d == 0.0 || (1e-3..1e7).contains(&d.abs()) {
+        with_point(format!("{d}"))
+    }
+    else {
+        java_exponent(format!("{d:e}"))
+    }
+}
+
+// Same as format_double, but for Float.toString. A float has to stay one, so it gets the fewest digits that read back
+// as the same float. Widening first would print 0.1f as 0.10000000149011612.
+pub fn format_float(f: f32) -> String {
+    if f.is_nan() {
+        String::from("NaN")
+    }
+    else if f.is_infinite() {
+        String::from(if f > 0.0 { "Infinity" } else { "-Infinity" })
+    }
+    else if f == 0.0 || (1e-3..1e7).contains(&f.abs()) {
+        with_point(format!("{f}"))
+    }
+    else {
+        java_exponent(format!("{f:e}"))
+    }
+}
+
+fn with_point(s: String) -> String {
+    if s.contains('.') { s } else { s + ".0" }
+}
+
+// Rust writes 1e10 and 1.5e-5 where Java writes 1.0E10 and 1.5E-5.
+fn java_exponent(s: String) -> String {
+    match s.split_once('e') {
+        Some((mantissa, exponent)) => format!("{}E{exponent}", with_point(String::from(mantissa))),
+        None => s,
+    }
+}
+
+// What String.valueOf would give for value, which has the type descriptor.
+fn concat_value(jvm: &mut JVM, value: Value<dyn Class, dyn Object>, descriptor: &str) -> Result<String, Error> {
+    Ok(match &descriptor[0..1] {
+        "Z" => (value.to_int()? != 0).to_string(),
+        "C" => char::from_u32(value.to_int()? as u32).unwrap_or(char::REPLACEMENT_CHARACTER).to_string(),
+        "B" | "S" | "I" => value.to_int()?.to_string(),
+        "J" => value.to_long()?.to_string(),
+        "F" => format_float(value.to_float()?),
+        "D" => format_double(value.to_double()?),
+        _ => match value.to_reference()? {
+            Reference::Null => String::from("null"),
+            Reference::Object(o, m) => {
+                if let Some(s) = o.as_any().downcast_ref::<natives::string::String>() {
+                    return Ok(s.backing_string().clone());
+                }
+                // Anything else gets its toString called, which might mean running some Java code.
+                match call_object_method(jvm, Reference::Object(o, m), "toString", "()Ljava/lang/String;", Vec::new())? {
+                    Some(Value::Reference(Reference::Null)) => String::from("null"),
+                    Some(Value::Reference(Reference::Object(s, _))) => match s.as_any().downcast_ref::<natives::string::String>() {
+                        Some(s) => s.backing_string().clone(),
+                        None => return Err(Error::IncorrectReferenceType(Opcode::INVOKEDYNAMIC)),
+                    },
+                    Some(_) => return Err(Error::IncorrectReferenceType(Opcode::INVOKEDYNAMIC)),
+                    None => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+                }
+            },
+            // Same as Object.toString, which uses the array's own class rather than the one in the recipe.
+            Reference::Array(a, m) => format!("{}@{:x}", a.descriptor().replace('/', "."), m.identity_hash()),
+            Reference::Interface(..) => return Err(Error::IncorrectReferenceType(Opcode::INVOKEDYNAMIC)),
+        },
+    })
+}
+
+// Calls one of java.lang.Object's methods on receiver, like invokevirtual would, and runs it to completion.
+fn call_object_method(jvm: &mut JVM, receiver: Reference<dyn Class, dyn Object>, name: &str, descriptor: &str,
+    args: Vec<Value<dyn Class, dyn Object>>) -> Result<Option<Value<dyn Class, dyn Object>>, Error> {
+    let object_class = jvm.resolve_class_reference("java/lang/Object")?;
+    let method = match JVM::find_declared_method(&object_class, name, descriptor)? {
+        Some(m) => m,
+        None => return Err(Error::NoSuchMethodError(Opcode::INVOKEDYNAMIC)),
+    };
+    let handle = MethodHandle { kind: ReferenceKind::RefInvokeVirtual, class: object_class, method,
+        name: String::from(name), descriptor: String::from(descriptor) };
+    let mut call_args = vec![Value::Reference(receiver)];
+    call_args.extend(args);
+    call_for_value(jvm, &handle, call_args)
+}
+
+// What toString, hashCode or equals do for a record, see java.lang.runtime.ObjectMethods.
+fn object_method(jvm: &mut JVM, method: &str, class: &Rc<dyn Class>, fields: &[NameAndType], args: Vec<Value<dyn Class, dyn Object>>)
+-> Result<Value<dyn Class, dyn Object>, Error> {
+    let mut args = args.into_iter();
+    let receiver = match args.next() {
+        Some(receiver) => receiver.to_reference()?,
+        None => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+    };
+    let receiver = match receiver {
+        Reference::Object(o, _) => o,
+        Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
+        _ => return Err(Error::IncorrectReferenceType(Opcode::INVOKEDYNAMIC)),
+    };
+    let field_ref = |field: &NameAndType| FieldRef::new(Rc::clone(class), field.clone(), false);
+    Ok(match method {
+        "toString" => {
+            // Like Class.getSimpleName, so a record nested in Outer is still just Point[x=1, y=2].
+            let class_file = class.get_class_file();
+            let name = class_file.name();
+            let name = name[name.rfind(['/', '$']).map_or(0, |i| i + 1)..].trim_start_matches(|c: char| c.is_ascii_digit());
+            let mut s = format!("{name}[");
+            for (i, field) in fields.iter().enumerate() {
+                if i > 0 {
+                    s.push_str(", ");
+                }
+                let value = receiver.get_field(&field_ref(field), jvm)?;
+                s.push_str(&format!("{}={}", field.name, concat_value(jvm, value, &field.descriptor)?));
+            }
+            s.push(']');
+            let s = natives::string::String::new_from_string(s, jvm)? as Rc<dyn Object>;
+            Value::Reference(Reference::Object(s, Rc::new(Monitor::new())))
+        },
+        "hashCode" => {
+            let mut hash = 0i32;
+            for field in fields {
+                let value = receiver.get_field(&field_ref(field), jvm)?;
+                hash = hash.wrapping_mul(31).wrapping_add(hash_value(jvm, value, &field.descriptor)?);
+            }
+            Value::Int(hash)
+        },
+        "equals" => {
+            let other = match args.next() {
+                Some(other) => other.to_reference()?,
+                None => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+            };
+            let other = match other {
+                Reference::Object(o, _) if same_class(&o.class(), class) => o,
+                _ => return Ok(Value::Int(0)),
+            };
+            for field in fields {
+                let a = receiver.get_field(&field_ref(field), jvm)?;
+                let b = other.get_field(&field_ref(field), jvm)?;
+                if !values_equal(jvm, a, b, &field.descriptor)? {
+                    return Ok(Value::Int(0));
+                }
+            }
+            Value::Int(1)
+        },
+        _ => return Err(Error::BootstrapMethodError(Opcode::INVOKEDYNAMIC, format!("ObjectMethods can't make {method}"))),
+    })
+}
+
+// What SwitchBootstraps.typeSwitch's call sites do. The arguments are the value being switched on,
+// and the label to start looking from, which is how a guard that fails carries on to the next case.
+fn type_switch(jvm: &mut JVM, labels: &[BootstrapArgument], args: Vec<Value<dyn Class, dyn Object>>) -> Result<Value<dyn Class, dyn Object>, Error> {
+    let (target, start) = match &args[..] {
+        [target, start] => (target.as_reference()?, start.as_int()?),
+        _ => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+    };
+    let target_desc = match &target {
+        Reference::Null => return Ok(Value::Int(-1)),
+        Reference::Object(o, _) => format!("L{}", o.class().get_class_file().name()),
+        Reference::Interface(c, _) => format!("L{}", c.get_class_file().name()),
+        Reference::Array(a, _) => String::from(a.descriptor()),
+    };
+    // Int labels match boxed numbers and chars with the same int value.
+    let number = match &target {
+        Reference::Object(o, _) if o.class().get_class_file().name() == "java/lang/Character" => {
+            Some(unbox_value(jvm, Value::Reference(target.clone()), "C")?.to_int()?)
+        },
+        Reference::Object(o, _) => {
+            let class = o.class();
+            let is_number = !target_desc.starts_with('[') && jvm.is_subclass(class, "java/lang/Number")?;
+            match is_number {
+                true => Some(number_int_value(jvm, target.clone())?),
+                false => None,
+            }
+        },
+        _ => None,
+    };
+    for (index, label) in labels.iter().enumerate().skip((*start).max(0) as usize) {
+        let matched = match label {
+            BootstrapArgument::Class(name) => jvm.check_class(&target_desc, name)?,
+            BootstrapArgument::String(s) => match &target {
+                Reference::Object(o, _) => o.as_any().downcast_ref::<natives::string::String>().is_some_and(|t| t.backing_string() == s),
+                _ => false,
+            },
+            BootstrapArgument::Int(i) => number == Some(*i),
+            _ => false,
+        };
+        if matched {
+            return Ok(Value::Int(index as i32));
+        }
+    }
+    Ok(Value::Int(labels.len() as i32))
+}
+
+// Number.intValue, for any of the boxes.
+fn number_int_value(jvm: &mut JVM, number: Reference<dyn Class, dyn Object>) -> Result<i32, Error> {
+    let number_class = jvm.resolve_class_reference("java/lang/Number")?;
+    let method = match JVM::find_declared_method(&number_class, "intValue", "()I")? {
+        Some(m) => m,
+        None => return Err(Error::NoSuchMethodError(Opcode::INVOKEDYNAMIC)),
+    };
+    let handle = MethodHandle { kind: ReferenceKind::RefInvokeVirtual, class: number_class, method,
+        name: String::from("intValue"), descriptor: String::from("()I") };
+    match call_for_value(jvm, &handle, vec![Value::Reference(number)])? {
+        Some(value) => value.to_int(),
+        None => Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+    }
+}
+
+// Float.floatToIntBits and Double.doubleToLongBits, which make every NaN the same.
+fn float_bits(f: f32) -> i32 {
+    if f.is_nan() { 0x7fc00000 } else { f.to_bits() as i32 }
+}
+fn double_bits(d: f64) -> i64 {
+    if d.is_nan() { 0x7ff8000000000000 } else { d.to_bits() as i64 }
+}
+
+// The hashCode of value's box, or of the object it refers to, like Objects.hashCode.
+fn hash_value(jvm: &mut JVM, value: Value<dyn Class, dyn Object>, descriptor: &str) -> Result<i32, Error> {
+    Ok(match descriptor {
+        "Z" => if value.to_int()? != 0 { 1231 } else { 1237 },
+        "B" | "S" | "C" | "I" => value.to_int()?,
+        "J" => {
+            let l = value.to_long()?;
+            (l ^ ((l as u64) >> 32) as i64) as i32
+        },
+        "F" => float_bits(value.to_float()?),
+        "D" => {
+            let bits = double_bits(value.to_double()?);
+            (bits ^ ((bits as u64) >> 32) as i64) as i32
+        },
+        _ => match value.to_reference()? {
+            Reference::Null => 0,
+            r => match call_object_method(jvm, r, "hashCode", "()I", Vec::new())? {
+                Some(hash) => hash.to_int()?,
+                None => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+            },
+        },
+    })
+}
+
+// Whether two values of the same type are equal, like the box's equals, or Objects.equals for references.
+fn values_equal(jvm: &mut JVM, a: Value<dyn Class, dyn Object>, b: Value<dyn Class, dyn Object>, descriptor: &str) -> Result<bool, Error> {
+    Ok(match descriptor {
+        "Z" | "B" | "S" | "C" | "I" => a.to_int()? == b.to_int()?,
+        "J" => a.to_long()? == b.to_long()?,
+        "F" => float_bits(a.to_float()?) == float_bits(b.to_float()?),
+        "D" => double_bits(a.to_double()?) == double_bits(b.to_double()?),
+        _ => {
+            let (a, b) = (a.to_reference()?, b.to_reference()?);
+            if Reference::ptr_eq(&a, &b) {
+                true
+            }
+            else if let Reference::Null = a {
+                false
+            }
+            else {
+                match call_object_method(jvm, a, "equals", "(Ljava/lang/Object;)Z", vec![Value::Reference(b)])? {
+                    Some(equal) => equal.to_int()? != 0,
+                    None => return Err(Error::StackUnderflow(Opcode::INVOKEDYNAMIC)),
+                }
+            }
+        },
+    })
+}
+
+// Gets entry index of the class's BootstrapMethods attribute.
+fn bootstrap_method(class_file: &ClassFile, index: u16, opcode: Opcode) -> Result<&BootstrapMethod, Error> {
+    match &class_file.bootstrap_methods {
+        Some(m) => match m.get(index as usize) {
+            Some(m) => Ok(m),
+            None => Err(Error::MissingBootstrapTable(opcode)),
+        },
+        None => Err(Error::MissingBootstrapTable(opcode)),
+    }
+}
+
+// Calls handle with args, and runs it to completion. Gives back whatever it returned, if it wasn't void.
+// If it threw, the exception is left on the stack and we return Error::Exception.
+fn call_for_value(jvm: &mut JVM, handle: &MethodHandle, args: Vec<Value<dyn Class, dyn Object>>) -> Result<Option<Value<dyn Class, dyn Object>>, Error> {
+    let depth = jvm.current_thread().m_stack.len();
+    let thread = current_thread_mut!(jvm);
+    let frame = current_frame_mut!(thread);
+    frame.op_stack.extend(args);
+    handle.invoke(jvm)?;
+    finish_call(jvm, depth)?;
+    if handle.kind != ReferenceKind::RefNewInvokeSpecial && handle.descriptor.ends_with('V') {
+        return Ok(None);
+    }
+    let thread = current_thread_mut!(jvm);
+    let frame = current_frame_mut!(thread);
+    Ok(frame.op_stack.pop())
+}
+
+// Runs whatever a call pushed on top of depth frames until it returns.
+// If it threw, the exception is left on the stack and we return Error::Exception.
+fn finish_call(jvm: &mut JVM, depth: usize) -> Result<(), Error> {
+    if jvm.current_thread().m_stack.len() > depth {
+        if let Some(exception) = jvm.run_until_stack_depth(depth)? {
+            let thread = current_thread_mut!(jvm);
+            let frame = current_frame_mut!(thread);
+            frame.op_stack.push(exception);
+            return Err(Error::Exception);
+        }
+    }
+    Ok(())
+}
+
+// Makes the java.lang.invoke.MethodType for a method descriptor.
+pub fn method_type_object(jvm: &mut JVM, descriptor: &str) -> Result<Reference<dyn Class, dyn Object>, Error> {
+    let t = natives::method_type::MethodType::new_with_descriptor(descriptor, jvm)?;
+    Ok(Reference::Object(t as Rc<dyn Object>, Rc::new(Monitor::new())))
+}
+
+// Makes the java.lang.invoke.MethodHandle for one of our direct method handles.
+pub fn method_handle_object(jvm: &mut JVM, handle: MethodHandle) -> Result<Reference<dyn Class, dyn Object>, Error> {
+    let h = natives::method_handle::MethodHandle::new_with_handle(handle, jvm)?;
+    Ok(Reference::Object(h as Rc<dyn Object>, Rc::new(Monitor::new())))
+}
+
+// Resolves the dynamically-computed constant at index in current_class's constant pool.
+// See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-5.html#jvms-5.4.3.6
+// The bootstraps in ConstantBootstraps that don't need java.lang.invoke are done natively, anything else gets called, see call_bootstrap.
+pub fn resolve_dynamic_constant(jvm: &mut JVM, current_class: &Rc<dyn Class>, index: u16, opcode: Opcode) -> Result<Value<dyn Class, dyn Object>, Error> {
+    let class_file = current_class.get_class_file();
+    let dynamic_info = class_file.cp_entry(index)?.as_dynamic()?;
+    let name_and_type = class_file.cp_entry(dynamic_info.name_and_type_index)?.as_name_and_type()?;
+    let name = class_file.cp_entry(name_and_type.name_index)?.as_utf8()?.clone();
+    let descriptor = class_file.cp_entry(name_and_type.descriptor_index)?.as_utf8()?.clone();
+    let bootstrap_method = bootstrap_method(&class_file, dynamic_info.bootstrap_method_attr_index, opcode.clone())?;
+    let bootstrap_handle = MethodHandle::resolve(jvm, current_class, bootstrap_method.bootstrap_method_ref, opcode.clone())?;
+    let mut args = Vec::new();
+    for arg in &bootstrap_method.bootstrap_arguments {
+        args.push(BootstrapArgument::resolve(jvm, current_class, *arg, opcode.clone())?);
+    }
+    let bootstrap_class_file = bootstrap_handle.class.get_class_file();
+    let value = match (bootstrap_class_file.name(), bootstrap_handle.name.as_str()) {
+        ("java/lang/invoke/ConstantBootstraps", "nullConstant") => {
+            if is_primitive(&descriptor) {
+                return Err(Error::BootstrapMethodError(opcode, format!("nullConstant can't make a {descriptor}")));
+            }
+            Value::Reference(Reference::Null)
+        },
+        ("java/lang/invoke/ConstantBootstraps", "primitiveClass") => {
+            if name.len() != 1 || descriptor != "Ljava/lang/Class;" {
+                return Err(Error::BootstrapMethodError(opcode, format!("{name} isn't a primitive type")));
+            }
+            Value::Reference(jvm.class_mirror(&name)?)
+        },
+        ("java/lang/invoke/ConstantBootstraps", "getStaticFinal" | "enumConstant") => {
+            // The declaring class is either given, or is the type of the constant.
+            let declaring_class = match args.first() {
+                Some(BootstrapArgument::Class(c)) => c.clone(),
+                _ if descriptor.starts_with('L') => String::from(&descriptor[1..descriptor.len() - 1]),
+                _ => return Err(Error::BootstrapMethodError(opcode, format!("no declaring class for {name}"))),
+            };
+            let c = jvm.resolve_class_reference(&declaring_class)?;
+            let c = jvm.static_field_class(c, &name, &descriptor)?;
+            jvm.initialize_class(Rc::clone(&c))?;
+            c.get_static(&name, &descriptor, jvm)?
+        },
+        _ => {
+            let constant_type = jvm.class_mirror(&descriptor)?;
+            let value = call_bootstrap(jvm, current_class, &bootstrap_handle, name, constant_type, args, opcode)?;
+            // The bootstrap method might give back a box for a primitive constant, or the other way around.
+            convert_value(jvm, value, return_descriptor(&bootstrap_handle.descriptor), &descriptor)?
+        },
+    };
+    Ok(value)
+}
+
+// Calls a bootstrap method with a Lookup for current_class, which is the class with the invokedynamic or dynamic constant, 
+// then the name and type of what it's making, then its static arguments. Gives back what it returned.
+// Our Lookup only has lookupClass, findStatic, findVirtual and findConstructor (see natives::lookup), so a bootstrap 
+// method that needs anything else from it gets a warning and a null back.
+fn call_bootstrap(jvm: &mut JVM, current_class: &Rc<dyn Class>, bootstrap_handle: &MethodHandle, name: String, value_type: Reference<dyn Class, dyn Object>,
+    args: Vec<BootstrapArgument>, opcode: Opcode) -> Result<Value<dyn Class, dyn Object>, Error> {
+    let params = parameter_descriptors(&bootstrap_handle.descriptor)?;
+    if bootstrap_handle.kind != ReferenceKind::RefInvokeStatic || params.len() != args.len() + 3 {
+        return Err(Error::BootstrapMethodError(opcode, format!("can't call {}.{}{}",
+            bootstrap_handle.class.get_class_file().name(), bootstrap_handle.name, bootstrap_handle.descriptor)));
+    }
+    let lookup = natives::lookup::Lookup::new_for(Rc::clone(current_class), jvm)?;
+    let name = natives::string::String::new_from_string(name, jvm)?;
+    let mut call_args = vec![
+        Value::Reference(Reference::Object(lookup as Rc<dyn Object>, Rc::new(Monitor::new()))),
+        Value::Reference(Reference::Object(name as Rc<dyn Object>, Rc::new(Monitor::new()))),
+        Value::Reference(value_type),
+    ];
+    for (arg, param) in args.into_iter().zip(&params[3..]) {
+        call_args.push(arg.into_value(jvm, param)?);
+    }
+    match call_for_value(jvm, bootstrap_handle, call_args)? {
+        Some(value) => Ok(value),
+        None => Err(Error::BootstrapMethodError(opcode, String::from("bootstrap method returned void"))),
+    }
+}
@@ -0,0 +1,104 @@
+// Running each Java thread on an OS thread of its own, for --os-threads. Everything in the JVM is still Rcs and Cells,
+// so only the OS thread holding the lock in OsThreads gets to touch it, and it only lets go between turns.
+// That means monitors, volatile fields, and longs and doubles all behave the way the memory model says,
+// since every instruction happens entirely before or after every other one, and taking the lock makes the last turn's writes visible.
+// It also means two threads never actually run at the same time, but blocking in sleep, join, wait or on a monitor parks the OS thread
+// instead of going through the green thread scheduler. See JVM::run for that one, which is still the default.
+use crate::thread::ThreadState;
+
+use std::sync::{Condvar, Mutex};
+use std::thread::Scope;
+use std::time::Instant;
+
+use super::JVM;
+
+// How many instructions a thread gets to run each time it has the lock. Handing the lock over costs a lot more than switching green threads,
+// so turns are a lot longer than JVM::m_step_size.
+const TURN_SIZE: usize = 1000;
+
+struct Schedule {
+    // The ids of the Java threads that have an OS thread.
+    started: Vec<usize>,
+    done: bool,
+}
+
+struct OsThreads {
+    jvm: *mut JVM,
+    schedule: Mutex<Schedule>,
+    // Signalled at the end of every turn, since that's when whatever a thread is blocked on can change.
+    turn_over: Condvar,
+}
+
+// The JVM itself is only ever used with the schedule locked.
+unsafe impl Sync for OsThreads {}
+
+impl OsThreads {
+    fn run_thread<'scope>(&'scope self, scope: &'scope Scope<'scope, '_>, id: usize) {
+        let mut schedule = self.schedule.lock().unwrap();
+        loop {
+            if schedule.done {
+                return;
+            }
+            let jvm = unsafe { &mut *self.jvm };
+            let Some(index) = jvm.m_threads.iter().position(|t| t.id == id) else {
+                return;
+            };
+            jvm.m_thread_index = index;
+            jvm.step(TURN_SIZE);
+            if jvm.end_turn() {
+                schedule.done = true;
+            }
+            // Threads started during the turn get their own OS thread.
+            for thread in &jvm.m_threads {
+                if !schedule.started.contains(&thread.id) {
+                    schedule.started.push(thread.id);
+                    let id = thread.id;
+                    scope.spawn(move || self.run_thread(scope, id));
+                }
+            }
+            let Some(thread) = jvm.m_threads.iter().find(|t| t.id == id) else {
+                self.turn_over.notify_all();
+                return;
+            };
+            let deadline = match thread.state {
+                _ if thread.current_monitor.is_some() => None,
+                ThreadState::Runnable | ThreadState::Yielding => {
+                    // Let someone else have a turn, since the lock isn't fair and we'd probably just get it back.
+                    self.turn_over.notify_all();
+                    drop(schedule);
+                    std::thread::yield_now();
+                    schedule = self.schedule.lock().unwrap();
+                    continue;
+                },
+                ThreadState::Sleeping(until) => Some(until),
+                ThreadState::Joining(_, deadline) | ThreadState::Waiting(deadline) => deadline,
+            };
+            // We might be the last one into a deadlock, and nobody is ever going to wake us up from that.
+            if !schedule.done && deadline.is_none() && jvm.report_deadlocks() {
+                schedule.done = true;
+            }
+            self.turn_over.notify_all();
+            if schedule.done {
+                return;
+            }
+            schedule = match deadline {
+                Some(deadline) => self.turn_over.wait_timeout(schedule, deadline.saturating_duration_since(Instant::now())).unwrap().0,
+                None => self.turn_over.wait(schedule).unwrap(),
+            };
+        }
+    }
+}
+
+impl JVM {
+    // Same as run, but with an OS thread for each Java thread. Gives back once they've all finished, or the JVM is done.
+    pub fn run_on_os_threads(&mut self) {
+        let started = self.m_threads.iter().map(|t| t.id).collect::<Vec<usize>>();
+        let os_threads = OsThreads { jvm: self, schedule: Mutex::new(Schedule { started: started.clone(), done: false }), turn_over: Condvar::new() };
+        let os_threads = &os_threads;
+        std::thread::scope(|scope| {
+            for id in started {
+                scope.spawn(move || os_threads.run_thread(scope, id));
+            }
+        });
+    }
+}
@@ -1,4 +1,5 @@
 // Just a list of flags.
 pub static SHOULD_VERIFY: u8 = 1 << 0;
 pub static SHOULD_CONTROL_ACCESS: u8 = 1 << 1; 
-pub static SHOULD_BACKTRACE: u8 = 1 << 2;
\ No newline at end of file
+pub static SHOULD_BACKTRACE: u8 = 1 << 2;
+pub static SHOULD_USE_OS_THREADS: u8 = 1 << 3;
\ No newline at end of file
@@ -0,0 +1,199 @@
+// Thread dumps and deadlock detection, in the same format as HotSpot's, so they read like what jstack prints.
+// Since the threads are interpreted, we know exactly which monitor each one is stuck on, and who holds it.
+
+use crate::class::{Class, natives};
+use crate::errorcodes::Error;
+use crate::frame::Frame;
+use crate::reference::{Reference, Monitor};
+use crate::reference::object::Object;
+use crate::thread::{Thread, ThreadState};
+use crate::value::VarValue;
+
+use std::collections::HashSet;
+use std::fmt::Write;
+use std::rc::Rc;
+
+use super::JVM;
+
+impl JVM {
+    // Every thread's state and stack, like a HotSpot thread dump.
+    pub fn thread_dump(&mut self) -> Result<String, Error> {
+        let mut dump = String::from("Full thread dump:\n");
+        for index in 0..self.m_threads.len() {
+            let object = self.m_threads[index].object.clone();
+            let (name, tid, priority) = natives::thread::Thread::describe(self, &object)?;
+            let thread = &self.m_threads[index];
+            let daemon = if thread.is_daemon { " daemon" } else { "" };
+            let _ = writeln!(dump, "\n\"{name}\" #{tid}{daemon} prio={priority}");
+            let _ = writeln!(dump, "   java.lang.Thread.State: {}", state_name(thread));
+            dump.push_str(&stack_trace(thread));
+        }
+        Ok(dump)
+    }
+    // The cycles of threads that are each waiting for a monitor the next one holds, by index in m_threads.
+    // We only call it a deadlock once nothing else can run either, since a thread that can might still sort things out.
+    pub fn find_deadlocks(&self) -> Vec<Vec<usize>> {
+        if !self.m_threads.iter().all(|t| self.is_stuck(t)) {
+            return Vec::new();
+        }
+        let mut cycles = Vec::new();
+        let mut seen = HashSet::new();
+        for start in 0..self.m_threads.len() {
+            // Follow who each thread is waiting on, until we get somewhere we've been.
+            let mut path = Vec::new();
+            let mut index = Some(start);
+            while let Some(i) = index {
+                if !seen.insert(i) {
+                    break;
+                }
+                path.push(i);
+                index = self.blocking_thread(&self.m_threads[i]).and_then(|id| self.m_threads.iter().position(|t| t.id == id));
+            }
+            // If we stopped on a thread from an earlier path, its cycle has already been found.
+            if let Some(position) = index.and_then(|i| path.iter().position(|&p| p == i)) {
+                cycles.push(path.split_off(position));
+            }
+        }
+        cycles
+    }
+    // The report HotSpot prints when it finds deadlocks, with the stacks of every thread in them.
+    pub fn deadlock_report(&mut self, cycles: &[Vec<usize>]) -> Result<String, Error> {
+        let mut names = Vec::with_capacity(self.m_threads.len());
+        for index in 0..self.m_threads.len() {
+            let object = self.m_threads[index].object.clone();
+            names.push(natives::thread::Thread::describe(self, &object)?.0);
+        }
+        let mut report = String::new();
+        for cycle in cycles {
+            report.push_str("Found one Java-level deadlock:\n=============================\n");
+            for &index in cycle {
+                let thread = &self.m_threads[index];
+                let Some(monitor) = blocked_monitor(thread) else {
+                    continue;
+                };
+                let holder = self.m_threads.iter().position(|t| t.id == monitor.owned_thread.get()).map_or("", |i| names[i].as_str());
+                let _ = writeln!(report, "\"{}\":", names[index]);
+                let _ = writeln!(report, "  waiting to lock monitor {:#018x}{},", monitor_address(monitor),
+                    object_description(thread, monitor).map_or(String::new(), |d| format!(" ({d})")));
+                let _ = writeln!(report, "  which is held by \"{holder}\"");
+            }
+            report.push_str("\nJava stack information for the threads listed above:\n===================================================\n");
+            for &index in cycle {
+                let _ = writeln!(report, "\"{}\":", names[index]);
+                report.push_str(&stack_trace(&self.m_threads[index]));
+            }
+            report.push('\n');
+        }
+        if cycles.len() == 1 {
+            report.push_str("Found 1 deadlock.\n");
+        }
+        else {
+            let _ = writeln!(report, "Found {} deadlocks.", cycles.len());
+        }
+        Ok(report)
+    }
+    // Whether the thread can't go anywhere until another thread does something.
+    fn is_stuck(&self, thread: &Thread) -> bool {
+        if thread.interrupted {
+            return false;
+        }
+        self.blocking_thread(thread).is_some() || match thread.state {
+            ThreadState::Waiting(None) => thread.waiting_on.as_ref().is_some_and(|(m, _)| m.is_waiting(thread.id)),
+            ThreadState::Joining(id, None) => self.is_alive(id),
+            _ => false,
+        }
+    }
+    // The id of the thread holding the monitor this one is trying to get, if it's trying to get one.
+    fn blocking_thread(&self, thread: &Thread) -> Option<usize> {
+        let monitor = blocked_monitor(thread)?;
+        if monitor.entry_count.get() == 0 || monitor.owned_thread.get() == thread.id {
+            // It's free, so the thread gets it on its next turn.
+            return None;
+        }
+        Some(monitor.owned_thread.get())
+    }
+}
+
+fn state_name(thread: &Thread) -> &'static str {
+    if blocked_monitor(thread).is_some() {
+        return "BLOCKED (on object monitor)";
+    }
+    match thread.state {
+        ThreadState::Runnable | ThreadState::Yielding => "RUNNABLE",
+        ThreadState::Sleeping(_) => "TIMED_WAITING (sleeping)",
+        ThreadState::Joining(_, None) | ThreadState::Waiting(None) => "WAITING (on object monitor)",
+        ThreadState::Joining(_, Some(_)) | ThreadState::Waiting(Some(_)) => "TIMED_WAITING (on object monitor)",
+    }
+}
+
+// One line for each frame, innermost first, with the monitors the thread is waiting for or has entered under the frames they belong to.
+fn stack_trace(thread: &Thread) -> String {
+    let mut trace = String::new();
+    for (depth, frame) in thread.m_stack.iter().rev().enumerate() {
+        let _ = writeln!(trace, "\tat {}", frame_location(frame));
+        if depth == 0 {
+            if let Some(monitor) = &thread.current_monitor {
+                let _ = writeln!(trace, "\t- waiting to lock {}", monitor_name(thread, monitor));
+            }
+            if let Some((monitor, _)) = &thread.waiting_on {
+                let action = if monitor.is_waiting(thread.id) { "waiting on" } else { "waiting to re-lock in wait()" };
+                let _ = writeln!(trace, "\t- {action} {}", monitor_name(thread, monitor));
+            }
+        }
+        if let Some(monitor) = &frame.monitor {
+            let _ = writeln!(trace, "\t- locked {}", monitor_name(thread, monitor));
+        }
+    }
+    trace
+}
+
+// The monitor the thread needs before it can go on: the one it's blocked on, or the one it has to get back after being notified.
+fn blocked_monitor(thread: &Thread) -> Option<&Rc<Monitor>> {
+    match (&thread.current_monitor, &thread.waiting_on) {
+        (Some(monitor), _) => Some(monitor),
+        (None, Some((monitor, _))) if !monitor.is_waiting(thread.id) => Some(monitor),
+        _ => None,
+    }
+}
+
+fn monitor_address(monitor: &Rc<Monitor>) -> usize {
+    Rc::as_ptr(monitor) as usize
+}
+
+fn monitor_name(thread: &Thread, monitor: &Rc<Monitor>) -> String {
+    match object_description(thread, monitor) {
+        Some(description) => format!("<{:#018x}> ({description})", monitor_address(monitor)),
+        None => format!("<{:#018x}>", monitor_address(monitor)),
+    }
+}
+
+// Monitors don't know what they belong to, but the object is almost always somewhere in the thread's frames,
+// either about to be entered on the operand stack, or as the receiver of a synchronized method.
+fn object_description(thread: &Thread, monitor: &Rc<Monitor>) -> Option<String> {
+    let matches = |reference: &Reference<dyn Class, dyn Object>| reference.monitor().is_ok_and(|m| Rc::ptr_eq(m, monitor));
+    for frame in thread.m_stack.iter().rev() {
+        let locals = frame.local_variables.iter().filter_map(|v| match v {
+            VarValue::Reference(r) => Some(r.clone()),
+            _ => None,
+        });
+        let operands = frame.op_stack.iter().filter_map(|v| v.as_reference().ok());
+        if let Some(reference) = operands.chain(locals).find(matches) {
+            return match reference {
+                Reference::Object(o, _) => Some(format!("a {}", o.class().get_class_file().name().replace('/', "."))),
+                Reference::Array(..) => Some(String::from("an array")),
+                _ => None,
+            };
+        }
+    }
+    None
+}
+
+// Where a frame is, the way stack traces print it, like Main.run(Main.java:12).
+fn frame_location(frame: &Frame) -> String {
+    let location = match (frame.source_file(), frame.line_number()) {
+        (Some(source), Some(line)) => format!("{source}:{line}"),
+        (Some(source), None) => source,
+        (None, _) => String::from("Unknown Source"),
+    };
+    format!("{}.{}({location})", frame.rt_const_pool.get_class_file().name().replace('/', "."), frame.method_name())
+}
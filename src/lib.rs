@@ -16,6 +16,7 @@ pub mod class;
 pub mod data_access;
 pub mod errorcodes;
 pub mod thread;
+pub mod heap;
 pub mod frame;
 pub mod value;
 pub mod attributes;
@@ -24,12 +25,15 @@ pub mod flags;
 pub mod reference;
 pub mod multitypebox;
 pub mod llvm;
+pub mod jar;
+pub mod jimage;
+pub mod classpath;
 
 #[cfg(test)]
 pub mod testing;
 
 use std::{fs::File, fs, io::Read};
-use {class::classfile::ClassFile};
+use {class::classfile::ClassFile, errorcodes::Error};
 
 pub fn load_class(f: &mut File, path: &str) -> (ClassFile, Vec<Vec<u8>>) {
     unsafe {
@@ -40,4 +44,14 @@ pub fn load_class(f: &mut File, path: &str) -> (ClassFile, Vec<Vec<u8>>) {
         f.read(buf_bytes).unwrap();
         ClassFile::new(buf_bytes).unwrap()
     }
-}
\ No newline at end of file
+}
+
+// The class file parser wants an 8 byte aligned buffer, which a Vec<u8> (e.g. from a jar) doesn't guarantee.
+pub fn load_class_from_bytes(bytes: &[u8]) -> Result<(ClassFile, Vec<Vec<u8>>), Error> {
+    unsafe {
+        let buffer: Vec<u64> = vec![0; bytes.len().div_ceil(8)];
+        let buf_bytes = std::slice::from_raw_parts_mut(buffer.as_ptr() as *mut u8, buffer.len() * std::mem::size_of::<u64>());
+        buf_bytes[..bytes.len()].copy_from_slice(bytes);
+        ClassFile::new(buf_bytes)
+    }
+}
@@ -1,11 +1,11 @@
 use crate::class::Class;
-use crate::errorcodes::Error;
+use crate::errorcodes::{Error, Opcode};
 use crate::reference::Reference;
 use crate::reference::object::Object;
 use crate::value::Value;
 use crate::multitypebox::MultiTypeBox;
 
-use std::mem::size_of;
+use std::mem::{align_of, size_of};
 
 
 #[repr(u8)]
@@ -52,20 +52,71 @@ impl ValueMarker {
         }) as u8
     }
    
-    pub fn to_value(&self, b: &MultiTypeBox, idx: usize) -> Option<Value<dyn Class, dyn Object>> {
+    // References are bigger than they are aligned, so this isn't always the same as size.
+    pub fn align(&self) -> u8 {
+        match self {
+            Self::Reference => align_of::<Reference<dyn Class, dyn Object>>() as u8,
+            _ => self.size().max(1),
+        }
+    }
+    // Reads the value of this type at offset in b.
+    pub fn to_value(&self, b: &MultiTypeBox, offset: usize) -> Option<Value<dyn Class, dyn Object>> {
         Some( match self {
-            ValueMarker::Byte => Value::Byte(*b.get::<i8>(idx)? as i32),
-            ValueMarker::Short => Value::Short(*b.get::<i16>(idx)? as i32),
-            ValueMarker::Int => Value::Int(*b.get(idx)?),
-            ValueMarker::Long => Value::Long(*b.get(idx)?),
-            ValueMarker::Char => Value::Char(*b.get::<u16>(idx)? as i32),
-            ValueMarker::Float => Value::Float(*b.get(idx)?),
-            ValueMarker::Double => Value::Double(*b.get(idx)?),
-            ValueMarker::Reference => Value::Reference(b.get::<Reference<dyn Class, dyn Object>>(idx)?.clone()),
+            ValueMarker::Byte => Value::Byte(*b.get::<i8>(offset)? as i32),
+            ValueMarker::Short => Value::Short(*b.get::<i16>(offset)? as i32),
+            ValueMarker::Int => Value::Int(*b.get(offset)?),
+            ValueMarker::Long => Value::Long(*b.get(offset)?),
+            ValueMarker::Char => Value::Char(*b.get::<u16>(offset)? as i32),
+            ValueMarker::Float => Value::Float(*b.get(offset)?),
+            ValueMarker::Double => Value::Double(*b.get(offset)?),
+            ValueMarker::Reference => Value::Reference(b.get::<Reference<dyn Class, dyn Object>>(offset)?.clone()),
             ValueMarker::Void => return None,
             ValueMarker::Top => return None,
         } )
     }
+    // Writes value at offset in b, as this type. Ints get cut down to size, like the JVM does when they're stored in smaller fields.
+    pub fn store(&self, b: &mut MultiTypeBox, offset: usize, value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        let stored = match self {
+            ValueMarker::Byte => {
+                let value = *value.as_int()? as i8;
+                b.get_mut::<i8>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Short => {
+                let value = *value.as_int()? as i16;
+                b.get_mut::<i16>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Int => {
+                let value = *value.as_int()?;
+                b.get_mut::<i32>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Long => {
+                let value = *value.as_long()?;
+                b.get_mut::<i64>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Char => {
+                let value = *value.as_int()? as u16;
+                b.get_mut::<u16>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Float => {
+                let value = *value.as_float()?;
+                b.get_mut::<f32>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Double => {
+                let value = *value.as_double()?;
+                b.get_mut::<f64>(offset).map(|v| *v = value)
+            },
+            // Assigning drops the reference that was there before.
+            ValueMarker::Reference => {
+                let value = value.to_reference()?;
+                b.get_mut::<Reference<dyn Class, dyn Object>>(offset).map(|v| *v = value)
+            },
+            ValueMarker::Void | ValueMarker::Top => None,
+        };
+        match stored {
+            Some(()) => Ok(()),
+            None => Err(Error::NoSuchFieldError(Opcode::PUTFIELD)),
+        }
+    }
 }
 
 impl PartialOrd for ValueMarker {
@@ -1,9 +1,9 @@
-use rust_jvm::{load_class, value::{Value, VarValue}, reference::object::Object, class::Class};
+use rust_jvm::{load_class, load_class_from_bytes, jar::Jar, classpath::{self, ClassPath}, value::{Value, VarValue}, reference::object::Object, class::Class};
 use send_wrapper::SendWrapper;
 use once_cell::sync::Lazy;
 
 use std::{env, fs::File, fs, io::Read, mem::size_of};
-use rust_jvm::{jvm::JVM, class::classfile::ClassFile, argsparser};
+use rust_jvm::{jvm::JVM, class::classfile::ClassFile, argsparser::{self, FileType}};
 
 #[cfg(not(target_family = "wasm"))]
 use {
@@ -29,22 +29,109 @@ fn main() {
                 argsparser::ParseError::MissingFileArgument => println!("Missing argument: filename"),
                 argsparser::ParseError::FileDoesNotExist(s) => println!("Provided file {s} does not exist"),
                 argsparser::ParseError::MissingClassPathArgument => println!("Missing argument: class path"),
+                argsparser::ParseError::MissingBootClassPathArgument => println!("Missing argument: boot class path"),
+                argsparser::ParseError::MissingJdkArgument => println!("Missing argument: JDK directory"),
+                argsparser::ParseError::InvalidInitialHeapSize(s) => println!("Invalid initial heap size: {s}"),
+                argsparser::ParseError::InvalidMaxHeapSize(s) => println!("Invalid maximum heap size: {s}"),
+                argsparser::ParseError::InitialHeapTooLarge => println!("Initial heap size set to a larger value than the maximum heap size"),
+                argsparser::ParseError::InvalidStackSize(s) => println!("Invalid thread stack size: {s}"),
                 
             }
             println!("Aborting due to previous error");
             return;
         }
     };
-    let (main_class_file, code_bytes) = load_class(&mut result_args.file, &result_args.fpath);
+    // An explicit boot class path goes in front of the JDK, and we only use the extracted classes if neither is given.
+    let mut boot_class_path = ClassPath::new();
+    if result_args.boot_classpath.is_none() && result_args.jdk.is_none() {
+        result_args.boot_classpath = Some(String::from(classpath::DEFAULT_BOOT_CLASS_PATH));
+    }
+    if let Some(bcp) = &result_args.boot_classpath {
+        match ClassPath::parse(bcp) {
+            Ok(cp) => boot_class_path.append(cp),
+            Err(e) => {
+                println!("Error: Invalid boot class path: {e:?}");
+                return;
+            }
+        }
+    }
+    if let Some(jdk) = &result_args.jdk {
+        match ClassPath::from_jdk(jdk) {
+            Ok(cp) => boot_class_path.append(cp),
+            Err(e) => {
+                println!("Error: Invalid JDK {jdk}: {e:?}");
+                return;
+            }
+        }
+    }
+    let mut class_path = ClassPath::new();
+    let (main_class_file, code_bytes) = if result_args.ftype == FileType::Jar {
+        let j = match Jar::open(&result_args.fpath) {
+            Ok(j) => j,
+            Err(e) => {
+                println!("Error: Invalid or corrupt jarfile {}: {e:?}", result_args.fpath);
+                return;
+            }
+        };
+        let main_class = match j.manifest().map(|m| m.main_class) {
+            Ok(Some(c)) => c,
+            _ => {
+                println!("no main manifest attribute, in {}", result_args.fpath);
+                return;
+            }
+        };
+        let bytes = match j.read_entry(&format!("{main_class}.class")) {
+            Ok(Some(b)) => b,
+            _ => {
+                println!("Error: Could not find or load main class {}", main_class.replace('/', "."));
+                return;
+            }
+        };
+        // Like java, -cp is ignored when running a jar.
+        if let Err(e) = class_path.push_jar(j) {
+            println!("Error: Invalid or corrupt jarfile {}: {e:?}", result_args.fpath);
+            return;
+        }
+        match load_class_from_bytes(&bytes) {
+            Ok(c) => c,
+            Err(e) => {
+                println!("Error: Could not find or load main class {}", main_class.replace('/', "."));
+                println!("Caused by: {e:?}");
+                return;
+            }
+        }
+    }
+    else {
+        // Same as java, we fall back to CLASSPATH and then the current directory.
+        let cp = result_args.classpath.clone().or_else(|| env::var("CLASSPATH").ok()).unwrap_or(String::from("."));
+        class_path = match ClassPath::parse(&cp) {
+            Ok(cp) => cp,
+            Err(e) => {
+                println!("Error: Invalid class path: {e:?}");
+                return;
+            }
+        };
+        load_class(&mut result_args.file, &result_args.fpath)
+    };
     #[cfg(not(target_family = "wasm"))]
-    let mut jvm = JVM::new_with_main_class(main_class_file, code_bytes, result_args.flags, result_args.classpath.clone(), &CONTEXT).unwrap();
+    let mut jvm = JVM::new_with_main_class(main_class_file, code_bytes, result_args.flags, class_path, boot_class_path, &CONTEXT).unwrap();
     #[cfg(target_family = "wasm")]
-    let mut jvm = JVM::new_with_main_class(main_class_file, code_bytes, result_args.flags, result_args.classpath.clone()).unwrap();
+    let mut jvm = JVM::new_with_main_class(main_class_file, code_bytes, result_args.flags, class_path, boot_class_path).unwrap();
+    if let Some(initial) = result_args.initial_heap {
+        jvm.heap.set_initial(initial);
+    }
+    jvm.heap.set_max(result_args.max_heap);
+    if let Some(stack_size) = result_args.stack_size {
+        jvm.max_stack_size = stack_size;
+    }
     if result_args.should_dump {
         println!("Loaded Class: {}", jvm.resolve_class_reference(jvm.m_main_class_name.clone().as_str()).unwrap().get_class_file());
     }
     if result_args.should_run {
-        jvm.excecute();
+        let exit_code = jvm.excecute();
+        if exit_code != 0 {
+            std::process::exit(exit_code);
+        }
     }
 
 }
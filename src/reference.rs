@@ -8,48 +8,135 @@ use crate::class::Class;
 use crate::errorcodes::{Error, Opcode};
 use crate::reference::object::Object;
 
+use std::cell::{Cell, RefCell};
+use std::collections::VecDeque;
 use std::rc::Rc;
+use std::sync::atomic::{AtomicU64, Ordering};
 
 use self::object::customobject::CustomObject;
 
 use colored::Colorize;
 
-#[derive(Clone, Debug, Eq, PartialEq)]
+// The state of the generator identity hashes come from. It's a fixed seed, so runs are repeatable.
+static HASH_STATE: AtomicU64 = AtomicU64::new(0x2545_f491_4f6c_dd1d);
+
+// The next identity hash, from a xorshift generator like the one HotSpot uses by default.
+// Like HotSpot's, they're 31 bits and never 0, which is how a monitor says it doesn't have one yet.
+fn next_identity_hash() -> i32 {
+    let step = |mut x: u64| {
+        x ^= x << 13;
+        x ^= x >> 7;
+        x ^= x << 17;
+        x
+    };
+    loop {
+        let previous = HASH_STATE.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| Some(step(x))).unwrap_or_else(|x| x);
+        let hash = (step(previous) >> 33) as i32 & 0x7fff_ffff;
+        if hash != 0 {
+            return hash;
+        }
+    }
+}
+
+// Every reference to an object shares its monitor through the Rc, so entering and exiting go through Cells.
+#[derive(Debug, Eq, PartialEq)]
 pub struct Monitor {
-    pub owned_thread: usize,
-    pub entry_count: usize,
+    pub owned_thread: Cell<usize>,
+    pub entry_count: Cell<usize>,
+    // The ids of the threads in Object.wait on this monitor, in the order notify wakes them.
+    pub wait_set: RefCell<VecDeque<usize>>,
+    // The object's identity hash, or 0 until something asks for it. The monitor is the closest thing we have to an object header,
+    // so this is where it goes, like HotSpot keeps it in the mark word. It never depends on where the object is,
+    // so moving objects around wouldn't change it.
+    hash: Cell<i32>,
 }
 
 impl Monitor {
     pub fn new() -> Monitor {
-        Monitor { owned_thread: 0, entry_count: 0 } // It doesn't matter what thread originally creates it, because an object is not locked until someone tries to enter it.
+        Monitor { owned_thread: Cell::new(0), entry_count: Cell::new(0), wait_set: RefCell::new(VecDeque::new()), hash: Cell::new(0) } // It doesn't matter what thread originally creates it, because an object is not locked until someone tries to enter it.
     } 
-    // These functions will have to be atmoic if we want actual multithreading.
+    // These functions will have to be atomic if threads ever run at the same time.
     // See: https://doc.rust-lang.org/nomicon/atomics.html
-    // For now, we are a single threaded JVM pretending to be multithreaded, so we don't have to worry.
-    pub fn try_enter(&mut self, current_thread: usize) -> bool {
-        if self.entry_count == 0 {
-            self.owned_thread = current_thread;
-            self.entry_count = 1;
+    // For now, even with --os-threads only one thread runs at once (see jvm::os_threads), so we don't have to worry.
+    pub fn try_enter(&self, current_thread: usize) -> bool {
+        if self.entry_count.get() == 0 {
+            self.owned_thread.set(current_thread);
+            self.entry_count.set(1);
             true
         }
-        else if self.owned_thread == current_thread {
-            self.entry_count += 1;
+        else if self.owned_thread.get() == current_thread {
+            self.entry_count.set(self.entry_count.get() + 1);
             true
         }
         else {
             false
         }
     }
-    pub fn try_exit(&mut self, current_thread: usize) -> bool {
-        if self.owned_thread != current_thread || self.entry_count == 0  {
+    pub fn try_exit(&self, current_thread: usize) -> bool {
+        if !self.is_owned_by(current_thread) {
             false
         }
         else {
-            self.entry_count -= 1;
+            self.entry_count.set(self.entry_count.get() - 1);
             true
         }
     }
+    pub fn is_owned_by(&self, thread: usize) -> bool {
+        self.entry_count.get() > 0 && self.owned_thread.get() == thread
+    }
+    // Whether try_enter would succeed, without entering.
+    pub fn can_enter(&self, thread: usize) -> bool {
+        self.entry_count.get() == 0 || self.owned_thread.get() == thread
+    }
+    // Lets go of the monitor completely, however many times the thread entered it, and adds the thread to the wait set.
+    // Gives back the entry count, which reenter puts back once the thread is done waiting.
+    pub fn wait(&self, thread: usize) -> usize {
+        let count = self.entry_count.replace(0);
+        self.wait_set.borrow_mut().push_back(thread);
+        count
+    }
+    pub fn reenter(&self, thread: usize, count: usize) -> bool {
+        if self.entry_count.get() != 0 {
+            return false;
+        }
+        self.owned_thread.set(thread);
+        self.entry_count.set(count);
+        true
+    }
+    pub fn notify(&self) {
+        self.wait_set.borrow_mut().pop_front();
+    }
+    pub fn notify_all(&self) {
+        self.wait_set.borrow_mut().clear();
+    }
+    // Whether the thread is still waiting to be notified.
+    pub fn is_waiting(&self, thread: usize) -> bool {
+        self.wait_set.borrow().contains(&thread)
+    }
+    // For when a wait times out or is interrupted before anyone notifies it.
+    pub fn stop_waiting(&self, thread: usize) {
+        self.wait_set.borrow_mut().retain(|&t| t != thread);
+    }
+    // What Object.hashCode and System.identityHashCode give back, which gets picked the first time it's asked for.
+    pub fn identity_hash(&self) -> i32 {
+        if self.hash.get() == 0 {
+            self.hash.set(next_identity_hash());
+        }
+        self.hash.get()
+    }
+}
+
+// A copy of a monitor is in the same state, but doesn't share it with the original.
+// It belongs to a different object though, so it gets its own identity hash.
+impl Clone for Monitor {
+    fn clone(&self) -> Self {
+        Monitor {
+            owned_thread: self.owned_thread.clone(),
+            entry_count: self.entry_count.clone(),
+            wait_set: self.wait_set.clone(),
+            hash: Cell::new(0),
+        }
+    }
 }
 
 impl Default for Monitor {
@@ -170,6 +257,14 @@ impl Reference<dyn Class, dyn Object> {
         }
     }
 
+    // The monitor shared by every reference to the same thing.
+    pub fn monitor(&self) -> Result<&Rc<Monitor>, Error> {
+        match self {
+            Reference::Null => Err(Error::NullPointerException(Opcode::Unknown)),
+            Reference::Array(_, m) | Reference::Interface(_, m) | Reference::Object(_, m) => Ok(m),
+        }
+    }
+
     pub fn is_null(&self) -> bool {
         matches!(self, Reference::Null)
     }
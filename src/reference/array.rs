@@ -12,7 +12,7 @@ use crate::class::Class;
 use crate::errorcodes::Error;
 use crate::value::Value;
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Debug, PartialEq)]
 pub struct RefArray<C: Class + ?Sized, O: Object + ?Sized> {
     pub arr: Vec<Reference<C, O>>,
     // This descriptor could be massively improved by making it a recursive enum. For now, this works.
@@ -25,7 +25,7 @@ impl PartialEq for RefArray<dyn Class, dyn Object> {
     }
 }
 
-#[derive(Clone, Debug, PartialEq)]
+#[derive(Debug, PartialEq)]
 pub enum Array<C: Class + ?Sized, O: Object + ?Sized> {
     Bool(Vec<bool>),
     Char(Vec<u16>),
@@ -38,6 +38,30 @@ pub enum Array<C: Class + ?Sized, O: Object + ?Sized> {
     Ref(RefArray<C, O>),
 }
 
+// These can't be derived, because that would need the classes and objects themselves to be Clone, and all we clone are References.
+// Cloning an array of references only copies the references, which is the shallow copy clone() is supposed to make.
+impl<C: Class + ?Sized, O: Object + ?Sized> Clone for RefArray<C, O> {
+    fn clone(&self) -> Self {
+        RefArray { arr: self.arr.clone(), descriptor: self.descriptor.clone() }
+    }
+}
+
+impl<C: Class + ?Sized, O: Object + ?Sized> Clone for Array<C, O> {
+    fn clone(&self) -> Self {
+        match self {
+            Array::Bool(v) => Array::Bool(v.clone()),
+            Array::Char(v) => Array::Char(v.clone()),
+            Array::Float(v) => Array::Float(v.clone()),
+            Array::Double(v) => Array::Double(v.clone()),
+            Array::Byte(v) => Array::Byte(v.clone()),
+            Array::Short(v) => Array::Short(v.clone()),
+            Array::Int(v) => Array::Int(v.clone()),
+            Array::Long(v) => Array::Long(v.clone()),
+            Array::Ref(r) => Array::Ref(r.clone()),
+        }
+    }
+}
+
 // This could be an enum, but this also works (and is, I think, simpler)
 pub mod atype {
     pub const T_BOOLEAN: u8 = 4;
@@ -121,6 +145,47 @@ impl<C: Class + ?Sized, O: Object + ?Sized> Array<C, O> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+    // Roughly how much memory this array takes up, for keeping track of the heap.
+    pub fn heap_size(&self) -> usize {
+        let atype = match self {
+            Array::Bool(_) => atype::T_BOOLEAN,
+            Array::Char(_) => atype::T_CHAR,
+            Array::Float(_) => atype::T_FLOAT,
+            Array::Double(_) => atype::T_DOUBLE,
+            Array::Byte(_) => atype::T_BYTE,
+            Array::Short(_) => atype::T_SHORT,
+            Array::Int(_) => atype::T_INT,
+            Array::Long(_) => atype::T_LONG,
+            Array::Ref(_) => atype::T_REF,
+        };
+        Array::<C, O>::heap_size_of(self.len(), atype)
+    }
+    // How much memory a new array would take up, so we can check there's room for it before making it.
+    pub fn heap_size_of(size: usize, atype: u8) -> usize {
+        let element_size = match atype {
+            atype::T_BOOLEAN => std::mem::size_of::<bool>(),
+            atype::T_CHAR => std::mem::size_of::<u16>(),
+            atype::T_FLOAT => std::mem::size_of::<f32>(),
+            atype::T_DOUBLE => std::mem::size_of::<f64>(),
+            atype::T_BYTE => std::mem::size_of::<i8>(),
+            atype::T_SHORT => std::mem::size_of::<i16>(),
+            atype::T_INT => std::mem::size_of::<i32>(),
+            atype::T_LONG => std::mem::size_of::<i64>(),
+            _ => std::mem::size_of::<Reference<C, O>>(),
+        };
+        size.saturating_mul(element_size).saturating_add(std::mem::size_of::<Self>())
+    }
+    // Calls visit with every reference in the array. See Object::trace.
+    pub fn trace(&self, visit: &mut dyn FnMut(&Reference<C, O>)) {
+        if let Array::Ref(refarray) = self {
+            refarray.arr.iter().for_each(visit);
+        }
+    }
+    pub fn clear_references(&mut self) {
+        if let Array::Ref(refarray) = self {
+            refarray.arr.fill_with(|| Reference::Null);
+        }
+    }
 
 }
 
@@ -156,6 +221,22 @@ where
     
 } 
 
+// The atype for an element descriptor, like I for int arrays or L for arrays of objects.
+fn descriptor_atype(c: char) -> Option<u8> {
+    match c {
+        'B' => Some(atype::T_BYTE),
+        'C' => Some(atype::T_CHAR),
+        'D' => Some(atype::T_DOUBLE),
+        'F' => Some(atype::T_FLOAT),
+        'I' => Some(atype::T_INT),
+        'J' => Some(atype::T_LONG),
+        'S' => Some(atype::T_SHORT),
+        'Z' => Some(atype::T_BOOLEAN),
+        'L' | '[' => Some(atype::T_REF),
+        _ => None,
+    }
+}
+
 impl<C: Class + ?Sized, O: Object + ?Sized> Array<C, O> {
     // same reason as fill_multi_level
     pub fn new_multi<CC, OO>(dimensions: u8, counts: &[Value<CC, OO>], descriptor: String) -> Result<Array<C, O>, Error> 
@@ -167,27 +248,35 @@ impl<C: Class + ?Sized, O: Object + ?Sized> Array<C, O> {
         fn size_and_base(descriptor: &str, base_count: u8) -> (u8, char) {
             match descriptor.as_bytes()[0] as char {
                 '[' => size_and_base(&descriptor[1..], base_count + 1),
-                _ => (base_count, descriptor.as_bytes()[base_count as usize] as char ),
+                _ => (base_count, descriptor.as_bytes()[0] as char),
             }
         }
         let (dimensionality, base_type_char) = size_and_base(&descriptor, 0);
         if dimensionality < dimensions {
             return Err(Error::IncompatibleDimensionalityAndDescriptor);
         }
-        let base_type = match base_type_char {
-            'B' => atype::T_BYTE,
-            'C' => atype::T_CHAR,
-            'D' => atype::T_DOUBLE,
-            'F' => atype::T_FLOAT,
-            'I' => atype::T_INT,
-            'J' => atype::T_LONG,
-            'S' => atype::T_SHORT,
-            'Z' => atype::T_BOOLEAN,
-            'L' => atype::T_REF,
-            _ => return Err(Error::IllegalDescriptor),
+        let base_type = match descriptor_atype(base_type_char) {
+            Some(base_type) => base_type,
+            None => return Err(Error::IllegalDescriptor),
         };
         fill_multi_level(0, dimensions as usize, counts, base_type, descriptor)
     } 
+    // How much memory new_multi would take up with these counts, adding it up one level at a time.
+    pub fn multi_heap_size(counts: &[usize], descriptor: &str) -> usize {
+        let mut size: usize = 0;
+        // How many arrays there are on this level.
+        let mut arrays: usize = 1;
+        for (dimension, &count) in counts.iter().enumerate() {
+            // Everything but the innermost level holds arrays.
+            let atype = match descriptor.as_bytes().get(dimension + 1) {
+                Some(&c) => descriptor_atype(c as char).unwrap_or(atype::T_REF),
+                None => atype::T_REF,
+            };
+            size = size.saturating_add(arrays.saturating_mul(Array::<C, O>::heap_size_of(count, atype)));
+            arrays = arrays.saturating_mul(count);
+        }
+        size
+    }
 }
 
 impl<C: Class + ?Sized, O: Object + ?Sized> Array<C, O> {
@@ -231,7 +320,8 @@ impl<C: Class + ?Sized, O: Object + ?Sized> Array<C, O> {
             // As per https://docs.oracle.com/javase/specs/jvms/se18/html/jvms-2.html, bool is accessed using byte array instructions. 
             // Therefore, this case returns a Value byte.
             Array::Bool(bvec) => {
-                bvec[index] = *val.as_int()? == 0;
+                // Like HotSpot, we only keep the lowest bit. See https://docs.oracle.com/javase/specs/jvms/se17/html/jvms-6.html#jvms-6.5.bastore
+                bvec[index] = (*val.as_int()? & 1) != 0;
                 Ok(())
             }
             Array::Byte(bvec) => {
@@ -1,7 +1,7 @@
 use std::any::Any;
 
 use super::*;
-use crate::{value::Value, class::classfile::MethodInfo};
+use crate::{value::Value, class::{classfile::MethodInfo, rt_const_pool::FieldRef}};
 
 pub mod customobject;
 pub mod natives;
@@ -14,11 +14,11 @@ pub trait Object {
     fn new_with_name(name: &str, jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self : Sized {
         customobject::CustomObject::<dyn Class>::new_with_name(name, jvm)
     }
-    // This gives an index into the rt const pool of the class of the object, which references a field. 
-    fn get_field(&self, current_method_class: Rc<dyn Class>, class_index: u16, jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error>;
-    // This gives an index into the rt const pool of the class of the object, which references a field. 
+    // This gives a field that was resolved out of the rt const pool of the class of the current method.
+    fn get_field(&self, field: &FieldRef, jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error>;
+    // This gives a field that was resolved out of the rt const pool of the class of the current method. 
     // It also gives a value to be put in that field.
-    fn put_field(&mut self, current_method_class: Rc<dyn Class>, class_index: u16, jvm: &mut JVM, value: Value<dyn Class, dyn Object>) -> Result<(), Error>;
+    fn put_field(&mut self, field: &FieldRef, jvm: &mut JVM, value: Value<dyn Class, dyn Object>) -> Result<(), Error>;
     // This is run after the JVM decides which function to execute. Just run the code.
     fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo) 
     -> Result<bool, Error>; // Figure out what else to pass
@@ -27,15 +27,54 @@ pub trait Object {
     fn as_any(&self) -> &dyn Any;
     fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object>;
     fn is_equal(&self, other: &dyn Object) -> bool;
+    // Calls visit with every reference this object holds, so the collector can follow them. See crate::heap.
+    #[allow(clippy::type_complexity)]
+    fn trace(&self, _visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {}
+    // Sets every reference this object holds to null. The collector does this to garbage so its cycles get freed.
+    fn clear_references(&mut self) {}
+    // A copy with the same class and fields, for Object.clone. Native objects don't have fields to copy, so they can't be cloned.
+    fn shallow_clone(&self) -> Result<Option<Rc<dyn Object>>, Error> {
+        Ok(None)
+    }
+    // Roughly how much memory this object takes up, for keeping track of the heap.
+    fn heap_size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
 }
 
 pub fn new_object_with_name(name: &str, jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> {
-    match name {
+    let class = jvm.resolve_class_reference(name)?;
+    new_object_of_class(&class, jvm)
+}
+
+// Same as new_object_with_name, for when the class is already resolved, like in new_quick.
+pub fn new_object_of_class(class: &Rc<dyn Class>, jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> {
+    let class_file = class.get_class_file();
+    let name = class_file.name();
+    let object = match name {
         "java/lang/String" => natives::string::String::new(None, None, jvm),
         "java/io/PrintStream" => natives::print_stream::PrintStream::new(None, None, jvm),
         "java/lang/StringBuilder" => natives::string_builder::StringBuilder::new(None, None, jvm),
-        _ => customobject::CustomObject::<dyn Class>::new_with_name(name, jvm)
-    }
+        "java/lang/invoke/ConstantCallSite" | "java/lang/invoke/MutableCallSite" | "java/lang/invoke/VolatileCallSite" => {
+            natives::call_site::CallSite::new_with_name(name, jvm)
+        },
+        _ => customobject::CustomObject::new_of_class(Rc::clone(class), jvm)
+    }?;
+    jvm.heap.track_object(&object);
+    Ok(object)
+}
+
+// How much heap new_object_with_name will take for an object of the class, so it can be reserved before the object is made.
+pub fn heap_size_of(class: &Rc<dyn Class>, jvm: &mut JVM) -> Result<usize, Error> {
+    Ok(match class.get_class_file().name() {
+        "java/lang/String" => std::mem::size_of::<natives::string::String>(),
+        "java/io/PrintStream" => std::mem::size_of::<natives::print_stream::PrintStream>(),
+        "java/lang/StringBuilder" => std::mem::size_of::<natives::string_builder::StringBuilder>(),
+        "java/lang/invoke/ConstantCallSite" | "java/lang/invoke/MutableCallSite" | "java/lang/invoke/VolatileCallSite" => {
+            std::mem::size_of::<natives::call_site::CallSite>()
+        },
+        _ => customobject::Shape::of(class, jvm)?.object_size(),
+    })
 }
 
 pub fn new_object(current_method_class: Option<Rc<dyn Class>>, class_index: Option<u16>, jvm: &mut JVM) 
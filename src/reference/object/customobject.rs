@@ -1,62 +1,150 @@
 use std::any::Any;
+use std::cmp::Reverse;
 use std::rc::Rc;
 use std::result::Result;
 use std::collections::HashMap;
 
-use crate::class::{Class, classfile::*};
+use colored::Colorize;
+
+use crate::class::{Class, classfile::*, natives, rt_const_pool::FieldRef};
 use crate::constant_pool::NameAndType;
 use crate::errorcodes::{Error, Opcode};
+use crate::flags;
 use crate::frame::Frame;
 use crate::jvm::JVM;
 use crate::multitypebox::MultiTypeBox;
 use crate::reference::Reference;
-use crate::reference::array::Array;
 use super::object::Object;
 use crate::llvm::valuemarker::ValueMarker;
 use crate::value::{Value, VarValue};
 
-// Allows for future work to remove HashMap access
-// TODO: Store NameAndTypes more efficiently (i.e. without strings).
-struct Shape {
-    mem: MultiTypeBox,
-    layout: HashMap<NameAndType, (usize, ValueMarker)>
+// Where one instance field lives in an object.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct Slot {
+    // Where the slot is in its shape's slots. A field resolved against a superclass keeps the same index in every subclass.
+    pub index: usize,
+    // Where the field starts in the object's fields, in bytes.
+    pub offset: usize,
+    pub marker: ValueMarker,
+}
+
+// How the instance fields of a class are laid out, which gets worked out once per class.
+// A subclass's shape always starts with its superclass's, like vtables do, so a slot from a superclass works for all of its subclasses.
+#[derive(Debug)]
+pub struct Shape {
+    slots: Vec<Slot>,
+    // A subclass's own fields hide ones with the same name and type from its superclasses, but those still have slots.
+    by_name: HashMap<NameAndType, usize>,
+    size: usize,
+    align: usize,
+    // The offsets of every reference, which have to be dropped with the object and followed by the collector.
+    references: Vec<usize>,
 }
 
 impl Shape {
-    pub fn new(mut map: Vec<(NameAndType, Value<dyn Class, dyn Object>)>) -> Shape {
-        let mut map: Vec<(&mut NameAndType, ValueMarker)> = map.iter_mut().
-            map(|(n, v) |  (n, ValueMarker::from(v).unwrap())).collect();
-        map.sort_by(|(_, v), (_, other)| 
-            other.cmp(&v)); // Reversed to sort biggest -> smallest
-        let mut counts = [0; 5];
-        // count number of each
-        for (_, val) in &map {
-            counts[MultiTypeBox::offset_idx_from_size(val.size() as usize).unwrap()] += 1;
+    pub fn new(file: &ClassFile, super_shape: Option<&Shape>) -> Result<Shape, Error> {
+        let (mut slots, mut by_name, mut size, mut align, mut references) = match super_shape {
+            Some(s) => (s.slots.clone(), s.by_name.clone(), s.size, s.align, s.references.clone()),
+            None => (Vec::new(), HashMap::new(), 0, 1, Vec::new()),
+        };
+        let mut fields = Vec::new();
+        for field in &file.fields {
+            if (field.access_flags.flags & flags::field::ACC_STATIC) > 0 {
+                continue;
+            }
+            let name = file.cp_entry(field.name_index)?.as_utf8()?.clone();
+            let descriptor = file.cp_entry(field.descriptor_index)?.as_utf8()?.clone();
+            let marker = ValueMarker::from(&Value::new(descriptor.as_str()))?;
+            fields.push((NameAndType { name, descriptor }, marker));
         }
-        let mem = MultiTypeBox::new(counts).unwrap();
-        let mut layout = HashMap::new();
-        let mut offsets = [0; 5];
-        for (name, val) in &map {
-            layout.insert((*name).clone(), (offsets[MultiTypeBox::offset_idx_from_size(val.size() as usize).unwrap()], *val));
-            offsets[MultiTypeBox::offset_idx_from_size(val.size() as usize).unwrap()] += 1;
+        // Biggest first, so smaller fields fill in behind them instead of leaving padding everywhere.
+        fields.sort_by_key(|(_, marker)| Reverse(marker.size()));
+        for (name_and_type, marker) in fields {
+            let field_align = marker.align() as usize;
+            let offset = size.next_multiple_of(field_align);
+            size = offset + marker.size() as usize;
+            align = align.max(field_align);
+            if marker == ValueMarker::Reference {
+                references.push(offset);
+            }
+            by_name.insert(name_and_type, slots.len());
+            slots.push(Slot { index: slots.len(), offset, marker });
         }
-        Shape { mem, layout }
-
+        Ok(Shape { slots, by_name, size, align, references })
+    }
+    // The shape of a class's objects. Native classes don't keep one, so theirs gets worked out again.
+    pub fn of(class: &Rc<dyn Class>, jvm: &mut JVM) -> Result<Rc<Shape>, Error> {
+        match class.shape() {
+            Some(shape) => Ok(shape),
+            None => Shape::load(&class.get_class_file(), jvm),
+        }
+    }
+    // Works out the shape for a class file, starting from its superclass's.
+    pub fn load(file: &ClassFile, jvm: &mut JVM) -> Result<Rc<Shape>, Error> {
+        let super_shape = match file.super_name() {
+            Some(super_name) => {
+                let super_class = jvm.resolve_class_reference(super_name)?;
+                Some(Shape::of(&super_class, jvm)?)
+            },
+            None => None,
+        };
+        Ok(Rc::new(Shape::new(file, super_shape.as_deref())?))
     }
-    pub fn get_from_NAT(&self, var: NameAndType) -> Value<dyn Class, dyn Object> {
-        panic!()
+    pub fn slot(&self, name_and_type: &NameAndType) -> Option<Slot> {
+        self.by_name.get(name_and_type).map(|&index| self.slots[index])
+    }
+    // How much heap an object with this shape takes up, which is the same for all of them.
+    pub fn object_size(&self) -> usize {
+        std::mem::size_of::<CustomObject<dyn Class>>() + self.size
+    }
+    // Zeroes are already the default for every number, so only the references need filling in.
+    fn new_fields(&self) -> MultiTypeBox {
+        let mut fields = MultiTypeBox::new(self.size, self.align);
+        for &offset in &self.references {
+            fields.init::<Reference<dyn Class, dyn Object>>(offset, Reference::Null);
+        }
+        fields
     }
 }
 
-
-#[derive(Clone, PartialEq, Debug)]
 pub struct CustomObject<C> 
 where
     C: Class,
     C: ?Sized,
 {
     pub class: Rc<C>,
-    instance_vars: HashMap<NameAndType, Value<dyn Class, dyn Object>>,
+    shape: Rc<Shape>,
+    fields: MultiTypeBox,
+}
+
+impl<C: Class + ?Sized> CustomObject<C> {
+    // The slot a resolved field is in. Usually that's the one it was resolved to,
+    // but anything the shape doesn't line up with (like a field of a native class) gets looked up by name.
+    fn slot(&self, field: &FieldRef) -> Option<Slot> {
+        match field.slot {
+            Some(slot) if self.shape.slots.get(slot.index) == Some(&slot) => Some(slot),
+            _ => self.shape.slot(&field.name_and_type),
+        }
+    }
+    // Field by field, so the references get cloned properly.
+    // The copy is whole before anything goes in it, so if a store fails, dropping it still drops the references it got.
+    fn try_clone(&self) -> Result<Self, Error> {
+        let mut copy = CustomObject { class: Rc::clone(&self.class), shape: Rc::clone(&self.shape), fields: self.shape.new_fields() };
+        for slot in &self.shape.slots {
+            if let Some(value) = slot.marker.to_value(&self.fields, slot.offset) {
+                slot.marker.store(&mut copy.fields, slot.offset, value)?;
+            }
+        }
+        Ok(copy)
+    }
+}
+
+impl<C: Class + ?Sized> Drop for CustomObject<C> {
+    fn drop(&mut self) {
+        for &offset in &self.shape.references {
+            self.fields.drop_in_place::<Reference<dyn Class, dyn Object>>(offset);
+        }
+    }
 }
 
 impl<C: Class + ?Sized + 'static> Object for CustomObject<C> {
@@ -66,151 +154,65 @@ impl<C: Class + ?Sized + 'static> Object for CustomObject<C> {
         let class_info = cm_class_file.cp_entry(class_index.unwrap())?;
         let name_index = *class_info.as_class()?;
         let name = cm_class_file.cp_entry(name_index)?.as_utf8()?;
-        fn resolve_class_fields(inner_name: &str, jvm: &mut JVM, map: &mut HashMap<NameAndType, Value<dyn Class, dyn Object>>) -> Result<(), Error> {
-            // We clone the name here, meaning that the name technically could become different from the actual stored name.
-            // We just have to be careful to not let this happen.
-            let (should_resolve, name) = {
-                let current_class = jvm.resolve_class_reference(inner_name)?;
-                if current_class.get_class_file().has_super() {
-                    (true, Some(String::from(current_class.get_class_file().super_name().unwrap())))
-                }
-                else {
-                    (false, None)
-                }
-            };
-            if should_resolve {
-                resolve_class_fields(name.unwrap().as_str(), jvm, map)?;
-            }
-            let current_class = jvm.resolve_class_reference(inner_name)?;
-            for field in current_class.get_class_file().fields() {
-                let name = current_class.get_class_file().cp_entry(field.name_index)?.as_utf8()?.clone();
-                let descriptor = current_class.get_class_file().cp_entry(field.descriptor_index)?.as_utf8()?.clone();
-                let name_and_type = NameAndType {
-                    name,
-                    descriptor,
-                };
-                let _ = map.try_insert( name_and_type, Value::new(current_class.get_class_file().cp_entry(field.descriptor_index)?.as_utf8()?));
-            }
-            Ok(())
-        }
-        let mut map = HashMap::new();
-        resolve_class_fields(name, jvm, &mut map)?;
-        let final_class = jvm.resolve_class_reference(name)?;
-        Ok(Rc::new(CustomObject {
-            class: final_class,
-            instance_vars: map,
-        }))
+        CustomObject::<dyn Class>::new_with_name(name, jvm)
     }
     fn new_with_name(name: &str, jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> {
-        // W resolve the instance variables of the class.
-        // This resolution is recursive, because for each class we also have to resolve the fields of its superclasses.
-        // To do this, we use this helper function.
-        // This function has to use mutable references instead of return values because HashMap has no append function.
-        fn resolve_class_fields(inner_name: &str, jvm: &mut JVM, map: &mut HashMap<NameAndType, Value<dyn Class, dyn Object>>) -> Result<(), Error> {
-            // We clone the name here, meaning that the name technically could become different from the actual stored name.
-            // We just have to be careful to not let this happen.
-            let (should_resolve, name) = {
-                let current_class = jvm.resolve_class_reference(inner_name)?;
-                if current_class.get_class_file().has_super() {
-                    (true, Some(String::from(current_class.get_class_file().super_name().unwrap())))
-                }
-                else {
-                    (false, None)
-                }
-            };
-            if should_resolve {
-                resolve_class_fields(name.unwrap().as_str(), jvm, map)?;
-            }
-            let current_class = jvm.resolve_class_reference(inner_name)?;
-            let current_class_file = current_class.get_class_file();
-            for field in current_class.get_class_file().fields().clone() {
-                let new_val = {                    
-                    let desc = current_class_file.cp_entry(field.descriptor_index)?.as_utf8()?;
-                    Value::new(desc.as_str())
-                };
-                let name = current_class_file.cp_entry(field.name_index)?.as_utf8()?.clone();
-                let descriptor = current_class_file.cp_entry(field.descriptor_index)?.as_utf8()?.clone();
-                let name_and_type = NameAndType {
-                    name,
-                    descriptor,
-                };
-                let _ = map.try_insert( name_and_type, new_val);
-            }
-            Ok(())
-        }
-        let mut map = HashMap::new();
-        resolve_class_fields(name, jvm, &mut map)?;
-        let final_class = jvm.resolve_class_reference(name)?;
-        Ok(Rc::new(CustomObject {
-            class: final_class,
-            instance_vars: map,
-        }))
+        let class = jvm.resolve_class_reference(name)?;
+        CustomObject::new_of_class(class, jvm)
     }
-    fn get_field(&self, current_method_class: Rc<dyn Class>, class_index: u16, jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
-        let cm_class_file = current_method_class.get_class_file();
-        let field_ref = cm_class_file.cp_entry(class_index)?.as_field_ref()?;
-        // This code is for verifing that the class the field is for is the same as this class. 
-        /*
-        let class_ref = *cm_class_file.cp_entry(field_ref.class_index)?.as_class()?;
-        let class_name = cm_class_file.cp_entry(class_ref)?.as_utf8()?;
-        #[allow(clippy::vtable_address_comparisons)]
-        if !Rc::ptr_eq(&Rc::clone(&self.class).as_dyn_rc(), &jvm.resolve_class_reference(class_name)?) {
-            println!("Class refs were not the same! this class pointer: {:p}, other class pointer: {:p}", 
-                Rc::as_ptr(&Rc::clone(&self.class).as_dyn_rc()),  Rc::as_ptr(&jvm.resolve_class_reference(class_name)?));
-
-            return Err(Error::IncompatibleObjectAndField(String::from(self.class.get_class_file().name()), String::from(class_name)));
-        }
-        */
-        let name_and_type_info = cm_class_file.cp_entry(field_ref.name_and_type_index)?.as_name_and_type()?;
-        let name = cm_class_file.cp_entry(name_and_type_info.name_index)?.as_utf8()?.clone();
-        let descriptor = cm_class_file.cp_entry(name_and_type_info.descriptor_index)?.as_utf8()?.clone();
-        let name_and_type = NameAndType {
-            name,
-            descriptor,
-        };
-        match self.instance_vars.get(&name_and_type) {
-            Some(value) => {
-                match value.is_reference() {
-                    false => Ok(value.clone()), // If the value is a primitive type, we clone it.
-                    true => Ok(Value::Reference(value.as_reference().unwrap().clone())), // If it's a reference, return a new reference. 
-                    // We intentionally use '.unwrap()', because we want to crash if .is_reference() is not working correctly.
-                }
-            },
+    fn get_field(&self, field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        match self.slot(field).and_then(|slot| slot.marker.to_value(&self.fields, slot.offset)) {
+            Some(value) => Ok(value),
             None => Err(Error::NoSuchFieldError(Opcode::GETFIELD)),
         }
     }
-    fn put_field(&mut self, current_method_class: Rc<dyn Class>, class_index: u16, jvm: &mut JVM, value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
-        let cm_class_file = current_method_class.get_class_file();
-        let field_ref = cm_class_file.cp_entry(class_index)?.as_field_ref()?;
-        // This code is for verifing that the class the field is for is the same as this class. 
-        /*
-        let class_ref = *cm_class_file.cp_entry(field_ref.class_index)?.as_class()?;
-        let class_name = cm_class_file.cp_entry(class_ref)?.as_utf8()?;
-        #[allow(clippy::vtable_address_comparisons)]
-        if !Rc::ptr_eq(&Rc::clone(&self.class).as_dyn_rc(), &jvm.resolve_class_reference(class_name)?) {
-            println!("Class refs were not the same! this class pointer: {:p}, other class pointer: {:p}", 
-                Rc::as_ptr(&Rc::clone(&self.class).as_dyn_rc()),  Rc::as_ptr(&jvm.resolve_class_reference(class_name)?));
-
-            return Err(Error::IncompatibleObjectAndField(String::from(self.class.get_class_file().name()), String::from(class_name)));
-        }
-        */
-        let name_and_type_info = cm_class_file.cp_entry(field_ref.name_and_type_index)?.as_name_and_type()?;
-        let name = current_method_class.get_class_file().cp_entry(name_and_type_info.name_index)?.as_utf8()?.clone();
-        let descriptor = current_method_class.get_class_file().cp_entry(name_and_type_info.descriptor_index)?.as_utf8()?.clone();
-        let name_and_type = NameAndType {
-            name,
-            descriptor,
-        };
-        match self.instance_vars.insert(name_and_type, value) {
-            Some(_) => Ok(()),
+    fn put_field(&mut self, field: &FieldRef, _jvm: &mut JVM, value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        match self.slot(field) {
+            Some(slot) => slot.marker.store(&mut self.fields, slot.offset, value),
             None => Err(Error::NoSuchFieldError(Opcode::PUTFIELD)),
         }
     }
     fn exec_method(&mut self, new_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo) 
     -> Result<bool, Error> {
+        // Thread objects are CustomObjects too, but some of Thread's methods have to be handled by the JVM.
+        if let Ok(thread_class) = Rc::clone(&new_method_class).as_any_rc().downcast::<natives::thread::Thread>() {
+            if let Some(was_native) = thread_class.exec_on_object(jvm, method)? {
+                return Ok(was_native);
+            }
+        }
+        if method.access_flags & flags::method::ACC_NATIVE {
+            // Same as CustomClass, a few of these we do ourselves.
+            match jvm.execute_native(method, Rc::clone(&new_method_class)) {
+                Err(Error::UnsatisfiedLinkError(..)) => {},
+                result => return result.map(|_| true),
+            }
+        }
+        jvm.reserve_stack(Frame::size_for(method))?;
         let thread = current_thread_mut!(jvm);
         // Fill out the local variables.
         let c_file = new_method_class.get_class_file();
+        if method.access_flags & flags::method::ACC_NATIVE {
+            // Like static ones in CustomClass, we skip the rest. The receiver and arguments still have to come off the stack though, 
+            // and anything that returns something gets a default value.
+            let desc = c_file.cp_entry(method.descriptor_index)?.as_utf8()?;
+            eprintln!("{}", 
+            format!("WARNING: Native method {}.{}{} was called, skipping", 
+                c_file.name(),
+                c_file.cp_entry(method.name_index)?.as_utf8()?, 
+                desc
+            ).as_str().red());
+            let num_args = method.num_args(&c_file)?;
+            let frame = current_frame_mut!(thread);
+            if frame.op_stack.len() < num_args + 1 {
+                return Err(Error::StackUnderflow(Opcode::MethodInvoke));
+            }
+            frame.op_stack.truncate(frame.op_stack.len() - num_args - 1);
+            let ret = &desc[desc.rfind(')').unwrap() + 1..];
+            if ret != "V" {
+                frame.op_stack.push(Value::new(ret));
+            }
+            return Ok(false);
+        }
         // Use jvm::parse_descriptor
         let (local_types, _, real_max_locals) = JVM::parse_descriptor(c_file.cp_entry(method.descriptor_index)?.as_utf8()?)?;
         let mut new_frame = Frame::new_with_stack_size(new_method_class.as_dyn_rc(), method.clone(), 
@@ -336,11 +338,36 @@ impl<C: Class + ?Sized + 'static> Object for CustomObject<C> {
     fn is_equal(&self, other: &dyn Object) -> bool {
         match other.as_any().downcast_ref::<CustomObject<C>>() {
             None => false,
-            Some(other) => self.instance_vars == other.instance_vars && Rc::ptr_eq(&self.class, &other.class) ,
+            Some(other) => Rc::ptr_eq(&self.class, &other.class) && Rc::ptr_eq(&self.shape, &other.shape) && self.shape.slots.iter()
+                .all(|slot| slot.marker.to_value(&self.fields, slot.offset) == slot.marker.to_value(&other.fields, slot.offset)),
+        }
+    }
+    fn trace(&self, visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {
+        for &offset in &self.shape.references {
+            if let Some(r) = self.fields.get(offset) {
+                visit(r);
+            }
         }
     }
+    fn clear_references(&mut self) {
+        for &offset in &self.shape.references {
+            if let Some(r) = self.fields.get_mut::<Reference<dyn Class, dyn Object>>(offset) {
+                *r = Reference::Null;
+            }
+        }
+    }
+    fn shallow_clone(&self) -> Result<Option<Rc<dyn Object>>, Error> {
+        Ok(Some(Rc::new(self.try_clone()?)))
+    }
+    fn heap_size(&self) -> usize {
+        self.shape.object_size()
+    }
 }
 
 impl CustomObject<dyn Class> {
-
+    pub fn new_of_class(class: Rc<dyn Class>, jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> {
+        let shape = Shape::of(&class, jvm)?;
+        let fields = shape.new_fields();
+        Ok(Rc::new(CustomObject { class, shape, fields }))
+    }
 }
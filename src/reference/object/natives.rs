@@ -1,4 +1,10 @@
+pub mod call_site;
+pub mod class_mirror;
 pub mod integer;
+pub mod lambda;
+pub mod lookup;
+pub mod method_handle;
+pub mod method_type;
 pub mod print_stream;
 pub mod string;
 pub mod string_builder;
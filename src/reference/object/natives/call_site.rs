@@ -0,0 +1,115 @@
+use crate::{access_macros, errorcodes::Opcode, frame::Frame};
+
+use super::super::*;
+use super::{method_handle, method_type};
+
+// An instance of java.lang.invoke.ConstantCallSite, MutableCallSite or VolatileCallSite, which is what bootstrap methods
+// give back for invokedynamic. All we need is the target, and the real ones need Unsafe to set it.
+pub struct CallSite {
+    class: Rc<dyn Class>,
+    target: Reference<dyn Class, dyn Object>,
+}
+
+impl CallSite {
+    pub fn target(&self) -> &Reference<dyn Class, dyn Object> {
+        &self.target
+    }
+}
+
+impl Object for CallSite {
+    fn new(_current_method_class: Option<Rc<dyn Class>>, _class_index: Option<u16>, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call CallSite::new")
+    }
+    fn new_with_name(name: &str, jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        Ok(Rc::new(CallSite { class: jvm.resolve_class_reference(name)?, target: Reference::Null }))
+    }
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::GETFIELD))
+    }
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
+    }
+    fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo)
+    -> Result<bool, Error> {
+        let cm_class_file = current_method_class.get_class_file();
+        let name = cm_class_file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = cm_class_file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let num_args = method.num_args(&cm_class_file)?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+        let args = match frame.op_stack.len().checked_sub(num_args + 1) {
+            Some(index) => frame.op_stack.split_off(index + 1),
+            None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+        };
+        frame.op_stack.pop();
+        let mut was_natively_executed = true;
+        let result = match (name, desc) {
+            ("<init>", "(Ljava/lang/invoke/MethodHandle;)V") => {
+                let target = args[0].as_reference()?;
+                if let Reference::Null = target {
+                    return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]);
+                }
+                self.target = target;
+                None
+            },
+            // Mutable and volatile call sites can start out without a target.
+            ("<init>", "(Ljava/lang/invoke/MethodType;)V") => None,
+            ("getTarget", "()Ljava/lang/invoke/MethodHandle;") => Some(Value::Reference(self.target.clone())),
+            ("setTarget", "(Ljava/lang/invoke/MethodHandle;)V") => {
+                if self.class.get_class_file().name() == "java/lang/invoke/ConstantCallSite" {
+                    return jvm.throw_exception("java/lang/UnsupportedOperationException", "()V", vec![]);
+                }
+                self.target = args[0].as_reference()?;
+                None
+            },
+            ("type", "()Ljava/lang/invoke/MethodType;") => {
+                let handle = match &self.target {
+                    Reference::Object(o, _) => o.as_any().downcast_ref::<method_handle::MethodHandle>().map(|h| h.handle().type_descriptor()),
+                    _ => None,
+                };
+                match handle {
+                    Some(descriptor) => {
+                        let t = method_type::MethodType::new_with_descriptor(&descriptor, jvm)?;
+                        Some(Value::Reference(Reference::Object(t as Rc<dyn Object>, Rc::new(Monitor::new()))))
+                    },
+                    None => Some(Value::Reference(Reference::Null)),
+                }
+            },
+            _ => {
+                // do funky stuff
+                eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class CallSite").red());
+                was_natively_executed = false;
+                if &desc[desc.len() - 1..] != "V" {
+                    // expected to push something onto stack
+                    Some(Value::Reference(Reference::Null))
+                } else {
+                    None
+                }
+            }
+        };
+        if let Some(result) = result {
+            let thread = access_macros::current_thread_mut!(jvm);
+            let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+            frame.op_stack.push(result);
+        }
+        Ok(was_natively_executed)
+    }
+    fn class(&self) -> Rc<dyn Class> {
+        self.class.clone()
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object> {
+        self
+    }
+    fn is_equal(&self, other: &dyn Object) -> bool {
+        std::ptr::eq(self as *const Self as *const u8, other.as_any() as *const dyn Any as *const u8)
+    }
+    fn trace(&self, visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {
+        visit(&self.target);
+    }
+    fn clear_references(&mut self) {
+        self.target = Reference::Null;
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{access_macros, errorcodes::Opcode, frame::Frame};
+
+use super::super::*;
+
+// An instance of java.lang.Class. There's only ever one of these for each type, see JVM::class_mirror.
+pub struct ClassMirror {
+    // The field descriptor of the type this mirrors, so "I", "Ljava/lang/String;", or "[I".
+    descriptor: String,
+    // The class this mirrors, if it's a class or interface.
+    mirrored: Option<Rc<dyn Class>>,
+    c_class: Rc<dyn Class>,
+}
+
+impl ClassMirror {
+    pub fn new_with_descriptor(descriptor: &str, mirrored: Option<Rc<dyn Class>>, jvm: &mut JVM) -> Result<Rc<ClassMirror>, Error> {
+        Ok(Rc::new(ClassMirror { descriptor: String::from(descriptor), mirrored,
+            c_class: jvm.resolve_class_reference("java/lang/Class")? }))
+    }
+    pub fn descriptor(&self) -> &str {
+        &self.descriptor
+    }
+    pub fn mirrored(&self) -> Option<Rc<dyn Class>> {
+        self.mirrored.clone()
+    }
+}
+
+// What Class.getName gives for a field descriptor.
+pub fn java_name(descriptor: &str) -> String {
+    match &descriptor[0..1] {
+        "B" => String::from("byte"),
+        "C" => String::from("char"),
+        "D" => String::from("double"),
+        "F" => String::from("float"),
+        "I" => String::from("int"),
+        "J" => String::from("long"),
+        "S" => String::from("short"),
+        "Z" => String::from("boolean"),
+        "V" => String::from("void"),
+        "L" => descriptor[1..descriptor.len() - 1].replace('/', "."),
+        _ => descriptor.replace('/', "."),
+    }
+}
+
+// What Class.getSimpleName gives for a field descriptor. We don't look at InnerClasses, so nested classes are
+// guessed from the name, and anonymous ones (Outer$1) keep their whole name.
+pub fn simple_name(descriptor: &str) -> String {
+    if let Some(component) = descriptor.strip_prefix('[') {
+        return format!("{}[]", simple_name(component));
+    }
+    let name = java_name(descriptor);
+    let name = match name.rfind('.') {
+        Some(idx) => &name[idx + 1..],
+        None => &name,
+    };
+    match name.rfind('$') {
+        Some(idx) if name[idx + 1..].starts_with(|c: char| c.is_alphabetic()) => String::from(&name[idx + 1..]),
+        _ => String::from(name),
+    }
+}
+
+impl Object for ClassMirror {
+    fn new(_current_method_class: Option<Rc<dyn Class>>, _class_index: Option<u16>, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call ClassMirror::new, use JVM::class_mirror")
+    }
+    fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call ClassMirror::new_with_name, use JVM::class_mirror")
+    }
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::GETFIELD))
+    }
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
+    }
+    fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo)
+    -> Result<bool, Error> {
+        let cm_class_file = current_method_class.get_class_file();
+        let name = cm_class_file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = cm_class_file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let is_interface = match &self.mirrored {
+            Some(c) => c.get_class_file().is_interface(),
+            None => false,
+        };
+        let mut was_natively_executed = true;
+        let result = match (name, desc) {
+            ("getName", "()Ljava/lang/String;") => {
+                let s = natives::string::String::new_from_string(java_name(&self.descriptor), jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            ("getSimpleName", "()Ljava/lang/String;") => {
+                let s = natives::string::String::new_from_string(simple_name(&self.descriptor), jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            ("toString", "()Ljava/lang/String;") => {
+                let s = if self.descriptor.len() == 1 {
+                    java_name(&self.descriptor)
+                } else if is_interface {
+                    format!("interface {}", java_name(&self.descriptor))
+                } else {
+                    format!("class {}", java_name(&self.descriptor))
+                };
+                let s = natives::string::String::new_from_string(s, jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            // We don't support assertions.
+            ("desiredAssertionStatus", "()Z") => Some(Value::Byte(0)),
+            ("isArray", "()Z") => Some(Value::Byte(self.descriptor.starts_with('[') as i32)),
+            ("isPrimitive", "()Z") => Some(Value::Byte((self.descriptor.len() == 1) as i32)),
+            ("isInterface", "()Z") => Some(Value::Byte(is_interface as i32)),
+            ("getComponentType", "()Ljava/lang/Class;") => Some(Value::Reference(match self.descriptor.strip_prefix('[') {
+                Some(component) => jvm.class_mirror(component)?,
+                None => Reference::Null,
+            })),
+            ("getSuperclass", "()Ljava/lang/Class;") => {
+                let super_name = match &self.mirrored {
+                    Some(c) if !is_interface => c.get_class_file().super_name().map(String::from),
+                    _ if self.descriptor.starts_with('[') => Some(String::from("java/lang/Object")),
+                    _ => None,
+                };
+                Some(Value::Reference(match super_name {
+                    Some(super_name) => jvm.class_mirror(&format!("L{super_name};"))?,
+                    None => Reference::Null,
+                }))
+            },
+            _ => {
+                // do funky stuff
+                eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class Class").red());
+                was_natively_executed = false;
+                let num_args = method.num_args(&cm_class_file)?;
+                let thread = access_macros::current_thread_mut!(jvm);
+                let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+                let new_len = frame.op_stack.len().saturating_sub(num_args);
+                frame.op_stack.truncate(new_len);
+                if &desc[desc.len() - 1..] != "V" {
+                    // expected to push something onto stack
+                    Some(Value::Reference(Reference::Null))
+                } else {
+                    None
+                }
+            }
+        };
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.pop();
+        if let Some(result) = result {
+            frame.op_stack.push(result);
+        }
+        Ok(was_natively_executed)
+    }
+    fn class(&self) -> Rc<dyn Class> {
+        self.c_class.clone()
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object> {
+        self
+    }
+    fn is_equal(&self, other: &dyn Object) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self.descriptor == other.descriptor,
+        }
+    }
+}
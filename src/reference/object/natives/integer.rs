@@ -14,10 +14,10 @@ impl Object for Integer {
     fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error>  where Self : Sized {
         panic!("No one should ever call Integer::new_with_name")
     }
-    fn get_field(&self, _current_method_class: Rc<dyn Class>, _class_index: u16, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
         Err(Error::NoSuchFieldError(Opcode::GETFIELD))
     }
-    fn put_field(&mut self, _current_method_class: Rc<dyn Class>, _class_index: u16, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
         Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
     }
     fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo) 
@@ -0,0 +1,105 @@
+use crate::{access_macros, errorcodes::Opcode, frame::Frame, jvm::invoke::MethodHandle};
+
+use super::super::*;
+
+// An instance of a class made by LambdaMetafactory. It holds onto whatever the call site captured,
+// and passes it, along with the arguments, to the target whenever the interface method gets called.
+pub struct Lambda {
+    class: Rc<dyn Class>,
+    target: MethodHandle,
+    captured: Vec<Value<dyn Class, dyn Object>>,
+}
+
+impl Lambda {
+    pub fn new_with_target(class: Rc<dyn Class>, target: MethodHandle, captured: Vec<Value<dyn Class, dyn Object>>) -> Rc<Lambda> {
+        Rc::new(Lambda { class, target, captured })
+    }
+    // How much heap a lambda that captured this many values takes up.
+    pub fn heap_size_of(captured: usize) -> usize {
+        std::mem::size_of::<Lambda>() + captured * std::mem::size_of::<Value<dyn Class, dyn Object>>()
+    }
+}
+
+impl Object for Lambda {
+    fn new(_current_method_class: Option<Rc<dyn Class>>, _class_index: Option<u16>, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call Lambda::new")
+    }
+    fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call Lambda::new_with_name")
+    }
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::GETFIELD))
+    }
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
+    }
+    fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo)
+    -> Result<bool, Error> {
+        let cm_class_file = current_method_class.get_class_file();
+        let num_args = method.num_args(&cm_class_file)?;
+        let desc = cm_class_file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        if cm_class_file.name() == self.class.get_class_file().name() {
+            // The only methods our class has are the interface method and its bridges.
+            // Swap ourselves out for the captured values, and then call the target. The arguments might still need
+            // boxing or unboxing, like when an Integer gets passed to a method reference that takes an int.
+            let thread = access_macros::current_thread_mut!(jvm);
+            let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+            let args = match frame.op_stack.len().checked_sub(num_args) {
+                Some(index) => frame.op_stack.split_off(index),
+                None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+            };
+            frame.op_stack.pop();
+            frame.op_stack.extend(self.captured.iter().cloned());
+            frame.op_stack.extend(args);
+            self.target.invoke_as(jvm, desc)?;
+            return Ok(true);
+        }
+        if method.code.is_some() {
+            // Default methods in the interface run like they would on any other object.
+            jvm.setup_method_call(method, current_method_class, false)?;
+            return Ok(false);
+        }
+        let name = cm_class_file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class {}", self.class.get_class_file().name()).red());
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+        let new_len = frame.op_stack.len().saturating_sub(num_args + 1);
+        frame.op_stack.truncate(new_len);
+        if &desc[desc.len() - 1..] != "V" {
+            // expected to push something onto stack
+            frame.op_stack.push(Value::Reference(Reference::Null));
+        }
+        Ok(false)
+    }
+    fn class(&self) -> Rc<dyn Class> {
+        self.class.clone()
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object> {
+        self
+    }
+    fn is_equal(&self, other: &dyn Object) -> bool {
+        // Lambdas only have identity.
+        std::ptr::eq(self as *const Self as *const u8, other.as_any() as *const dyn Any as *const u8)
+    }
+    // Whatever got captured is only reachable through us, and a lambda that captured the object that holds it is a cycle.
+    fn trace(&self, visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {
+        for value in &self.captured {
+            if let Value::Reference(r) = value {
+                visit(r);
+            }
+        }
+    }
+    fn clear_references(&mut self) {
+        for value in &mut self.captured {
+            if let Value::Reference(r) = value {
+                *r = Reference::Null;
+            }
+        }
+    }
+    fn heap_size(&self) -> usize {
+        Lambda::heap_size_of(self.captured.len())
+    }
+}
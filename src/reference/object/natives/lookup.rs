@@ -0,0 +1,146 @@
+use crate::{access_macros, constant_pool::ReferenceKind, errorcodes::Opcode, frame::Frame, jvm::invoke};
+
+use super::super::*;
+use super::{class_mirror, method_type};
+
+// An instance of java.lang.invoke.MethodHandles.Lookup, which is what bootstrap methods get as their first argument.
+// The real one needs the JDK's MemberName machinery, so we only do what bootstrap methods usually want: the lookup class, and
+// finding static, virtual and constructor handles by name and type. We don't check access, so every lookup has full privileges.
+pub struct Lookup {
+    class: Rc<dyn Class>,
+    lookup_class: Rc<dyn Class>,
+}
+
+impl Lookup {
+    pub fn new_for(lookup_class: Rc<dyn Class>, jvm: &mut JVM) -> Result<Rc<Lookup>, Error> {
+        Ok(Rc::new(Lookup { class: jvm.resolve_class_reference("java/lang/invoke/MethodHandles$Lookup")?, lookup_class }))
+    }
+    // What a find method gives back, or the exception it throws. Like the JDK, a method that's missing is a
+    // NoSuchMethodException, and one that's there but the wrong kind (like a static one for findVirtual) is an IllegalAccessException.
+    fn find(&self, jvm: &mut JVM, kind: ReferenceKind, refc: &Value<dyn Class, dyn Object>, name: &str, method_type: &Value<dyn Class, dyn Object>)
+    -> Result<Value<dyn Class, dyn Object>, Error> {
+        let refc = match refc.as_reference()? {
+            Reference::Object(o, _) => match o.as_any().downcast_ref::<class_mirror::ClassMirror>().and_then(|m| m.mirrored()) {
+                Some(c) => c,
+                None => return Err(Error::IncorrectReferenceType(Opcode::NativeMethod)),
+            },
+            Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
+            _ => return Err(Error::IncorrectReferenceType(Opcode::NativeMethod)),
+        };
+        let descriptor = match method_type.as_reference()? {
+            Reference::Object(o, _) => match o.as_any().downcast_ref::<method_type::MethodType>() {
+                Some(t) => String::from(t.descriptor()),
+                None => return Err(Error::IncorrectReferenceType(Opcode::NativeMethod)),
+            },
+            Reference::Null => return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]),
+            _ => return Err(Error::IncorrectReferenceType(Opcode::NativeMethod)),
+        };
+        let is_interface = refc.get_class_file().is_interface();
+        let class_name = refc.get_class_file().name().replace('/', ".");
+        let kind = if kind == ReferenceKind::RefInvokeVirtual && is_interface { ReferenceKind::RefInvokeInterface } else { kind };
+        match invoke::MethodHandle::find(jvm, kind, refc, is_interface, name, &descriptor, Opcode::NativeMethod) {
+            Ok(handle) => Ok(Value::Reference(invoke::method_handle_object(jvm, handle)?)),
+            Err(Error::NoSuchMethodError(_)) => jvm.throw_exception_with_message("java/lang/NoSuchMethodException",
+                format!("no such method: {class_name}.{name}{}", method_type::method_type_string(&descriptor)?)),
+            Err(Error::IncompatibleClassChangeError(_)) => jvm.throw_exception_with_message("java/lang/IllegalAccessException",
+                format!("no such method: {class_name}.{name}{}", method_type::method_type_string(&descriptor)?)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+// The contents of a java.lang.String argument, or None for null.
+fn java_string(value: &Value<dyn Class, dyn Object>) -> Result<Option<std::string::String>, Error> {
+    match value.as_reference()? {
+        Reference::Object(o, _) => match o.as_any().downcast_ref::<natives::string::String>() {
+            Some(s) => Ok(Some(s.backing_string().clone())),
+            None => Err(Error::IncorrectReferenceType(Opcode::NativeMethod)),
+        },
+        Reference::Null => Ok(None),
+        _ => Err(Error::IncorrectReferenceType(Opcode::NativeMethod)),
+    }
+}
+
+impl Object for Lookup {
+    fn new(_current_method_class: Option<Rc<dyn Class>>, _class_index: Option<u16>, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call Lookup::new")
+    }
+    fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call Lookup::new_with_name")
+    }
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::GETFIELD))
+    }
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
+    }
+    fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo)
+    -> Result<bool, Error> {
+        let cm_class_file = current_method_class.get_class_file();
+        let name = cm_class_file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = cm_class_file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let num_args = method.num_args(&cm_class_file)?;
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+        let args = match frame.op_stack.len().checked_sub(num_args + 1) {
+            Some(index) => frame.op_stack.split_off(index + 1),
+            None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+        };
+        frame.op_stack.pop();
+        let mut was_natively_executed = true;
+        let result = match (name, desc) {
+            ("lookupClass", "()Ljava/lang/Class;") => {
+                Some(Value::Reference(jvm.class_mirror_from_name(self.lookup_class.get_class_file().name())?))
+            },
+            ("findStatic", "(Ljava/lang/Class;Ljava/lang/String;Ljava/lang/invoke/MethodType;)Ljava/lang/invoke/MethodHandle;") => {
+                let Some(name) = java_string(&args[1])? else {
+                    return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]);
+                };
+                Some(self.find(jvm, ReferenceKind::RefInvokeStatic, &args[0], &name, &args[2])?)
+            },
+            ("findVirtual", "(Ljava/lang/Class;Ljava/lang/String;Ljava/lang/invoke/MethodType;)Ljava/lang/invoke/MethodHandle;") => {
+                let Some(name) = java_string(&args[1])? else {
+                    return jvm.throw_exception("java/lang/NullPointerException", "()V", vec![]);
+                };
+                Some(self.find(jvm, ReferenceKind::RefInvokeVirtual, &args[0], &name, &args[2])?)
+            },
+            ("findConstructor", "(Ljava/lang/Class;Ljava/lang/invoke/MethodType;)Ljava/lang/invoke/MethodHandle;") => {
+                Some(self.find(jvm, ReferenceKind::RefNewInvokeSpecial, &args[0], "<init>", &args[1])?)
+            },
+            // A lookup with full privileges prints as just its class.
+            ("toString", "()Ljava/lang/String;") => {
+                let s = natives::string::String::new_from_string(self.lookup_class.get_class_file().name().replace('/', "."), jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            _ => {
+                // do funky stuff
+                eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class MethodHandles.Lookup").red());
+                was_natively_executed = false;
+                if &desc[desc.len() - 1..] != "V" {
+                    // expected to push something onto stack
+                    Some(Value::Reference(Reference::Null))
+                } else {
+                    None
+                }
+            }
+        };
+        if let Some(result) = result {
+            let thread = access_macros::current_thread_mut!(jvm);
+            let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+            frame.op_stack.push(result);
+        }
+        Ok(was_natively_executed)
+    }
+    fn class(&self) -> Rc<dyn Class> {
+        self.class.clone()
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object> {
+        self
+    }
+    fn is_equal(&self, other: &dyn Object) -> bool {
+        std::ptr::eq(self as *const Self as *const u8, other.as_any() as *const dyn Any as *const u8)
+    }
+}
@@ -0,0 +1,88 @@
+use crate::{access_macros, errorcodes::Opcode, frame::Frame, jvm::invoke};
+
+use super::super::*;
+use super::method_type;
+
+// An instance of java.lang.invoke.MethodHandle, wrapping one of our own direct method handles.
+pub struct MethodHandle {
+    handle: invoke::MethodHandle,
+    mh_class: Rc<dyn Class>,
+}
+
+impl MethodHandle {
+    pub fn new_with_handle(handle: invoke::MethodHandle, jvm: &mut JVM) -> Result<Rc<MethodHandle>, Error> {
+        Ok(Rc::new(MethodHandle { handle,
+            mh_class: jvm.resolve_class_reference("java/lang/invoke/MethodHandle")? }))
+    }
+    pub fn handle(&self) -> &invoke::MethodHandle {
+        &self.handle
+    }
+}
+
+impl Object for MethodHandle {
+    fn new(_current_method_class: Option<Rc<dyn Class>>, _class_index: Option<u16>, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call MethodHandle::new")
+    }
+    fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call MethodHandle::new_with_name")
+    }
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::GETFIELD))
+    }
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
+    }
+    fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo)
+    -> Result<bool, Error> {
+        let cm_class_file = current_method_class.get_class_file();
+        let name = cm_class_file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = cm_class_file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let mut was_natively_executed = true;
+        let result = match (name, desc) {
+            ("type", "()Ljava/lang/invoke/MethodType;") => {
+                let t = method_type::MethodType::new_with_descriptor(&self.handle.type_descriptor(), jvm)?;
+                Some(Value::Reference(Reference::Object(t as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            ("toString", "()Ljava/lang/String;") => {
+                let s = format!("MethodHandle{}", method_type::method_type_string(&self.handle.type_descriptor())?);
+                let s = natives::string::String::new_from_string(s, jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            _ => {
+                // do funky stuff
+                eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class MethodHandle").red());
+                was_natively_executed = false;
+                let num_args = method.num_args(&cm_class_file)?;
+                let thread = access_macros::current_thread_mut!(jvm);
+                let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+                let new_len = frame.op_stack.len().saturating_sub(num_args);
+                frame.op_stack.truncate(new_len);
+                if &desc[desc.len() - 1..] != "V" {
+                    // expected to push something onto stack
+                    Some(Value::Reference(Reference::Null))
+                } else {
+                    None
+                }
+            }
+        };
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.pop();
+        if let Some(result) = result {
+            frame.op_stack.push(result);
+        }
+        Ok(was_natively_executed)
+    }
+    fn class(&self) -> Rc<dyn Class> {
+        self.mh_class.clone()
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object> {
+        self
+    }
+    fn is_equal(&self, other: &dyn Object) -> bool {
+        std::ptr::eq(self as *const Self as *const u8, other.as_any() as *const dyn Any as *const u8)
+    }
+}
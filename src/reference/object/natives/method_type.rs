@@ -0,0 +1,114 @@
+use crate::{access_macros, errorcodes::Opcode, frame::Frame, jvm::invoke};
+
+use super::super::*;
+use super::class_mirror;
+
+// An instance of java.lang.invoke.MethodType. We only keep the descriptor, and make Class objects for it when asked.
+pub struct MethodType {
+    descriptor: String,
+    mt_class: Rc<dyn Class>,
+}
+
+impl MethodType {
+    pub fn new_with_descriptor(descriptor: &str, jvm: &mut JVM) -> Result<Rc<MethodType>, Error> {
+        // This checks that the descriptor is well formed.
+        invoke::parameter_descriptors(descriptor)?;
+        Ok(Rc::new(MethodType { descriptor: String::from(descriptor),
+            mt_class: jvm.resolve_class_reference("java/lang/invoke/MethodType")? }))
+    }
+    pub fn descriptor(&self) -> &str {
+        &self.descriptor
+    }
+}
+
+// What MethodType.toString gives, so "(int,String)void" for "(ILjava/lang/String;)V".
+pub fn method_type_string(descriptor: &str) -> Result<String, Error> {
+    let params: Vec<String> = invoke::parameter_descriptors(descriptor)?.into_iter().map(class_mirror::simple_name).collect();
+    let ret = &descriptor[descriptor.rfind(')').unwrap() + 1..];
+    Ok(format!("({}){}", params.join(","), class_mirror::simple_name(ret)))
+}
+
+impl Object for MethodType {
+    fn new(_current_method_class: Option<Rc<dyn Class>>, _class_index: Option<u16>, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call MethodType::new")
+    }
+    fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
+        panic!("No one should ever call MethodType::new_with_name")
+    }
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+        Err(Error::NoSuchFieldError(Opcode::GETFIELD))
+    }
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+        Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
+    }
+    fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo)
+    -> Result<bool, Error> {
+        let cm_class_file = current_method_class.get_class_file();
+        let name = cm_class_file.cp_entry(method.name_index)?.as_utf8()?.as_str();
+        let desc = cm_class_file.cp_entry(method.descriptor_index)?.as_utf8()?.as_str();
+        let mut was_natively_executed = true;
+        let result = match (name, desc) {
+            ("toMethodDescriptorString" | "descriptorString", "()Ljava/lang/String;") => {
+                let s = natives::string::String::new_from_string(self.descriptor.clone(), jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            ("toString", "()Ljava/lang/String;") => {
+                let s = natives::string::String::new_from_string(method_type_string(&self.descriptor)?, jvm)?;
+                Some(Value::Reference(Reference::Object(s as Rc<dyn Object>, Rc::new(Monitor::new()))))
+            },
+            ("parameterCount", "()I") => Some(Value::Int(invoke::parameter_descriptors(&self.descriptor)?.len() as i32)),
+            ("parameterType", "(I)Ljava/lang/Class;") => {
+                let thread = access_macros::current_thread_mut!(jvm);
+                let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+                let index = frame.op_stack.pop().unwrap().to_int()?;
+                let params = invoke::parameter_descriptors(&self.descriptor)?;
+                match params.get(index as usize) {
+                    Some(param) if index >= 0 => Some(Value::Reference(jvm.class_mirror(param)?)),
+                    _ => return Err(Error::ArrayIndexOutOfBoundsException(Opcode::NativeMethod)),
+                }
+            },
+            ("returnType", "()Ljava/lang/Class;") => {
+                let ret = &self.descriptor[self.descriptor.rfind(')').unwrap() + 1..];
+                Some(Value::Reference(jvm.class_mirror(ret)?))
+            },
+            _ => {
+                // do funky stuff
+                eprintln!("{}", format!("Use of unimplemented function: {name}{desc} in class MethodType").red());
+                was_natively_executed = false;
+                let num_args = method.num_args(&cm_class_file)?;
+                let thread = access_macros::current_thread_mut!(jvm);
+                let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+                let new_len = frame.op_stack.len().saturating_sub(num_args);
+                frame.op_stack.truncate(new_len);
+                if &desc[desc.len() - 1..] != "V" {
+                    // expected to push something onto stack
+                    Some(Value::Reference(Reference::Null))
+                } else {
+                    None
+                }
+            }
+        };
+        let thread = access_macros::current_thread_mut!(jvm);
+        let frame: &mut Frame = access_macros::current_frame_mut!(thread);
+        frame.op_stack.pop();
+        if let Some(result) = result {
+            frame.op_stack.push(result);
+        }
+        Ok(was_natively_executed)
+    }
+    fn class(&self) -> Rc<dyn Class> {
+        self.mt_class.clone()
+    }
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+    fn into_any_rc(self: Rc<Self>) -> Rc<dyn Object> {
+        self
+    }
+    fn is_equal(&self, other: &dyn Object) -> bool {
+        match other.as_any().downcast_ref::<Self>() {
+            None => false,
+            Some(other) => self.descriptor == other.descriptor,
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use crate::{errorcodes::Opcode, access_macros, frame::Frame};
+use crate::{errorcodes::Opcode, access_macros, frame::Frame, jvm::invoke};
 
 use super::super::*;
 
@@ -27,23 +27,21 @@ impl PrintStreamInner {
         Ok(())
     } 
     pub fn print_double(&self, d: &f64) -> Result<(), Error> {
-        //println!("Printing double with value {}", d);
-        let d = *d;
-        if d == d.round() {
-            match self {
-                PrintStreamInner::Stdout => print!("{d:.1}"),
-                PrintStreamInner::Stderr => eprint!("{d:.1}"),
-                PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
-            }
+        let d = invoke::format_double(*d);
+        match self {
+            PrintStreamInner::Stdout => print!("{d}"),
+            PrintStreamInner::Stderr => eprint!("{d}"),
+            PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
         }
-        else {
-            match self {
-                PrintStreamInner::Stdout => print!("{d}"),
-                PrintStreamInner::Stderr => eprint!("{d}"),
-                PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
-            }
+        Ok(())
+    } 
+    pub fn print_float(&self, f: &f32) -> Result<(), Error> {
+        let f = invoke::format_float(*f);
+        match self {
+            PrintStreamInner::Stdout => print!("{f}"),
+            PrintStreamInner::Stderr => eprint!("{f}"),
+            PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
         }
-        
         Ok(())
     } 
     pub fn print_int(&self, i: &i32) -> Result<(), Error> {
@@ -54,6 +52,32 @@ impl PrintStreamInner {
         }
         Ok(())
     } 
+    pub fn print_long(&self, l: &i64) -> Result<(), Error> {
+        match self {
+            PrintStreamInner::Stdout => print!("{l}"),
+            PrintStreamInner::Stderr => eprint!("{l}"),
+            PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
+        }
+        Ok(())
+    } 
+    pub fn print_boolean(&self, z: &i32) -> Result<(), Error> {
+        let z = *z != 0;
+        match self {
+            PrintStreamInner::Stdout => print!("{z}"),
+            PrintStreamInner::Stderr => eprint!("{z}"),
+            PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
+        }
+        Ok(())
+    } 
+    pub fn print_char(&self, c: &i32) -> Result<(), Error> {
+        let c = char::from_u32(*c as u32).unwrap_or(char::REPLACEMENT_CHARACTER);
+        match self {
+            PrintStreamInner::Stdout => print!("{c}"),
+            PrintStreamInner::Stderr => eprint!("{c}"),
+            PrintStreamInner::File(_f) => return Err(Error::Todo(Opcode::NativeMethod)),
+        }
+        Ok(())
+    } 
     pub fn newline(&self) -> Result<(), Error> {
         match self {
             PrintStreamInner::Stdout => println!(),
@@ -79,10 +103,10 @@ impl Object for PrintStream {
     fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self : Sized {
         panic!("No one should ever call PrintStream::new_with_name")
     }
-    fn get_field(&self, _current_method_class: Rc<dyn Class>, _class_index: u16, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
         Err(Error::Todo(Opcode::NativeMethod))
     }
-    fn put_field(&mut self, _current_method_class: Rc<dyn Class>, _class_index: u16, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
         Err(Error::Todo(Opcode::NativeMethod))
     }
     fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo) 
@@ -115,6 +139,34 @@ impl Object for PrintStream {
                 self.inner.print_double(frame.op_stack.pop().unwrap().as_double()?)?;
                 self.inner.newline()?;
             },
+            ("print", "(Z)V") => {
+                self.inner.print_boolean(frame.op_stack.pop().unwrap().as_int()?)?;
+            },
+            ("print", "(C)V") => {
+                self.inner.print_char(frame.op_stack.pop().unwrap().as_int()?)?;
+            },
+            ("print", "(J)V") => {
+                self.inner.print_long(frame.op_stack.pop().unwrap().as_long()?)?;
+            },
+            ("print", "(F)V") => {
+                self.inner.print_float(frame.op_stack.pop().unwrap().as_float()?)?;
+            },
+            ("println", "(Z)V") => {
+                self.inner.print_boolean(frame.op_stack.pop().unwrap().as_int()?)?;
+                self.inner.newline()?;
+            },
+            ("println", "(C)V") => {
+                self.inner.print_char(frame.op_stack.pop().unwrap().as_int()?)?;
+                self.inner.newline()?;
+            },
+            ("println", "(J)V") => {
+                self.inner.print_long(frame.op_stack.pop().unwrap().as_long()?)?;
+                self.inner.newline()?;
+            },
+            ("println", "(F)V") => {
+                self.inner.print_float(frame.op_stack.pop().unwrap().as_float()?)?;
+                self.inner.newline()?;
+            },
             ("println", "()V") => {
                 self.inner.newline()?;
             },
@@ -152,6 +204,16 @@ impl Object for PrintStream {
             None => false
         }
     }
+    fn trace(&self, visit: &mut dyn FnMut(&Reference<dyn Class, dyn Object>)) {
+        if let PrintStreamInner::File(f) = &self.inner {
+            visit(f);
+        }
+    }
+    fn clear_references(&mut self) {
+        if let PrintStreamInner::File(f) = &mut self.inner {
+            *f = Reference::Null;
+        }
+    }
 }
 
 impl PrintStream {
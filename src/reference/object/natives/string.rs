@@ -16,10 +16,10 @@ impl Object for String {
     fn new_with_name(_name: &str, _jvm: &mut JVM) -> Result<Rc<dyn Object>, Error> where Self: Sized {
         panic!("No one should ever call String::new_with_name")
     }
-    fn get_field(&self, _current_method_class: Rc<dyn Class>, _class_index: u16, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
+    fn get_field(&self, _field: &FieldRef, _jvm: &mut JVM) -> Result<Value<dyn Class, dyn Object>, Error> {
         Err(Error::NoSuchFieldError(Opcode::GETFIELD))
     }
-    fn put_field(&mut self, _current_method_class: Rc<dyn Class>, _class_index: u16, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
+    fn put_field(&mut self, _field: &FieldRef, _jvm: &mut JVM, _value: Value<dyn Class, dyn Object>) -> Result<(), Error> {
         Err(Error::NoSuchFieldError(Opcode::PUTFIELD))
     }
     fn exec_method(&mut self, current_method_class: Rc<dyn Class>, jvm: &mut JVM, method: &MethodInfo) 
@@ -36,9 +36,30 @@ impl Object for String {
                 // Nothing to do, because we already init with an empty string.
             },
             ("length", "()I") => {
-                let len = Value::Int(self.s.len().try_into().unwrap());
+                // Java strings are counted in UTF-16 chars, not bytes.
+                let len = Value::Int(self.s.encode_utf16().count().try_into().unwrap());
+                frame.op_stack.pop();
+                popped_self = true;
                 frame.op_stack.push(len);
             }
+            ("hashCode", "()I") => {
+                // Same as String.hashCode, which goes over the UTF-16 chars, so strings that are equal in Java hash the same.
+                let hash = self.s.encode_utf16().fold(0i32, |h, c| h.wrapping_mul(31).wrapping_add(c as i32));
+                frame.op_stack.pop();
+                popped_self = true;
+                frame.op_stack.push(Value::Int(hash));
+            }
+            ("equals", "(Ljava/lang/Object;)Z") => {
+                let equal = match frame.op_stack.pop() {
+                    Some(Value::Reference(Reference::Object(other, _))) => self.is_equal(other.as_ref()),
+                    Some(Value::Reference(_)) => false,
+                    Some(_) => return Err(Error::UnexpectedTypeOnStack(Opcode::MethodInvoke)),
+                    None => return Err(Error::StackUnderflow(Opcode::MethodInvoke)),
+                };
+                frame.op_stack.pop();
+                popped_self = true;
+                frame.op_stack.push(Value::Int(equal as i32));
+            }
             ("toString", "()Ljava/lang/String;") => {
                 // just return self
                 popped_self = true; // lies but it works
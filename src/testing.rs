@@ -47,6 +47,11 @@ pub fn test_chapter_timed(chap: &str, file: &str) {
 }
 
 pub fn test_file(path: &str, file: &str) {
+    test_file_with_args(path, file, &[]);
+}
+
+// Same as test_file, but both JVMs also get args, which have to be options they both take, like -Xmx.
+pub fn test_file_with_args(path: &str, file: &str, args: &[&str]) {
     let mut s = String::from(".");
     if let Ok(news) = std::env::var("JVM_FOLDER_PATH") {
         s = news;
@@ -58,13 +63,101 @@ pub fn test_file(path: &str, file: &str) {
     let true_file = format!("{}/{}.class", path, file);
     let true_file = true_file.as_str();
 
-    let expected = Command::new("java").arg("-cp").arg(path).arg(file).output().expect("Failed to run java file from cli");
+    let expected = Command::new("java").args(args).arg("-cp").arg(path).arg(file).output().expect("Failed to run java file from cli");
 
-    let actual = Command::new("./target/release/cmd").arg("-r").arg(true_file).output().expect("Failed to run jvm");
+    // The class path is there for any other classes in the folder, like nested ones.
+    let actual = Command::new("./target/release/cmd").arg("-r").args(args).arg("-cp").arg(path).arg(true_file).output().expect("Failed to run jvm");
     
     assert_eq!(expected, actual);
 }
 
+// Same as test_file, but only our JVM gets args, for options java doesn't have, like --os-threads.
+pub fn test_file_with_jvm_args(path: &str, file: &str, args: &[&str]) {
+    let mut s = String::from(".");
+    if let Ok(news) = std::env::var("JVM_FOLDER_PATH") {
+        s = news;
+    }
+
+    let path = format!("{}/{}", s, path);
+    let path = path.as_str();
+
+    let true_file = format!("{}/{}.class", path, file);
+    let true_file = true_file.as_str();
+
+    let expected = Command::new("java").arg("-cp").arg(path).arg(file).output().expect("Failed to run java file from cli");
+
+    let actual = Command::new("./target/release/cmd").arg("-r").args(args).arg("-cp").arg(path).arg(true_file).output().expect("Failed to run jvm");
+
+    assert_eq!(expected, actual);
+}
+
+// Same as test_file, but the class path also has entries, which are relative to path and can be jars or end in *.
+pub fn test_file_with_classpath(path: &str, file: &str, entries: &[&str]) {
+    let mut s = String::from(".");
+    if let Ok(news) = std::env::var("JVM_FOLDER_PATH") {
+        s = news;
+    }
+
+    let path = format!("{}/{}", s, path);
+    let path = path.as_str();
+
+    let true_file = format!("{}/{}.class", path, file);
+    let true_file = true_file.as_str();
+
+    let mut class_path = String::from(path);
+    for entry in entries {
+        class_path.push_str(&format!(":{}/{}", path, entry));
+    }
+
+    let expected = Command::new("java").arg("-cp").arg(&class_path).arg(file).output().expect("Failed to run java file from cli");
+
+    let actual = Command::new("./target/release/cmd").arg("-r").arg("-cp").arg(&class_path).arg(true_file).output().expect("Failed to run jvm");
+
+    assert_eq!(expected, actual);
+}
+
+// Same as test_file, but we load the JDK's classes from the same installation java uses, instead of the extracted ones.
+pub fn test_file_with_jdk(path: &str, file: &str) {
+    let mut s = String::from(".");
+    if let Ok(news) = std::env::var("JVM_FOLDER_PATH") {
+        s = news;
+    }
+
+    let path = format!("{}/{}", s, path);
+    let path = path.as_str();
+
+    let true_file = format!("{}/{}.class", path, file);
+    let true_file = true_file.as_str();
+
+    // java prints its settings to stderr, and java.home is where it was installed.
+    let settings = Command::new("java").arg("-XshowSettings:properties").arg("-version").output().expect("Failed to run java from cli");
+    let settings = String::from_utf8_lossy(&settings.stderr);
+    let java_home = settings.lines().find_map(|l| l.trim().strip_prefix("java.home = ")).expect("java didn't say where it's installed");
+
+    let expected = Command::new("java").arg("-cp").arg(path).arg(file).output().expect("Failed to run java file from cli");
+
+    let actual = Command::new("./target/release/cmd").arg("-r").arg("--jdk").arg(java_home).arg("-cp").arg(path).arg(true_file).output().expect("Failed to run jvm");
+
+    assert_eq!(expected, actual);
+}
+
+// Runs a jar with both JVMs, the way `java -jar` does, so the main class comes from the manifest.
+pub fn test_jar(path: &str, jar: &str) {
+    let mut s = String::from(".");
+    if let Ok(news) = std::env::var("JVM_FOLDER_PATH") {
+        s = news;
+    }
+
+    let true_file = format!("{}/{}/{}.jar", s, path, jar);
+    let true_file = true_file.as_str();
+
+    let expected = Command::new("java").arg("-jar").arg(true_file).output().expect("Failed to run jar from cli");
+
+    let actual = Command::new("./target/release/cmd").arg("-r").arg("-j").arg(true_file).output().expect("Failed to run jvm");
+
+    assert_eq!(expected, actual);
+}
+
 pub fn test_file_timed(path: &str, file: &str) {
     let mut s = String::from(".");
     if let Ok(news) = std::env::var("JVM_FOLDER_PATH") {
@@ -82,7 +175,7 @@ pub fn test_file_timed(path: &str, file: &str) {
     let time_after_java = Instant::now(); 
 
     let time_before_jvm = Instant::now();
-    let actual = Command::new("./target/release/cmd").arg("-r").arg(true_file).output().expect("Failed to run jvm");
+    let actual = Command::new("./target/release/cmd").arg("-r").arg("-cp").arg(path).arg(true_file).output().expect("Failed to run jvm");
     let time_after_jvm = Instant::now(); 
 
     assert_eq!(expected, actual);
@@ -165,7 +258,254 @@ mod book {
     }
 }
 
+mod features {
+    use super::*;
+    // bastore and baload used to reject byte arrays, lastore read its index as a long, and boolean stores were inverted.
+    mod arrays {
+        use super::*;
+
+        #[test]
+        fn byte_array_store() {
+            test_file("features/arrays", "ByteArrayStore");
+        }
+        #[test]
+        fn byte_array_load() {
+            test_file("features/arrays", "ByteArrayLoad");
+        }
+        #[test]
+        fn boolean_array_store() {
+            test_file("features/arrays", "BooleanArrayStore");
+        }
+        #[test]
+        fn long_array_store() {
+            test_file("features/arrays", "LongArrayStore");
+        }
+    }
+    mod init {
+        use super::*;
+
+        // The order static initializers run in, and ExceptionInInitializerError then NoClassDefFoundError when one throws.
+        #[test]
+        fn clinit_order_and_errors() {
+            test_file("features/init", "Init");
+        }
+    }
+    mod jar {
+        use super::*;
+
+        // Hello.jar is built from features/jar/src, with app.Main as its Main-Class.
+        #[test]
+        fn main_class_from_manifest() {
+            test_jar("features/jar", "Hello");
+        }
+
+        // Loop.jar is built from features/jar/src/loop, and its manifest's Class-Path leads back to itself.
+        #[test]
+        fn class_path_naming_itself() {
+            test_jar("features/jar", "Loop");
+        }
+    }
+    mod classpath {
+        use super::*;
+
+        // Main needs a class from a jar that's only found through libs/*, and one from a second directory.
+        // libs/shapes.jar is built from features/classpath/src.
+        #[test]
+        fn directories_and_jars() {
+            test_file_with_classpath("features/classpath", "Main", &["libs/*", "extra"]);
+        }
+    }
+    mod jdk {
+        use super::*;
+
+        #[test]
+        fn classes_from_jimage() {
+            test_file_with_jdk("features/jdk", "UsesJdk");
+        }
+    }
+    // Every instruction goes through Op, so these cover each group of opcodes, compared against java.
+    mod opcodes {
+        use super::*;
+
+        #[test]
+        fn constants() {
+            test_file("features/opcodes", "Constants");
+        }
+        #[test]
+        fn loads_and_stores() {
+            test_file("features/opcodes", "LoadsStores");
+        }
+        #[test]
+        fn math() {
+            test_file("features/opcodes", "Maths");
+        }
+        #[test]
+        fn conversions() {
+            test_file("features/opcodes", "Conversions");
+        }
+        #[test]
+        fn comparisons() {
+            test_file("features/opcodes", "Comparisons");
+        }
+        #[test]
+        fn control() {
+            test_file("features/opcodes", "Control");
+        }
+        #[test]
+        fn stack() {
+            test_file("features/opcodes", "Stack");
+        }
+        // getfield and putfield on null, both before and after they get quickened.
+        #[test]
+        fn null_fields() {
+            test_file("features/opcodes", "NullFields");
+        }
+        #[test]
+        fn references() {
+            test_file("features/opcodes", "References");
+        }
+        #[test]
+        fn extended() {
+            test_file("features/opcodes", "Extended");
+        }
+    }
+    mod ldc {
+        use super::*;
+
+        // Two ldcs of the same MethodHandle, MethodType or dynamically-computed constant give back the same object.
+        // Ldcs is generated by features/ldc/ldcs.py.
+        #[test]
+        fn constants_resolve_once_per_entry() {
+            test_file("features/ldc", "Ldcs");
+        }
+    }
+    mod dispatch {
+        use super::*;
+
+        // invokeinterface, default methods and which one is the most specific, overrides, super calls and private methods.
+        #[test]
+        fn virtual_and_interface_calls() {
+            test_file("features/dispatch", "Dispatch");
+        }
+        // Object and Runnable methods called on a subclass of Thread, which has no vtable to inherit.
+        #[test]
+        fn native_superclass() {
+            test_file("features/dispatch", "NativeSuper");
+        }
+    }
+    mod indy {
+        use super::*;
+
+        // Lambdas that capture, method and constructor references, and default methods that call them.
+        #[test]
+        fn lambdas() {
+            test_file("features/indy", "Lambdas");
+        }
+        // Concatenation of every type, and floats that have to print as floats.
+        #[test]
+        fn concat() {
+            test_file("features/indy", "Concat");
+        }
+        // Call sites and condy constants from a bootstrap method of our own, and constants that need unboxing.
+        // Indys is generated by features/indy/indys.py.
+        #[test]
+        fn own_bootstraps() {
+            test_file("features/indy", "Indys");
+        }
+        // Records get toString, hashCode and equals from ObjectMethods.
+        #[test]
+        fn records() {
+            test_file("features/indy", "Records");
+        }
+        // Method references that need their arguments or return value boxed, unboxed or widened.
+        #[test]
+        fn method_refs() {
+            test_file("features/indy", "MethodRefs");
+        }
+        // Pattern matching switches are still a preview in 17, so this needs a java that's 17 too.
+        #[test]
+        fn type_switch() {
+            test_file_with_args("features/indy", "TypeSwitch", &["--enable-preview"]);
+        }
+    }
+    mod threads {
+        use super::*;
+
+        // Synchronized methods, static ones and blocks, from several threads, with a monitor that gets reentered.
+        #[test]
+        fn synchronized() {
+            test_file("features/threads", "Sync");
+        }
+        // Starting, joining, sleeping and interrupting threads.
+        #[test]
+        fn start_join_sleep_interrupt() {
+            test_file("features/threads", "Threads");
+        }
+        // A producer and consumer using wait and notifyAll, a timed wait, and notify without the monitor.
+        #[test]
+        fn wait_and_notify() {
+            test_file("features/threads", "WaitNotify");
+        }
+        // The same three, with each thread on an OS thread of its own.
+        #[test]
+        fn synchronized_on_os_threads() {
+            test_file_with_jvm_args("features/threads", "Sync", &["--os-threads"]);
+        }
+        #[test]
+        fn start_join_sleep_interrupt_on_os_threads() {
+            test_file_with_jvm_args("features/threads", "Threads", &["--os-threads"]);
+        }
+        #[test]
+        fn wait_and_notify_on_os_threads() {
+            test_file_with_jvm_args("features/threads", "WaitNotify", &["--os-threads"]);
+        }
+    }
+    mod gc {
+        use super::*;
 
+        // Garbage cycles that go through a lambda's captured values, which only get freed if the collector follows them.
+        #[test]
+        fn lambda_cycles() {
+            test_file_with_args("features/gc", "LambdaCycles", &["-Xmx16m"]);
+        }
+    }
+
+    mod limits {
+        use super::*;
+
+        // Filling the heap past -Xmx throws OutOfMemoryError, and the program can catch it and keep going.
+        #[test]
+        fn out_of_memory() {
+            test_file_with_args("features/limits", "OutOfMemory", &["-Xmx16m"]);
+        }
+
+        // Recursing past -Xss throws StackOverflowError, and the thread can still call methods once it has caught it.
+        #[test]
+        fn stack_overflow() {
+            test_file_with_args("features/limits", "StackOverflow", &["-Xss1m"]);
+        }
+    }
+
+    mod identity {
+        use super::*;
+
+        // The default hashCode and toString, and classes that override them.
+        #[test]
+        fn hash_code_and_to_string() {
+            test_file("features/identity", "Identity");
+        }
+    }
+
+    mod clone {
+        use super::*;
+
+        // Shallow clones of primitive, reference and nested arrays, of a Cloneable object, and the exception for one that isn't.
+        #[test]
+        fn arrays_and_objects() {
+            test_file("features/clone", "Clones");
+        }
+    }
+}
 
 mod speed {
     use super::*;
@@ -1,27 +1,60 @@
 use std::rc::Rc;
+use std::time::Instant;
 use std::vec::Vec;
 
 use crate::class::Class;
 use crate::errorcodes::Error;
 use crate::frame::Frame;
-use crate::reference::Monitor;
+use crate::reference::{Monitor, Reference};
 use crate::reference::object::Object;
 use crate::value::{Value, VarValue};
 
 
+// What a thread is doing besides running, which the scheduler checks before giving it a turn.
+// Sleep, join and wait have already returned by the time the thread gets here, so it picks up after the invoke once it wakes up.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum ThreadState {
+    Runnable,
+    // Thread.yield, which gives up the rest of this turn.
+    Yielding,
+    Sleeping(Instant),
+    // Waiting for the thread with this id to finish, or until the deadline if there is one.
+    Joining(usize, Option<Instant>),
+    // In Object.wait until it's notified, or until the deadline if there is one. See Thread::waiting_on.
+    Waiting(Option<Instant>),
+}
+
 // FIXME: Because we access the length so much, should we just store it?
 
 pub struct Thread {
+    // Threads move around in JVM::m_threads as others finish, so monitors use this to know who owns them.
+    pub id: usize,
+    // The java.lang.Thread for this thread. The main thread only gets one once Thread.currentThread asks for it.
+    pub object: Reference<dyn Class, dyn Object>,
+    pub is_daemon: bool,
+    pub state: ThreadState,
+    pub interrupted: bool,
     pub m_stack: Vec<Frame>,
-    // This is a bad name, but I can't think of a better one right now.
-    // This could be an unsafe mutable pointer, because we only modify if we're the only owner, and we (will) modify it atomically.
-    // However, for now, this is easier. 
+    // How much memory the frames in m_stack take up, which -Xss limits. Frames have to go through push_frame and pop_frame to keep it right.
+    pub stack_size: usize,
+    // The monitor this thread is blocked on, if any. The instruction that tried to enter it stays where it is, 
+    // and runs again once the monitor is free.
     pub current_monitor: Option<Rc<Monitor>>,
+    // The monitor this thread is in Object.wait on, and how many times it had entered it, to put back once it wakes up.
+    pub waiting_on: Option<(Rc<Monitor>, usize)>,
     pub next_instruction_is_wide: bool,
 }
 impl Thread {
     pub fn new() -> Thread {
-        Thread {m_stack: Vec::new(), current_monitor: None, next_instruction_is_wide: false }
+        Thread::new_with_object(0, Reference::Null, false)
+    }
+    pub fn new_with_object(id: usize, object: Reference<dyn Class, dyn Object>, is_daemon: bool) -> Thread {
+        Thread { id, object, is_daemon, state: ThreadState::Runnable, interrupted: false, 
+            m_stack: Vec::new(), stack_size: 0, current_monitor: None, waiting_on: None, next_instruction_is_wide: false }
+    }
+    // Whether the scheduler should leave this thread alone for now. See JVM::wake_thread.
+    pub fn is_blocked(&self) -> bool {
+        self.current_monitor.is_some() || self.state != ThreadState::Runnable
     }
     pub fn clone_current_frame(&mut self) -> Frame {
         self.current_frame().clone()
@@ -35,13 +68,17 @@ impl Thread {
         &mut self.m_stack[length -1]
     }
     pub fn pop_frame(&mut self) -> Option<Frame> {
-        self.m_stack.pop()
+        let frame = self.m_stack.pop()?;
+        self.stack_size -= frame.size();
+        Some(frame)
     }
     pub fn replace_current_frame(&mut self, frame: Frame) {
         let length = self.m_stack.len();
+        self.stack_size = self.stack_size - self.m_stack[length - 1].size() + frame.size();
         self.m_stack[length - 1] = frame;
     }
     pub fn push_frame(&mut self, frame: Frame) {
+        self.stack_size += frame.size();
         self.m_stack.push(frame);
     }
     pub fn pc(&self) -> usize {